@@ -1,10 +1,10 @@
-use crate::prelude::SystemId;
+use crate::prelude::{Id, SystemId};
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
-pub struct BlockerId(SystemId);
+pub struct BlockerId(Id);
 
 impl<T> From<T> for BlockerId 
-where T: Into<SystemId>
+where T: Into<Id>
 {
     fn from(value: T) -> Self {
         Self(value.into())
@@ -12,11 +12,18 @@ where T: Into<SystemId>
 }
 
 impl BlockerId {
-    pub fn get_blocks(&self) -> &SystemId {
-        &self.0
+    /// The blocker named after the system it blocks
+    pub fn of_system(system_id: &SystemId) -> Self {
+        Self(system_id.clone().into_id())
     }
 
-    pub fn into(self) -> SystemId {
+    pub fn into_id(self) -> Id {
         self.0
     }
 }
+
+impl std::fmt::Display for BlockerId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
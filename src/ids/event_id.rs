@@ -1,4 +1,4 @@
-use crate::ids::Id;
+use crate::{ids::Id, prelude::{RunId, SystemId}};
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct EventId(Id);
@@ -15,4 +15,39 @@ impl EventId {
     pub fn get_id(&self) -> &Id {
         &self.0
     }
+
+    /// The id raised when typed events of `T` are current, so id-only criteria can react to them
+    pub fn of<T: ?Sized>() -> Self {
+        Self::from(std::any::type_name::<T>())
+    }
+
+    /// Emitted when the system is cancelled for running past its timeout
+    pub fn timed_out(system_id: &SystemId) -> Self {
+        Self::from(format!("{system_id}-TimedOut"))
+    }
+
+    /// Emitted when a background system is cancelled, see `BackgroundTasks::cancel`
+    pub fn cancelled(system_id: &SystemId) -> Self {
+        Self::from(format!("{system_id}-Cancelled"))
+    }
+
+    /// Emitted when the system returns `SystemResult::Error`
+    pub fn errored(system_id: &SystemId) -> Self {
+        Self::from(format!("{system_id}-Errored"))
+    }
+
+    /// Emitted when an executable run fails, see `FailedExecutables`
+    pub fn executable_failed() -> Self {
+        Self::from("Executable-Failed")
+    }
+
+    /// Marks the stage of a single run as complete, see `BufferedExecutable::completed`
+    pub fn completed(executable_label: &str, run_id: RunId) -> Self {
+        Self::from(format!("{executable_label}-Completed-{run_id}"))
+    }
+
+    /// Marks the stage of a single run as failed, see `BufferedExecutable::failed`
+    pub fn failed(executable_label: &str, run_id: RunId) -> Self {
+        Self::from(format!("{executable_label}-Failed-{run_id}"))
+    }
 }
@@ -0,0 +1,18 @@
+use crate::prelude::Id;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct LoopId(Id);
+
+impl<T> From<T> for LoopId 
+where T: Into<Id>
+{
+    fn from(value: T) -> Self {
+        Self(value.into())
+    }
+}
+
+impl std::fmt::Display for LoopId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -2,6 +2,9 @@ pub mod system_id;
 pub mod program_id;
 pub mod event_id;
 pub mod blocker_id;
+pub mod schedule_id;
+pub mod loop_id;
+pub mod run_id;
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
 pub struct Id(String);
@@ -12,4 +15,10 @@ where T: Into<String>
     fn from(value: T) -> Self {
         Self(value.into())
     }
+}
+
+impl std::fmt::Display for Id {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,18 @@
+use crate::prelude::Id;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct ScheduleId(Id);
+
+impl<T> From<T> for ScheduleId 
+where T: Into<Id>
+{
+    fn from(value: T) -> Self {
+        Self(value.into())
+    }
+}
+
+impl std::fmt::Display for ScheduleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
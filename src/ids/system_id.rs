@@ -15,4 +15,10 @@ impl SystemId {
     pub fn into_id(self) -> Id {
         self.0
     }
+}
+
+impl std::fmt::Display for SystemId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,78 @@
+use std::{ops::Deref, sync::Arc};
+
+use crate::prelude::{AccessDropper, AccessMap, BackgroundTasks, DeAccessResolver, Injection, MemoryDomain, MemoryTarget, ReservationAccessMap, ResolveError, ResourceId, Shared, SystemId, TaskSignal};
+
+/// The `TaskSignal` of this background system, to report progress & check for cancellation
+pub struct TaskHandle {
+    signal: TaskSignal,
+    dropper: DeAccessResolver,
+}
+
+impl Deref for TaskHandle {
+    type Target = TaskSignal;
+
+    fn deref(&self) -> &Self::Target {
+        &self.signal
+    }
+}
+
+impl AccessDropper for TaskHandle {
+    fn access_dropper(&self) -> &DeAccessResolver {
+        &self.dropper
+    }
+}
+
+impl Injection for TaskHandle {
+    type Item<'new> = TaskHandle;
+
+    fn failed_message() -> String {
+        "Expected Background Task: `BackgroundTasks`".to_string()
+    }
+
+    fn create_access_map() -> AccessMap {
+        Shared::<BackgroundTasks>::create_access_map()
+    }
+
+    fn resolve_accesses(access_map: &mut AccessMap, system_id: Option<&SystemId>, _resource_id: Option<ResourceId>) {
+        Shared::<BackgroundTasks>::resolve_accesses(access_map, system_id, None);
+    }
+
+    fn select_memory_target() -> MemoryTarget {
+        MemoryTarget::Global
+    }
+
+    fn retrieve<'a>(memory_domain: &'a Arc<MemoryDomain>, _resource_id: Option<&ResourceId>, system_id: Option<&SystemId>) -> Result<Self::Item<'a>, ResolveError> {
+        let system_id = system_id.ok_or(ResolveError::NotInBackground)?;
+        let background_tasks = Shared::<BackgroundTasks>::retrieve(memory_domain, None, Some(system_id))?;
+
+        // Cloned so the system doesn't hold `BackgroundTasks` while it runs
+        let signal = background_tasks.get(system_id).ok_or(ResolveError::NotInBackground)?.signal().clone();
+        let dropper = DeAccessResolver::new(Arc::clone(memory_domain), AccessMap::Heap(ReservationAccessMap::default()));
+
+        Ok(TaskHandle { signal, dropper })
+    }
+}
+
+#[cfg(test)]
+mod background_tests {
+    use std::time::Instant;
+
+    use crate::prelude::{BackgroundTasks, Memory, Progress, Shared};
+
+    use super::*;
+
+    #[test]
+    fn resolves_the_systems_task() {
+        let memory = Memory::new();
+        assert!(memory.insert(None, None, None, BackgroundTasks::default()).unwrap().is_ok());
+
+        let foo = SystemId::from("Foo");
+        assert_eq!(memory.resolve::<TaskHandle>(None, None, Some(&foo), None).unwrap().err(), Some(ResolveError::NotInBackground));
+
+        memory.resolve::<Shared<BackgroundTasks>>(None, None, None, None).unwrap().unwrap().start(foo.clone(), 0, Instant::now());
+        memory.resolve::<TaskHandle>(None, None, Some(&foo), None).unwrap().unwrap().report(Progress::new(0.5));
+
+        let background_tasks = memory.resolve::<Shared<BackgroundTasks>>(None, None, None, None).unwrap().unwrap();
+        assert_eq!(background_tasks.get(&foo).unwrap().progress(), Some(Progress::new(0.5)));
+    }
+}
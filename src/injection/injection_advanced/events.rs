@@ -0,0 +1,135 @@
+use std::{any::type_name, sync::Arc};
+
+use crate::prelude::{AccessDropper, AccessMap, CurrentTypedEvents, DeAccessResolver, Injection, MemoryDomain, MemoryTarget, NextTypedEvents, ResolveError, ResourceId, Shared, SystemId, Unique};
+
+/// Sends `T` events, readable by `EventReader<T>` next tick
+pub struct EventWriter<'a, T: 'static> {
+    events: Unique<'a, NextTypedEvents<T>>,
+}
+
+impl<T: 'static> EventWriter<'_, T> {
+    pub fn send(&mut self, event: T) {
+        self.events.send(event);
+    }
+
+    pub fn send_batch(&mut self, events: impl IntoIterator<Item = T>) {
+        self.events.extend(events);
+    }
+
+    /// Sent this tick so far
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+impl<T: 'static> AccessDropper for EventWriter<'_, T> {
+    fn access_dropper(&self) -> &DeAccessResolver {
+        self.events.access_dropper()
+    }
+}
+
+impl<T: 'static> Injection for EventWriter<'_, T> {
+    type Item<'new> = EventWriter<'new, T>;
+
+    fn failed_message() -> String {
+        format!("Expected Event: `{}`. Register it with `EventManager::register_event`", type_name::<T>())
+    }
+
+    fn create_access_map() -> AccessMap {
+        Unique::<NextTypedEvents<T>>::create_access_map()
+    }
+
+    fn resolve_accesses(access_map: &mut AccessMap, system_id: Option<&SystemId>, resource_id: Option<ResourceId>) {
+        Unique::<NextTypedEvents<T>>::resolve_accesses(access_map, system_id, resource_id);
+    }
+
+    fn select_memory_target() -> MemoryTarget {
+        MemoryTarget::Global
+    }
+
+    fn retrieve<'a>(memory_domain: &'a Arc<MemoryDomain>, resource_id: Option<&ResourceId>, system_id: Option<&SystemId>) -> Result<Self::Item<'a>, ResolveError> {
+        Ok(EventWriter { events: Unique::<NextTypedEvents<T>>::retrieve(memory_domain, resource_id, system_id)? })
+    }
+}
+
+/// Reads the `T` events sent last tick
+pub struct EventReader<'a, T: 'static> {
+    events: Shared<'a, CurrentTypedEvents<T>>,
+}
+
+impl<T: 'static> EventReader<'_, T> {
+    /// In the order they were sent
+    pub fn read(&self) -> impl Iterator<Item = &T> {
+        self.events.read()
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+impl<T: 'static> AccessDropper for EventReader<'_, T> {
+    fn access_dropper(&self) -> &DeAccessResolver {
+        self.events.access_dropper()
+    }
+}
+
+impl<T: 'static> Injection for EventReader<'_, T> {
+    type Item<'new> = EventReader<'new, T>;
+
+    fn failed_message() -> String {
+        format!("Expected Event: `{}`. Register it with `EventManager::register_event`", type_name::<T>())
+    }
+
+    fn create_access_map() -> AccessMap {
+        Shared::<CurrentTypedEvents<T>>::create_access_map()
+    }
+
+    fn resolve_accesses(access_map: &mut AccessMap, system_id: Option<&SystemId>, resource_id: Option<ResourceId>) {
+        Shared::<CurrentTypedEvents<T>>::resolve_accesses(access_map, system_id, resource_id);
+    }
+
+    fn select_memory_target() -> MemoryTarget {
+        MemoryTarget::Global
+    }
+
+    fn retrieve<'a>(memory_domain: &'a Arc<MemoryDomain>, resource_id: Option<&ResourceId>, system_id: Option<&SystemId>) -> Result<Self::Item<'a>, ResolveError> {
+        Ok(EventReader { events: Shared::<CurrentTypedEvents<T>>::retrieve(memory_domain, resource_id, system_id)? })
+    }
+}
+
+#[cfg(test)]
+mod events_tests {
+    use crate::prelude::Memory;
+
+    use super::*;
+
+    #[test]
+    fn writer_and_reader_do_not_alias() {
+        let memory = Memory::new();
+        assert!(memory.insert(None, None, None, NextTypedEvents::<i32>::default()).unwrap().is_ok());
+        assert!(memory.insert(None, None, None, CurrentTypedEvents::<i32>::default()).unwrap().is_ok());
+
+        let mut writer = memory.resolve::<EventWriter<i32>>(None, None, None, None).unwrap().unwrap();
+        let reader = memory.resolve::<EventReader<i32>>(None, None, None, None).unwrap().unwrap();
+
+        writer.send(1);
+        writer.send_batch([2, 3]);
+        assert_eq!(writer.len(), 3);
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn missing_event_fails() {
+        let memory = Memory::new();
+        assert!(memory.resolve::<EventReader<i32>>(None, None, None, None).unwrap().is_err());
+    }
+}
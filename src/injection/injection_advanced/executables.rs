@@ -0,0 +1,468 @@
+use std::{any::type_name, marker::PhantomData, ops::Deref, sync::Arc};
+
+use crate::prelude::{AccessDropper, AccessMap, BufferedExecutable, DeAccessResolver, EntityId, ExecutableBuffer, ExecutableInputs, ExecutableMessage, ExecutableOutputs, Injection, MemoryDomain, MemoryTarget, ReservationAccessMap, ResolveError, ResourceId, RunId, Shared, SystemId, TickAccumulator, Unique};
+
+/// The stages addressed to `system_id` that were triggered this tick, oldest run first, see `ExecutableBuffer::claim`
+fn addressed_this_tick(memory_domain: &Arc<MemoryDomain>, system_id: Option<&SystemId>) -> Result<Vec<BufferedExecutable>, ResolveError> {
+    let system_id = system_id.ok_or(ResolveError::NotAddressed)?;
+    let tick = Shared::<TickAccumulator>::retrieve(memory_domain, None, Some(system_id))?.load();
+    let buffer = Shared::<ExecutableBuffer>::retrieve(memory_domain, None, Some(system_id))?;
+
+    // Cloned so a background system doesn't hold the buffer past the tick
+    Ok(buffer.addressed(system_id).filter(|buffered_executable| buffered_executable.tick == tick).cloned().collect())
+}
+
+/// The single resource `source` carries
+fn single_resource(source: &ExecutableMessage) -> Result<&ResourceId, ResolveError> {
+    match source.resource_ids().as_slice() {
+        [resource_id] => Ok(resource_id),
+        [] => Err(ResolveError::NotAddressed),
+        _ => Err(ResolveError::Joined),
+    }
+}
+
+/// Reads the resources given to the stages addressed to this system (`Executable::with_system`) that were triggered this tick,
+/// one per run, see `ExecutableInputs`. Joined stages are read through `ExecutableWork::read`
+pub struct ExecutableInput<'a, T: 'static> {
+    inputs: Shared<'a, ExecutableInputs>,
+    stages: Vec<(BufferedExecutable, ResourceId)>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static> ExecutableInput<'_, T> {
+    /// Oldest run first
+    pub fn iter(&self) -> impl Iterator<Item = (&BufferedExecutable, &T)> {
+        self.stages.iter().map(|(stage, resource_id)| (stage, self.inputs.get(resource_id).expect("Checked when retrieved")))
+    }
+
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+}
+
+impl<T: 'static> AccessDropper for ExecutableInput<'_, T> {
+    fn access_dropper(&self) -> &DeAccessResolver {
+        self.inputs.access_dropper()
+    }
+}
+
+impl<T: 'static> Injection for ExecutableInput<'_, T> {
+    type Item<'new> = ExecutableInput<'new, T>;
+
+    fn failed_message() -> String {
+        format!("Expected Executable Input: `{}`", type_name::<T>())
+    }
+
+    fn create_access_map() -> AccessMap {
+        Shared::<ExecutableBuffer>::create_access_map()
+    }
+
+    fn resolve_accesses(access_map: &mut AccessMap, system_id: Option<&SystemId>, _resource_id: Option<ResourceId>) {
+        Shared::<ExecutableBuffer>::resolve_accesses(access_map, system_id, None);
+        Shared::<TickAccumulator>::resolve_accesses(access_map, system_id, None);
+        Shared::<ExecutableInputs>::resolve_accesses(access_map, system_id, None);
+    }
+
+    fn select_memory_target() -> MemoryTarget {
+        MemoryTarget::Global
+    }
+
+    fn retrieve<'a>(memory_domain: &'a Arc<MemoryDomain>, _resource_id: Option<&ResourceId>, system_id: Option<&SystemId>) -> Result<Self::Item<'a>, ResolveError> {
+        let work = addressed_this_tick(memory_domain, system_id)?;
+        if work.is_empty() {
+            return Err(ResolveError::NotAddressed);
+        }
+
+        let inputs = Shared::<ExecutableInputs>::retrieve(memory_domain, None, system_id)?;
+        let mut stages = Vec::with_capacity(work.len());
+        for stage in work {
+            let resource_id = single_resource(&stage.source)?.clone();
+            if inputs.get::<T>(&resource_id).is_none() {
+                return Err(ResolveError::NoResource(resource_id));
+            }
+
+            stages.push((stage, resource_id));
+        }
+
+        Ok(ExecutableInput { inputs, stages, _marker: PhantomData })
+    }
+}
+
+/// Writes the resources read as the inputs of the next stages, one per run addressed to this system this tick,
+/// see `ExecutableInput` & `ExecutableOutputs`
+pub struct ExecutableOutput<'a, T: 'static> {
+    outputs: Unique<'a, ExecutableOutputs>,
+    stages: Vec<BufferedExecutable>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static> ExecutableOutput<'_, T> {
+    /// Oldest run first
+    pub fn iter(&self) -> impl Iterator<Item = &BufferedExecutable> {
+        self.stages.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Inserted into memory next tick, before the stage completes
+    ///
+    /// false: The stage of `run_id` isn't addressed to this system this tick or doesn't output a resource
+    pub fn write(&mut self, run_id: RunId, value: T) -> bool {
+        let target = self.stages.iter().find(|stage| stage.run_id == run_id).map(|stage| &stage.target);
+        let Some(ExecutableMessage::ResourceId(resource_id)) = target else {
+            return false;
+        };
+
+        self.outputs.write(resource_id.clone(), value);
+        true
+    }
+}
+
+impl<T: 'static> AccessDropper for ExecutableOutput<'_, T> {
+    fn access_dropper(&self) -> &DeAccessResolver {
+        self.outputs.access_dropper()
+    }
+}
+
+impl<T: 'static> Injection for ExecutableOutput<'_, T> {
+    type Item<'new> = ExecutableOutput<'new, T>;
+
+    fn failed_message() -> String {
+        format!("Expected Executable Output: `{}`", type_name::<T>())
+    }
+
+    fn create_access_map() -> AccessMap {
+        Shared::<ExecutableBuffer>::create_access_map()
+    }
+
+    fn resolve_accesses(access_map: &mut AccessMap, system_id: Option<&SystemId>, _resource_id: Option<ResourceId>) {
+        Shared::<ExecutableBuffer>::resolve_accesses(access_map, system_id, None);
+        Shared::<TickAccumulator>::resolve_accesses(access_map, system_id, None);
+        Unique::<ExecutableOutputs>::resolve_accesses(access_map, system_id, None);
+    }
+
+    fn select_memory_target() -> MemoryTarget {
+        MemoryTarget::Global
+    }
+
+    fn retrieve<'a>(memory_domain: &'a Arc<MemoryDomain>, _resource_id: Option<&ResourceId>, system_id: Option<&SystemId>) -> Result<Self::Item<'a>, ResolveError> {
+        let stages = addressed_this_tick(memory_domain, system_id)?;
+        if stages.is_empty() {
+            return Err(ResolveError::NotAddressed);
+        }
+
+        let outputs = Unique::<ExecutableOutputs>::retrieve(memory_domain, None, system_id)?;
+        Ok(ExecutableOutput { outputs, stages, _marker: PhantomData })
+    }
+}
+
+/// The stages addressed to this system (`Executable::with_system`) that were triggered this tick, one per run
+pub struct ExecutableWork<'a> {
+    inputs: Shared<'a, ExecutableInputs>,
+    outputs: Unique<'a, ExecutableOutputs>,
+    work: Vec<BufferedExecutable>,
+    dropper: DeAccessResolver,
+}
+
+impl ExecutableWork<'_> {
+    /// Oldest run first
+    pub fn iter(&self) -> impl Iterator<Item = &BufferedExecutable> {
+        self.work.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.work.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.work.is_empty()
+    }
+
+    /// One per resource the stage was given, several if it joins branches
+    pub fn read<T: 'static>(&self, buffered_executable: &BufferedExecutable) -> Result<Vec<&T>, ResolveError> {
+        buffered_executable.source.resource_ids().into_iter()
+            .map(|resource_id| self.inputs.get::<T>(resource_id).ok_or_else(|| ResolveError::NoResource(resource_id.clone())))
+            .collect()
+    }
+
+    /// Clones the resource the stage was given, see `read` for a joined stage
+    pub fn read_cloned<T: 'static + Clone>(&self, buffered_executable: &BufferedExecutable) -> Result<T, ResolveError> {
+        let resource_id = single_resource(&buffered_executable.source)?;
+        self.inputs.get::<T>(resource_id).cloned().ok_or_else(|| ResolveError::NoResource(resource_id.clone()))
+    }
+
+    /// Inserted into memory next tick, before the stage completes
+    ///
+    /// false: The stage doesn't output a resource
+    pub fn write<T: 'static>(&mut self, buffered_executable: &BufferedExecutable, value: T) -> bool {
+        let ExecutableMessage::ResourceId(resource_id) = &buffered_executable.target else {
+            return false;
+        };
+
+        self.outputs.write(resource_id.clone(), value);
+        true
+    }
+}
+
+impl AccessDropper for ExecutableWork<'_> {
+    fn access_dropper(&self) -> &DeAccessResolver {
+        &self.dropper
+    }
+}
+
+impl Injection for ExecutableWork<'_> {
+    type Item<'new> = ExecutableWork<'new>;
+
+    fn failed_message() -> String {
+        "Expected Resources: `ExecutableBuffer`, `TickAccumulator`, `ExecutableInputs` & `ExecutableOutputs`".to_string()
+    }
+
+    fn create_access_map() -> AccessMap {
+        Shared::<ExecutableBuffer>::create_access_map()
+    }
+
+    fn resolve_accesses(access_map: &mut AccessMap, system_id: Option<&SystemId>, _resource_id: Option<ResourceId>) {
+        Shared::<ExecutableBuffer>::resolve_accesses(access_map, system_id, None);
+        Shared::<TickAccumulator>::resolve_accesses(access_map, system_id, None);
+        Shared::<ExecutableInputs>::resolve_accesses(access_map, system_id, None);
+        Unique::<ExecutableOutputs>::resolve_accesses(access_map, system_id, None);
+    }
+
+    fn select_memory_target() -> MemoryTarget {
+        MemoryTarget::Global
+    }
+
+    fn retrieve<'a>(memory_domain: &'a Arc<MemoryDomain>, _resource_id: Option<&ResourceId>, system_id: Option<&SystemId>) -> Result<Self::Item<'a>, ResolveError> {
+        let work = addressed_this_tick(memory_domain, system_id)?;
+        let inputs = Shared::<ExecutableInputs>::retrieve(memory_domain, None, system_id)?;
+        let outputs = Unique::<ExecutableOutputs>::retrieve(memory_domain, None, system_id)?;
+        let dropper = DeAccessResolver::new(Arc::clone(memory_domain), AccessMap::Heap(ReservationAccessMap::default()));
+
+        Ok(ExecutableWork { inputs, outputs, work, dropper })
+    }
+}
+
+/// The entities of the stages addressed to this system that were triggered this tick, see `ExecutableWork`.
+/// Their components are accessed through `World`
+pub struct ExecutableEntities {
+    entities: Vec<EntityId>,
+    dropper: DeAccessResolver,
+}
+
+impl Deref for ExecutableEntities {
+    type Target = [EntityId];
+
+    fn deref(&self) -> &Self::Target {
+        &self.entities
+    }
+}
+
+impl AccessDropper for ExecutableEntities {
+    fn access_dropper(&self) -> &DeAccessResolver {
+        &self.dropper
+    }
+}
+
+impl Injection for ExecutableEntities {
+    type Item<'new> = ExecutableEntities;
+
+    fn failed_message() -> String {
+        "Expected Resources: `ExecutableBuffer` & `TickAccumulator`".to_string()
+    }
+
+    fn create_access_map() -> AccessMap {
+        Shared::<ExecutableBuffer>::create_access_map()
+    }
+
+    fn resolve_accesses(access_map: &mut AccessMap, system_id: Option<&SystemId>, _resource_id: Option<ResourceId>) {
+        Shared::<ExecutableBuffer>::resolve_accesses(access_map, system_id, None);
+        Shared::<TickAccumulator>::resolve_accesses(access_map, system_id, None);
+    }
+
+    fn select_memory_target() -> MemoryTarget {
+        MemoryTarget::Global
+    }
+
+    fn retrieve<'a>(memory_domain: &'a Arc<MemoryDomain>, _resource_id: Option<&ResourceId>, system_id: Option<&SystemId>) -> Result<Self::Item<'a>, ResolveError> {
+        let mut entities = Vec::new();
+        for buffered_executable in addressed_this_tick(memory_domain, system_id)? {
+            for entity_id in buffered_executable.source.entity_ids() {
+                if !entities.contains(entity_id) {
+                    entities.push(entity_id.clone());
+                }
+            }
+        }
+
+        let dropper = DeAccessResolver::new(Arc::clone(memory_domain), AccessMap::Heap(ReservationAccessMap::default()));
+        Ok(ExecutableEntities { entities, dropper })
+    }
+}
+
+#[cfg(test)]
+mod executables_tests {
+    use crate::prelude::{BufferedExecutable, ExecutableLabel, Memory, Unique};
+
+    use super::*;
+
+    fn buffer(memory: &Memory, system_id: &str) {
+        assert!(memory.insert(None, None, None, ExecutableBuffer::default()).unwrap().is_ok());
+        assert!(memory.insert(None, None, None, ExecutableInputs::default()).unwrap().is_ok());
+        assert!(memory.insert(None, None, None, ExecutableOutputs::default()).unwrap().is_ok());
+        assert!(memory.insert(None, None, None, TickAccumulator::default()).unwrap().is_ok());
+
+        let buffered_executable = BufferedExecutable::new(
+            RunId::new(0),
+            ExecutableLabel::new("A".to_string()),
+            ExecutableMessage::ResourceId(ResourceId::from_labelled_heap("In")),
+            ExecutableMessage::ResourceId(ResourceId::from_labelled_heap("Out")),
+        ).with_system(Some(SystemId::from(system_id)));
+
+        memory.resolve::<Unique<ExecutableBuffer>>(None, None, None, None).unwrap().unwrap().push(buffered_executable);
+    }
+
+    /// What the `ExecutableManager` does as it triggers the stages
+    fn lend(memory: &Memory) {
+        let buffer = memory.resolve::<Shared<ExecutableBuffer>>(None, None, None, None).unwrap().unwrap();
+        memory.resolve::<Unique<ExecutableInputs>>(None, None, None, None).unwrap().unwrap().lend(memory, &buffer);
+    }
+
+    #[test]
+    fn input_and_output() {
+        let memory = Memory::new();
+        let system_id = SystemId::from("A");
+        buffer(&memory, "A");
+        assert!(memory.insert(None, Some(ResourceId::from_labelled_heap("In")), None, 1_i32).unwrap().is_ok());
+
+        // A second run reaching the system the same tick
+        let second = BufferedExecutable::new(
+            RunId::new(1),
+            ExecutableLabel::new("A".to_string()),
+            ExecutableMessage::ResourceId(ResourceId::from_labelled_heap("In")),
+            ExecutableMessage::ResourceId(ResourceId::from_labelled_heap("Second Out")),
+        ).with_system(Some(system_id.clone()));
+        memory.resolve::<Unique<ExecutableBuffer>>(None, None, None, None).unwrap().unwrap().push(second);
+        lend(&memory);
+
+        let input = memory.resolve::<ExecutableInput<i32>>(None, None, Some(&system_id), None).unwrap().unwrap();
+        let mut output = memory.resolve::<ExecutableOutput<i32>>(None, None, Some(&system_id), None).unwrap().unwrap();
+        assert_eq!(input.len(), 2);
+        assert_eq!(output.len(), 2);
+
+        // Lent while the stages are buffered
+        assert!(memory.resolve::<Unique<i32>>(None, Some(&ResourceId::from_labelled_heap("In")), None, None).unwrap().is_err());
+
+        for (stage, value) in input.iter() {
+            assert!(output.write(stage.run_id, *value + stage.run_id.get() as i32));
+        }
+        assert!(!output.write(RunId::new(2), 0));
+        drop((input, output));
+
+        // Until the `ExecutableManager` inserts them
+        assert!(memory.resolve::<Shared<i32>>(None, Some(&ResourceId::from_labelled_heap("Out")), None, None).unwrap().is_err());
+        memory.resolve::<Unique<ExecutableOutputs>>(None, None, None, None).unwrap().unwrap().flush(&memory);
+        assert_eq!(**memory.resolve::<Shared<i32>>(None, Some(&ResourceId::from_labelled_heap("Out")), None, None).unwrap().unwrap(), 1);
+        assert_eq!(**memory.resolve::<Shared<i32>>(None, Some(&ResourceId::from_labelled_heap("Second Out")), None, None).unwrap().unwrap(), 2);
+    }
+
+    #[test]
+    fn declares_its_accesses() {
+        let memory = Memory::new();
+        buffer(&memory, "A");
+
+        let reserve = |system_id: &str, output: bool| match output {
+            true => memory.reserve_accesses::<ExecutableOutput<i32>>(None, None, SystemId::from(system_id), None).unwrap(),
+            false => memory.reserve_accesses::<ExecutableInput<i32>>(None, None, SystemId::from(system_id), None).unwrap(),
+        };
+
+        assert!(reserve("A", false).is_ok());
+        assert!(reserve("A", true).is_ok());
+
+        // Inputs are read concurrently, outputs written by one system at a time
+        assert!(reserve("B", false).is_ok());
+        assert!(reserve("B", true).is_err());
+    }
+
+    #[test]
+    fn joined_inputs() {
+        let memory = Memory::new();
+        let system_id = SystemId::from("A");
+        assert!(memory.insert(None, None, None, ExecutableBuffer::default()).unwrap().is_ok());
+        assert!(memory.insert(None, None, None, ExecutableInputs::default()).unwrap().is_ok());
+        assert!(memory.insert(None, None, None, ExecutableOutputs::default()).unwrap().is_ok());
+        assert!(memory.insert(None, None, None, TickAccumulator::default()).unwrap().is_ok());
+
+        let joined = ExecutableMessage::Joined(["B", "C"].into_iter().map(|label| {
+            assert!(memory.insert(None, Some(ResourceId::from_labelled_heap(label)), None, label.len() as i32).unwrap().is_ok());
+            ExecutableMessage::ResourceId(ResourceId::from_labelled_heap(label))
+        }).collect());
+
+        let buffered_executable = BufferedExecutable::new(
+            RunId::new(0),
+            ExecutableLabel::new("A".to_string()),
+            joined,
+            ExecutableMessage::ResourceId(ResourceId::from_labelled_heap("Out")),
+        ).with_system(Some(system_id.clone()));
+        memory.resolve::<Unique<ExecutableBuffer>>(None, None, None, None).unwrap().unwrap().push(buffered_executable);
+        lend(&memory);
+
+        assert!(matches!(memory.resolve::<ExecutableInput<i32>>(None, None, Some(&system_id), None).unwrap(), Err(ResolveError::Joined)));
+
+        let work = memory.resolve::<ExecutableWork>(None, None, Some(&system_id), None).unwrap().unwrap();
+        let buffered_executable = work.iter().next().unwrap();
+        assert_eq!(work.read::<i32>(buffered_executable), Ok(vec![&1, &1]));
+        assert_eq!(work.read_cloned::<i32>(buffered_executable), Err(ResolveError::Joined));
+    }
+
+    #[test]
+    fn work_this_tick() {
+        let memory = Memory::new();
+        let system_id = SystemId::from("A");
+        buffer(&memory, "A");
+        assert!(memory.insert(None, Some(ResourceId::from_labelled_heap("In")), None, 1_i32).unwrap().is_ok());
+
+        let late = BufferedExecutable::new(
+            RunId::new(1),
+            ExecutableLabel::new("A".to_string()),
+            ExecutableMessage::ResourceId(ResourceId::from_labelled_heap("In")),
+            ExecutableMessage::ResourceId(ResourceId::from_labelled_heap("Late")),
+        ).with_system(Some(system_id.clone())).with_tick(1);
+        memory.resolve::<Unique<ExecutableBuffer>>(None, None, None, None).unwrap().unwrap().push(late);
+        lend(&memory);
+
+        let mut work = memory.resolve::<ExecutableWork>(None, None, Some(&system_id), None).unwrap().unwrap();
+        assert_eq!(work.len(), 1);
+
+        let buffered_executable = work.iter().next().unwrap().clone();
+        assert_eq!(buffered_executable.run_id, RunId::new(0));
+        assert_eq!(work.read_cloned::<i32>(&buffered_executable), Ok(1));
+        assert!(work.write(&buffered_executable, 2_i32));
+        drop(work);
+        assert!(memory.resolve::<Shared<ExecutableOutputs>>(None, None, None, None).unwrap().unwrap().contains(&ResourceId::from_labelled_heap("Out")));
+
+        memory.resolve::<Shared<TickAccumulator>>(None, None, None, None).unwrap().unwrap().increment(1);
+        let work = memory.resolve::<ExecutableWork>(None, None, Some(&system_id), None).unwrap().unwrap();
+        assert_eq!(work.iter().map(|buffered_executable| buffered_executable.run_id).collect::<Vec<_>>(), vec![RunId::new(1)]);
+    }
+
+    #[test]
+    fn not_addressed() {
+        let memory = Memory::new();
+        buffer(&memory, "A");
+        assert!(memory.insert(None, Some(ResourceId::from_labelled_heap("In")), None, 1_i32).unwrap().is_ok());
+        lend(&memory);
+
+        let other = SystemId::from("B");
+        assert!(matches!(memory.resolve::<ExecutableInput<i32>>(None, None, Some(&other), None).unwrap(), Err(ResolveError::NotAddressed)));
+        assert!(matches!(memory.resolve::<ExecutableOutput<i32>>(None, None, None, None).unwrap(), Err(ResolveError::NotAddressed)));
+    }
+}
@@ -1,4 +1,6 @@
 pub mod global;
 pub mod resulting;
 pub mod system_id;
-pub mod program_memory;
\ No newline at end of file
+pub mod program_memory;
+pub mod events;pub mod executables;
+pub mod background;
\ No newline at end of file
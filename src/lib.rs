@@ -11,12 +11,12 @@ pub mod prelude {
             entity::EntityId, world::World
         },
         ids::{
-            Id, system_id::SystemId, program_id::ProgramId, event_id::EventId, blocker_id::BlockerId
+            Id, system_id::SystemId, program_id::ProgramId, event_id::EventId, blocker_id::BlockerId, schedule_id::ScheduleId, loop_id::LoopId, run_id::RunId
         },
         injection::{
             AccessDropper, DeAccessResolver, 
             injection_advanced::{
-                global::Global, resulting::Resulting, system_id::GetSystemId, program_memory::ProgramMemory,
+                global::Global, resulting::Resulting, system_id::GetSystemId, program_memory::ProgramMemory, events::{EventWriter, EventReader}, executables::{ExecutableInput, ExecutableOutput, ExecutableWork, ExecutableEntities}, background::TaskHandle,
             },
             injection_primitives::{
                 cloned::Cloned, shared::Shared, unique::Unique
@@ -38,7 +38,7 @@ pub mod prelude {
                 Access, AccessMap
             },
             errors::{
-                DeResolveError, InsertError, ReservationError, ResolveError
+                DeResolveError, InsertError, RemoveError, ReservationError, ResolveError
             },
             memory_domain::MemoryDomain,
             program_memory_map::{
@@ -59,6 +59,7 @@ pub mod prelude {
                 managers::{
                     blocker_manager::{
                         blocker_manager::BlockerManager, current_blockers::CurrentBlockers, next_blockers::NextBlockers,
+                        blocker::{Blocker, BlockerTarget, BlockerLifetime, BlockerMode, ActiveBlocker}, blocked_events::BlockedEvents,
                     },
                     delay_manager::{
                         delay::{
@@ -67,26 +68,39 @@ pub mod prelude {
                         delay_buffer::DelayBuffer, delay_manager::DelayManager, delay_registry::DelayRegistry
                     },
                     event_manager::{
-                        event_manager::EventManager, event_mapper::EventMapper, next_events::NextEvents, current_events::CurrentEvents, system_event_registry::SystemEventRegistry,
+                        event_manager::EventManager, next_events::NextEvents, current_events::CurrentEvents, system_event_registry::SystemEventRegistry,
+                        typed_events::{NextTypedEvents, CurrentTypedEvents, TypedEventRegistry}, queued_event::{EventMode, QueuedEvent},
+                    },
+                    event_mapper_manager::{
+                        event_mapper_manager::EventMapperManager, is_event_mapper::IsEventMapper, becomes_event_mapper::BecomesEventMapper, mapping_error::MappingError,
+                    },
+                    time_event_manager::{
+                        clock::{Clock, SystemClock, MockClock}, cron::{CronSchedule, CronParseError}, schedule::Schedule, timed_event::TimedEvent,
+                        time_event_registry::TimeEventRegistry, time_event_manager::TimeEventManager,
+                    },
+                    while_manager::{
+                        while_event::WhileEvent, while_registry::{WhileRegistry, WhileInstance, WhileState}, while_manager::WhileManager,
                     },
                     executable_manager::{
                         executable_buffer::{
                             BufferedExecutable, ExecutableBuffer
                         },
+                        executable_inputs::ExecutableInputs, executable_outputs::ExecutableOutputs,
                         executable_label::ExecutableLabel,
                         executable_manager::ExecutableManager,
                         executable_message::ExecutableMessage,
                         executable_queue::{
                             QueuedExecutable, ExecutableQueue
                         },
-                        executable::Executable,
-                        executable_registry::{
-                            ExecutableRegistry, ParseResult
-                        }
+                        executable::{Executable, Completion},
+                        executable_status::{ExecutableStatus, ExecutableFailure},
+                        error_policy::ErrorPolicy, failed_executables::{FailedExecutables, FailedExecutable},
+                        executable_registry::{ExecutableRegistry, ExecutableRegistryBuilder},
+                        pipeline::{Pipeline, Guard, PipelineError}, pipeline_parser::{PipelineParser, PipelineParseError},
                     },
                 },
                 processors::{
-                    Processor, tasks::DummyWaker, unwinder::Unwinder, finished_graphs::FinishedGraphTracker,
+                    Processor, tasks::{Task, TaskWaker}, executor::{Executor, JobHandle, JobPanic, WorkerUtilization}, finished_graphs::FinishedGraphTracker, ready_queue::{ReadyQueue, AbortOnPanic},
                     non_blocking_processor::{
                         processors::{
                             finish_non_blocking_processor::FinishNonBlockingProcessor,
@@ -96,7 +110,8 @@ pub mod prelude {
                             async_join_handles::AsyncJoinHandles,
                             sync_join_handles::SyncJoinHandles,
                         },
-                        background_processor_system_registry::BackgroundProcessorSystemRegistry
+                        background_processor_system_registry::BackgroundProcessorSystemRegistry,
+                        background_tasks::{BackgroundTasks, BackgroundTask, TaskSignal, Progress},
                     },
                     system::{
                         FunctionSystem, System, system_cell::SystemCell, system_status::SystemStatus,
@@ -104,13 +119,13 @@ pub mod prelude {
                             AsyncSystem, StoredAsyncSystem, into_async_system::IntoAsyncSystem
                         },
                         stored_system::{
-                            StoredSystem, StoredSystemError
+                            StoredSystem, StoredSystemError, SystemFactory
                         },
                         sync_system::{
                             StoredSyncSystem, SyncSystem, into_sync_system::IntoSyncSystem   
                         },
                         system_metadata::{
-                            SystemMetadata, SystemRegistry, criteria::Criteria, stored_system_metadata::StoredSystemMetadata
+                            SystemMetadata, SystemRegistry, criteria::{Criteria, CriteriaContext}, criteria_parser::{CriteriaParser, CriteriaParseError}, resource_criteria::{ResourceCriteria, StoredResourceCriteria, IntoResourceCriteria, FunctionCriteria}, stored_system_metadata::StoredSystemMetadata, panic_policy::PanicPolicy, run_conditions::{RunConditions, RunState, Cooldown}
                         },
                         system_result::{
                             SystemEvent, SystemResult
@@ -38,6 +38,16 @@ impl Heap {
         // since no concurrent accesses
         unsafe { self.raw_heap.insert(heap_id, resource, guard) }
     }
+
+    /// Safety:
+    /// Ensure no concurrent accesses
+    pub unsafe fn remove(&self, heap_id: &HeapId) -> Option<HeapObject> {
+        let guard = self.lock.write();
+
+        // Safety:
+        // since no concurrent accesses
+        unsafe { self.raw_heap.remove(heap_id, guard) }
+    }
 }
 
 // would want a test to show no race conditions on inserts / _
@@ -77,4 +87,14 @@ mod heap_tests {
         assert!(heap.contains(&id));
         assert_eq!(unsafe { heap.get_mut::<i32>(&id) }, Some(&mut 100));
     }
+
+    #[test]
+    fn remove() {
+        let heap = Heap::default();
+        let id = HeapId::Label(Id::from("foo"));
+        assert!(unsafe { heap.remove(&id) }.is_none());
+        assert!(unsafe { heap.insert(id.clone(), HeapObject::dummy(100)) }.is_none());
+        assert!(unsafe { heap.remove(&id) }.is_some());
+        assert!(!heap.contains(&id));
+    }
 }
\ No newline at end of file
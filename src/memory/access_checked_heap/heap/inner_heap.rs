@@ -39,6 +39,12 @@ impl InnerHeap {
     pub unsafe fn insert(&mut self, heap_id: HeapId, heap_object: HeapObject) -> Option<HeapObject> {
         self.resources.insert(heap_id, heap_object)
     }
+
+    /// Safety:
+    /// Ensure no concurrent accesses
+    pub unsafe fn remove(&mut self, heap_id: &HeapId) -> Option<HeapObject> {
+        self.resources.remove(heap_id)
+    }
 }
 
 #[cfg(test)]
@@ -76,4 +82,14 @@ mod inner_heap_tests {
         assert!(heap.contains(&id));
         assert_eq!(unsafe { heap.get_mut::<i32>(&id) }, Some(&mut 100));
     }
+
+    #[test]
+    fn remove() {
+        let mut heap = InnerHeap::default();
+        let id = HeapId::Label(Id::from("foo"));
+        assert!(unsafe { heap.remove(&id) }.is_none());
+        assert!(unsafe { heap.insert(id.clone(), HeapObject::dummy(100)) }.is_none());
+        assert!(unsafe { heap.remove(&id) }.is_some());
+        assert!(!heap.contains(&id));
+    }
 }
\ No newline at end of file
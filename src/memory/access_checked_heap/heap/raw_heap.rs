@@ -47,6 +47,12 @@ impl RawHeap {
     pub unsafe fn insert(&self, heap_id: HeapId, heap_object: HeapObject, _guard: parking_lot::RwLockWriteGuard<()>) -> Option<HeapObject> {
         unsafe { self.get_mut_inner_heap().insert(heap_id, heap_object) }
     }
+
+    /// Safety:
+    /// Ensure no access removed
+    pub unsafe fn remove(&self, heap_id: &HeapId, _guard: parking_lot::RwLockWriteGuard<()>) -> Option<HeapObject> {
+        unsafe { self.get_mut_inner_heap().remove(heap_id) }
+    }
 }
 
 // no need to test since tested `heap` and `inner_heap` and this is simply a syntax separator layer
\ No newline at end of file
@@ -1,6 +1,6 @@
 use std::sync::Mutex;
 
-use crate::prelude::{Access, DeResolveError, Heap, HeapId, HeapObject, InsertError, MemoryDomain, RawAccessMap, ReservationAccessMap, ReservationError, ResolveError, ResourceId, SystemId};
+use crate::prelude::{Access, DeResolveError, Heap, HeapId, HeapObject, InsertError, MemoryDomain, RemoveError, RawAccessMap, ReservationAccessMap, ReservationError, ResolveError, ResourceId, SystemId};
 
 pub mod heap;
 pub mod reservation_access_map;
@@ -28,6 +28,10 @@ impl AccessCheckedHeap {
         self.reservation_access_map.lock().unwrap().unreserve(heap_id, access, system_id)
     }
 
+    pub fn unreserve_all(&self, system_id: &SystemId) -> bool {
+        self.reservation_access_map.lock().unwrap().unreserve_all(system_id)
+    }
+
     /// Will drain the access map
     pub fn reserve_accesses(&self, memory_domain: &MemoryDomain, system_id: SystemId, access_map: &mut RawAccessMap) -> Result<(), ReservationError> {
         self.reservation_access_map.lock().unwrap().reserve_accesses(memory_domain, system_id, access_map)
@@ -58,6 +62,18 @@ impl AccessCheckedHeap {
         Ok(unsafe { self.heap.insert(heap_id, resource) })
     }
 
+    pub fn remove(&self, heap_id: &HeapId) -> Result<Option<HeapObject>, RemoveError> {
+        let access_map = self.reservation_access_map.lock().unwrap();
+        if access_map.get_access(heap_id).is_some() {
+            return Err(RemoveError::ConcurrentAccess)
+        }
+
+        // Safety:
+        // Accesses are tracked
+        // No Access allowed
+        Ok(unsafe { self.heap.remove(heap_id) })
+    }
+
     // pub crate for now since i only want the dropper to use this
     /// Safety:
     /// Do not deaccess something unless you actually free the access!
@@ -129,6 +145,23 @@ mod access_checked_heap_tests {
         assert_eq!(r, Ok(&101))
     }
 
+    #[test]
+    fn remove() {
+        let access_checked_heap = AccessCheckedHeap::default();
+        let heap_id = HeapId::Label(Id::from("foo"));
+
+        assert!(access_checked_heap.remove(&heap_id).unwrap().is_none());
+        assert!(access_checked_heap.insert(heap_id.clone(), HeapObject::dummy(100)).unwrap().is_none());
+
+        let r = access_checked_heap.get_shared::<i32>(&heap_id, None);
+        assert!(access_checked_heap.remove(&heap_id).is_err());
+        assert_eq!(r, Ok(&100));
+
+        unsafe { access_checked_heap.deaccess(Access::Shared(1), &heap_id) }.unwrap();
+        assert!(access_checked_heap.remove(&heap_id).unwrap().is_some());
+        assert!(!access_checked_heap.ok_resource(&heap_id));
+    }
+
     #[test]
     fn get_shared() {
         let access_checked_heap = AccessCheckedHeap::default();
@@ -35,6 +35,11 @@ impl ReservationAccessMap {
         self.reserve_map.unreserve(system_id, heap_id, access);
     }
 
+    /// true if the system held any reservations
+    pub fn unreserve_all(&mut self, system_id: &SystemId) -> bool {
+        self.reserve_map.unreserve_all(system_id).is_some()
+    }
+
     pub fn ok_reservation_self(&self, other: &Self, system_id: Option<&SystemId>, memory_domain: &MemoryDomain) -> Option<ReservationError> {
         self.ok_reservation(&other.access_map, system_id, memory_domain)
     }
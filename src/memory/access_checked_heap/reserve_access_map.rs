@@ -46,6 +46,11 @@ impl ReserveAccessMap {
     pub fn unreserve(&mut self, system_id: &SystemId, item: &HeapId, access: Access) -> Option<Result<(), DeResolveError>> {
         Some(self.access_maps.get_mut(system_id)?.deaccess(access, item))
     }
+
+    /// removes every reservation held by the system, returning what was still reserved
+    pub fn unreserve_all(&mut self, system_id: &SystemId) -> Option<RawAccessMap> {
+        self.access_maps.remove(system_id)
+    }
 }
 
 #[cfg(test)]
@@ -115,4 +120,19 @@ mod reserve_access_map_tests {
         reserve_access_map.reserve(SystemId::from("bax"), raw_access_map.clone().drain());
         assert!(!reserve_access_map.has_conflicting_reservation(&raw_access_map, Some(&SystemId::from("bax"))));
     }
+
+    #[test]
+    fn unreserve_all() {
+        let mut reserve_access_map = ReserveAccessMap::default();
+
+        let item = HeapId::Label(Id::from("foo"));
+        let source = SystemId::from("bar");
+        assert!(reserve_access_map.unreserve_all(&source).is_none());
+
+        reserve_access_map.reserve(source.clone(), vec![(item.clone(), Access::Unique)].into_iter());
+        assert!(reserve_access_map.is_conflicting_reservation(&item, &Access::Shared(1), None));
+
+        assert!(reserve_access_map.unreserve_all(&source).is_some());
+        assert!(!reserve_access_map.is_conflicting_reservation(&item, &Access::Shared(1), None));
+    }
 }
\ No newline at end of file
@@ -7,6 +7,12 @@ pub enum ResolveError {
     TooManyAccesses(ResourceId),
     InvalidProgramId,
     NoResource(ResourceId),
+    /// No executable stage is addressed to the system
+    NotAddressed,
+    /// The executable stage joins several resources, see `ExecutableWork::read`
+    Joined,
+    /// The system isn't running in the background
+    NotInBackground,
 }
 
 #[derive(Debug)]
@@ -20,6 +26,11 @@ pub enum InsertError {
     ConcurrentAccess
 }
 
+#[derive(Debug)]
+pub enum RemoveError {
+    ConcurrentAccess
+}
+
 #[derive(Debug, PartialEq)]
 pub enum ReservationError {
     ConflictingReservation,
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, sync::{Arc, Mutex}};
 
-use crate::prelude::{Access, AccessCheckedHeap, AccessDropper, AccessMap, DeResolveError, Injection, InsertError, RawAccessMap, ReservationError, ResolveError, Resource, ResourceId, SystemId};
+use crate::prelude::{Access, AccessCheckedHeap, AccessDropper, AccessMap, DeResolveError, Injection, InsertError, RawAccessMap, RemoveError, ReservationError, ResolveError, Resource, ResourceId, SystemId};
 
 // Should be no public way of creating one of these to enforce dropping behaviour by injection types // doesnt matter because the UB would just panic
 #[derive(Debug)]
@@ -48,6 +48,11 @@ impl MemoryDomain {
         }
     }
 
+    /// Releases every reservation held by the system, true if there were any
+    pub fn unreserve(&self, system_id: &SystemId) -> bool {
+        self.heap.unreserve_all(system_id)
+    }
+
     pub fn ok_reservation_self(&self, other: &Self, system_id: Option<&SystemId>) -> Option<ReservationError> {
         self.heap.ok_reservation_self(&other.heap, system_id, &self)
     }
@@ -67,6 +72,12 @@ impl MemoryDomain {
         }
     }
 
+    pub fn remove(&self, resource_id: &ResourceId) -> Result<Option<Resource>, RemoveError> {
+        match resource_id {
+            ResourceId::Heap(id) => Ok(self.heap.remove(id)?.map(Resource::Heap))
+        }
+    }
+
     pub fn resolve<T: Injection>(self: &Arc<Self>, resource_id: Option<&ResourceId>, system_id: Option<&SystemId>) -> Result<T::Item<'_>, ResolveError> {
         let r = T::retrieve(&self, resource_id, system_id);
         if let Ok(r) = &r {
@@ -1,6 +1,6 @@
 use std::{any::Any, sync::Arc};
 
-use crate::{ids::{program_id::ProgramId, system_id::SystemId}, injection::injection_trait::{Injection, MemoryTarget}, memory::{access_checked_heap::heap::{HeapObject, raw_heap_object::RawHeapObject }, errors::{InsertError, ReservationError, ResolveError}, memory_domain::MemoryDomain, program_memory_map::{ProgramMemoryMap, inner_program_memory_map::ProgramKey}, resource_id::Resource}, prelude::ResourceId};
+use crate::{ids::{program_id::ProgramId, system_id::SystemId}, injection::injection_trait::{Injection, MemoryTarget}, memory::{access_checked_heap::heap::{HeapObject, raw_heap_object::RawHeapObject }, errors::{InsertError, RemoveError, ReservationError, ResolveError}, memory_domain::MemoryDomain, program_memory_map::{ProgramMemoryMap, inner_program_memory_map::ProgramKey}, resource_id::Resource}, prelude::ResourceId};
 
 pub mod access_checked_heap;
 pub mod resource_id;
@@ -99,6 +99,22 @@ impl Memory {
         }
     }
 
+    /// Releases every reservation the system holds in the program's memory and global memory
+    /// 
+    /// None: ProgramId failure
+    /// 
+    /// Some(bool): if any reservations were released
+    pub fn unreserve(&self, program_id: Option<&ProgramId>, system_id: &SystemId, key: Option<&ProgramKey>) -> Option<bool> {
+        let global = self.program_memory_map.get(&self.global_memory, None)?.unreserve(system_id);
+
+        let program = match program_id {
+            Some(program_id) if program_id != &self.global_memory => self.program_memory_map.get(program_id, key)?.unreserve(system_id),
+            _ => false
+        };
+
+        Some(global || program)
+    }
+
     /// None: ProgramId failure
     pub fn resolve<T: Injection>(&self, program_id: Option<&ProgramId>, resource_id: Option<&ResourceId>, system_id: Option<&SystemId>, key: Option<&ProgramKey>) -> Option<Result<T::Item<'_>, ResolveError>> {
         let map = match T::select_memory_target() {
@@ -142,6 +158,30 @@ impl Memory {
         )
     }
 
+    /// Inserts a resource as is, e.g. one taken by `remove`
+    pub fn insert_resource(&self, program_id: Option<&ProgramId>, resource_id: ResourceId, key: Option<&ProgramKey>, resource: Resource) -> Option<Result<Option<Resource>, InsertError>> {
+        let program_id = match program_id {
+            Some(program_id) => program_id,
+            None => &self.global_memory,
+        };
+
+        Some(self.program_memory_map.get(program_id, key)?.insert(resource_id, resource))
+    }
+
+    /// None: Invalid ProgramId/ProgramKey
+    /// 
+    /// Some/Err: RemoveError
+    /// 
+    /// Some/Ok/None: No ResourceId/Resource Existed
+    pub fn remove(&self, program_id: Option<&ProgramId>, resource_id: &ResourceId, key: Option<&ProgramKey>) -> Option<Result<Option<Resource>, RemoveError>> {
+        let program_id = match program_id {
+            Some(program_id) => program_id,
+            None => &self.global_memory,
+        };
+
+        Some(self.program_memory_map.get(program_id, key)?.remove(resource_id))
+    }
+
     pub fn contains_resource(&self, program_id: Option<&ProgramId>, resource_id: &ResourceId, key: Option<&ProgramKey>) -> Option<bool> {
         let program_id = match program_id {
             Some(program_id) => program_id,
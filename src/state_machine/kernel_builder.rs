@@ -1,17 +1,18 @@
-use std::sync::Arc;
-
 use tracing::{Level, event, span};
 
-use crate::prelude::{BlockerManager, BlockingProcessor, DelayManager, EventManager, ExecutableManager, FinishNonBlockingProcessor, KernelSystem, ReadOnlyProcessor, StartNonBlockingProcessor, StateMachine, StoredKernelSystem};
+use crate::prelude::{BlockerManager, BlockingProcessor, DelayManager, EventManager, EventMapperManager, ExecutableManager, Executor, FinishNonBlockingProcessor, KernelSystem, ReadOnlyProcessor, StartNonBlockingProcessor, StateMachine, StoredKernelSystem, SystemId, TimeEventManager, WhileManager};
 
 fn load_default(kernel_builder: KernelBuilder) -> KernelBuilder {
     // FinishNonBlockingProcessor: 0. Join handles asap
 
-    // Managers?
+    // Managers: 1-7. Executable, Delay, TimeEvent, While, Blocker, Event, EventMapper
+    // EventMapperManager is after EventManager to map the events it just made current
+
+    // BlockingProcessor: 8. Main Systems
+    // ReadOnlyProcessor: 9. Observers over BlockingProcessor
+    // StartNonBlockingProcessor: 10. Kick off background tasks
 
-    // BlockingProcessor: 5. Main Systems
-    // ReadOnlyProcessor: 6. Observers over BlockingProcessor
-    // StartNonBlockingProcessor: 7. Kick off background tasks
+    // Indices shift as kernel systems are added, `KernelBuilder::toggle` is keyed by `SystemId`
 
     // Finish is before Start because if you imagine the lifetime of a "tick"
     // the time before and after are undetermined and therefore could be treated as long enough
@@ -24,8 +25,11 @@ fn load_default(kernel_builder: KernelBuilder) -> KernelBuilder {
         .with_system(FinishNonBlockingProcessor)
         .with_system(ExecutableManager)
         .with_system(DelayManager)
+        .with_system(TimeEventManager)
+        .with_system(WhileManager)
         .with_system(BlockerManager)
         .with_system(EventManager)
+        .with_system(EventMapperManager)
         .with_system(BlockingProcessor)
         .with_system(ReadOnlyProcessor)
         .with_system(StartNonBlockingProcessor)
@@ -68,39 +72,43 @@ impl KernelBuilder {
         self.kernel_systems.len()
     }
     
-    pub fn toggle(mut self, ordering_index: usize, enable: bool) -> Self {
-        if let Some((_, toggle)) = self.kernel_systems.get_mut(ordering_index) {
+    /// e.g. `SystemId::from("Blocking Processor")`, see `KernelSystem::system_id`
+    pub fn toggle(mut self, system_id: impl Into<SystemId>, enable: bool) -> Self {
+        let system_id = system_id.into();
+        if let Some((_, toggle)) = self.kernel_systems.iter_mut().find(|(kernel_system, _)| kernel_system.system_id() == system_id) {
             *toggle = enable
         }
 
         self
     }
 
+    /// None if the kernel system isn't loaded
+    pub fn is_enabled(&self, system_id: &SystemId) -> Option<bool> {
+        self.kernel_systems.iter()
+            .find(|(kernel_system, _)| kernel_system.system_id() == *system_id)
+            .map(|(_, toggle)| *toggle)
+    }
+
     pub fn init(self, state_machine: &StateMachine) {
         let span = span!(Level::DEBUG, "Loading Kernel Systems");
         let _enter = span.enter();
 
         event!(Level::DEBUG, "Started");
         
-        let rt = tokio::runtime::Builder::new_multi_thread()
+        // Only drives the timers & IO used by async systems, the executor's workers enter it and run everything
+        let reactor = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
             .enable_all()
             .build()
             .unwrap();
 
-        let threadpool = threadpool::ThreadPool::new(self.threads);
+        let executor = Executor::new(self.threads, Some(reactor));
 
         assert!(state_machine.memory.insert(
             Some(&state_machine.program_id), 
             None, 
             Some(&state_machine.kernel_key), 
-            Arc::new(rt)
-        ).unwrap().is_ok());
-
-        assert!(state_machine.memory.insert(
-            Some(&state_machine.program_id), 
-            None, 
-            Some(&state_machine.kernel_key), 
-            threadpool
+            executor
         ).unwrap().is_ok());
 
         for (ordering_index, (mut kernel_system, _)) 
@@ -123,4 +131,17 @@ impl KernelBuilder {
 
         event!(Level::DEBUG, "Finished")
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod kernel_builder_tests {
+    use crate::prelude::{KernelBuilder, SystemId};
+
+    #[test]
+    fn toggles_by_system_id() {
+        let kernel_builder = KernelBuilder::full(1).toggle("Blocking Processor", false);
+
+        assert_eq!(kernel_builder.is_enabled(&SystemId::from("Blocking Processor")), Some(false));
+        assert_eq!(kernel_builder.is_enabled(&SystemId::from("Blocker Manager")), Some(true));
+        assert_eq!(kernel_builder.is_enabled(&SystemId::from("Missing")), None);
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::HashSet;
+
+use tracing::{Level, event};
+
+use crate::prelude::{BlockerMode, CurrentBlockers, EventId, SystemRegistry};
+
+/// The triggering events of systems blocked by a `Mask` or `Consume` blocker, collected once per tick across every processor
+#[derive(Debug, Default, Clone)]
+pub struct BlockedEvents {
+    masked: HashSet<EventId>,
+    consumed: HashSet<EventId>,
+}
+
+impl BlockedEvents {
+    pub fn collect<'a>(current_blockers: &CurrentBlockers, system_registries: impl IntoIterator<Item = &'a SystemRegistry>) -> Self {
+        let mut blocked_events = Self::default();
+        for system_registry in system_registries {
+            for (id, system_metadata) in system_registry.read() {
+                let program_id = system_metadata.stored_system_metadata().program_id();
+                for (blocker_id, active_blocker) in current_blockers.blockers_of(id, program_id.as_ref()) {
+                    let events = match active_blocker.blocker().mode() {
+                        BlockerMode::Skip => continue,
+                        BlockerMode::Mask => &mut blocked_events.masked,
+                        BlockerMode::Consume => &mut blocked_events.consumed,
+                    };
+
+                    event!(Level::TRACE, system_id = ?id, blocker_id = %blocker_id, mode = ?active_blocker.blocker().mode(), "Blocking Triggering Events");
+                    events.extend(system_metadata.criteria().events().into_iter().cloned());
+                }
+            }
+        }
+
+        blocked_events
+    }
+
+    /// Hidden from every system's criteria
+    pub fn hides(&self, event_id: &EventId) -> bool {
+        self.masked.contains(event_id) || self.consumed.contains(event_id)
+    }
+
+    /// Removed from `CurrentEvents`
+    pub fn consumed(&self) -> impl Iterator<Item = &EventId> {
+        self.consumed.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.masked.is_empty() && self.consumed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod blocked_events_tests {
+    use crate::prelude::{Blocker, BlockerId, Criteria, ResourceId, SchedulerOrdering, StoredSystemMetadata, SystemId, SystemMetadata};
+
+    use super::*;
+
+    fn registry(name: &str, event: &str) -> SystemRegistry {
+        let mut system_registry = SystemRegistry::default();
+        system_registry.insert(SystemId::from(name), SystemMetadata::new(
+            StoredSystemMetadata::new(ResourceId::from_labelled_heap(name), None, None),
+            Criteria::event(event),
+            SchedulerOrdering::default()
+        ));
+        system_registry
+    }
+
+    #[test]
+    fn across_registries() {
+        let mut current_blockers = CurrentBlockers::default();
+        current_blockers.insert(BlockerId::from("Mask"), Blocker::system("Foo").with_mode(BlockerMode::Mask), None, 0);
+        current_blockers.insert(BlockerId::from("Consume"), Blocker::system("Bar").with_mode(BlockerMode::Consume), None, 0);
+        current_blockers.insert(BlockerId::from("Skip"), Blocker::system("Baz"), None, 0);
+
+        let registries = [registry("Foo", "A"), registry("Bar", "B"), registry("Baz", "C")];
+        let blocked_events = BlockedEvents::collect(&current_blockers, &registries);
+
+        assert!(blocked_events.hides(&EventId::from("A")));
+        assert!(blocked_events.hides(&EventId::from("B")));
+        assert!(!blocked_events.hides(&EventId::from("C")));
+        assert_eq!(blocked_events.consumed().collect::<Vec<_>>(), vec![&EventId::from("B")]);
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::HashSet;
+
+use crate::prelude::{CurrentEvents, EventId, ProgramId, SystemId};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlockerTarget {
+    System(SystemId),
+    Systems(HashSet<SystemId>),
+    /// Every system inserted under the program
+    Program(ProgramId),
+}
+
+impl BlockerTarget {
+    pub fn targets(&self, system_id: &SystemId, program_id: Option<&ProgramId>) -> bool {
+        match self {
+            Self::System(target) => target == system_id,
+            Self::Systems(targets) => targets.contains(system_id),
+            Self::Program(target) => program_id == Some(target),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlockerLifetime {
+    Ticks(u64),
+    /// Until the event is current
+    UntilEvent(EventId),
+    /// Until explicitly unblocked
+    UntilUnblocked,
+}
+
+/// What happens to the triggering events (see `Criteria::events`) of blocked systems
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BlockerMode {
+    /// Only skips the system
+    #[default]
+    Skip,
+    /// Also hides the events from every system's criteria this tick
+    Mask,
+    /// Also removes the events from `CurrentEvents`
+    Consume,
+}
+
+/// What a blocker blocks and for how long
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blocker {
+    target: BlockerTarget,
+    lifetime: BlockerLifetime,
+    mode: BlockerMode,
+    cancels: bool,
+    reason: Option<String>,
+}
+
+impl Blocker {
+    /// Blocks for 1 tick
+    pub fn new(target: BlockerTarget) -> Self {
+        Self {
+            target,
+            lifetime: BlockerLifetime::Ticks(1),
+            mode: BlockerMode::Skip,
+            cancels: false,
+            reason: None,
+        }
+    }
+
+    pub fn system(system_id: impl Into<SystemId>) -> Self {
+        Self::new(BlockerTarget::System(system_id.into()))
+    }
+
+    pub fn systems(system_ids: impl IntoIterator<Item = impl Into<SystemId>>) -> Self {
+        Self::new(BlockerTarget::Systems(system_ids.into_iter().map(Into::into).collect()))
+    }
+
+    pub fn program(program_id: ProgramId) -> Self {
+        Self::new(BlockerTarget::Program(program_id))
+    }
+
+    pub fn for_ticks(mut self, ticks: u64) -> Self {
+        self.lifetime = BlockerLifetime::Ticks(ticks);
+        self
+    }
+
+    pub fn until_event(mut self, event_id: impl Into<EventId>) -> Self {
+        self.lifetime = BlockerLifetime::UntilEvent(event_id.into());
+        self
+    }
+
+    pub fn until_unblocked(mut self) -> Self {
+        self.lifetime = BlockerLifetime::UntilUnblocked;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: BlockerMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Inserted into `NextBlockers` mid tick, also cancels targeted systems which haven't started yet
+    pub fn cancelling(mut self) -> Self {
+        self.cancels = true;
+        self
+    }
+
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    pub fn target(&self) -> &BlockerTarget {
+        &self.target
+    }
+
+    pub fn lifetime(&self) -> &BlockerLifetime {
+        &self.lifetime
+    }
+
+    pub fn mode(&self) -> BlockerMode {
+        self.mode
+    }
+
+    pub fn cancels(&self) -> bool {
+        self.cancels
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+}
+
+/// A current `Blocker` with where it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveBlocker {
+    blocker: Blocker,
+    source: Option<SystemId>,
+    since_tick: u64,
+}
+
+impl ActiveBlocker {
+    pub fn new(blocker: Blocker, source: Option<SystemId>, since_tick: u64) -> Self {
+        Self { blocker, source, since_tick }
+    }
+
+    pub fn blocker(&self) -> &Blocker {
+        &self.blocker
+    }
+
+    /// The system that sent the blocker
+    pub fn source(&self) -> Option<&SystemId> {
+        self.source.as_ref()
+    }
+
+    pub fn since_tick(&self) -> u64 {
+        self.since_tick
+    }
+
+    pub fn is_expired(&self, current_tick: u64, current_events: &CurrentEvents) -> bool {
+        match self.blocker.lifetime() {
+            BlockerLifetime::Ticks(ticks) => current_tick >= self.since_tick + ticks,
+            BlockerLifetime::UntilEvent(event_id) => current_events.contains(event_id),
+            BlockerLifetime::UntilUnblocked => false,
+        }
+    }
+}
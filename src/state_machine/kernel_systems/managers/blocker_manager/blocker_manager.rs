@@ -1,11 +1,25 @@
 use std::{pin::Pin, sync::Arc};
 
-use crate::prelude::{CurrentBlockers, KernelSystem, Memory, NextBlockers, ProgramId, ProgramKey, SystemId, Unique};
+use crate::prelude::{BackgroundProcessorSystemRegistry, BlockedEvents, Blocker, BlockerId, CurrentBlockers, CurrentEvents, KernelSystem, Memory, NextBlockers, ProcessorSystemRegistry, ProgramId, ProgramKey, ReadOnlySystemRegistry, ResourceId, Shared, StateMachine, SystemId, TickAccumulator, Unique};
 
 use tracing::{event, Level};
 
 pub struct BlockerManager;
 
+impl BlockerManager {
+    /// Active from next tick
+    pub fn insert_blocker(state_machine: &StateMachine, blocker_id: impl Into<BlockerId>, blocker: Blocker) -> Option<Option<Blocker>> {
+        let mut next_blockers = state_machine.resolve::<Unique<NextBlockers>>(None, None, None, None)?.ok()?;
+        Some(next_blockers.insert_blocker(blocker_id.into(), blocker, None))
+    }
+
+    /// Removed next tick
+    pub fn unblock(state_machine: &StateMachine, blocker_id: impl Into<BlockerId>) -> Option<bool> {
+        let mut next_blockers = state_machine.resolve::<Unique<NextBlockers>>(None, None, None, None)?.ok()?;
+        Some(next_blockers.unblock(blocker_id.into()))
+    }
+}
+
 impl KernelSystem for BlockerManager {
     fn system_id(&self) -> SystemId {
         SystemId::from("Blocker Manager")
@@ -17,6 +31,14 @@ impl KernelSystem for BlockerManager {
         
         event!(Level::DEBUG, "Inserting CurrentBlockers");
         assert!(memory.insert(None, None, None, CurrentBlockers::default()).unwrap().is_ok());
+
+        event!(Level::DEBUG, "Inserting BlockedEvents");
+        assert!(memory.insert(None, None, None, BlockedEvents::default()).unwrap().is_ok());
+
+        event!(Level::DEBUG, "Checking CurrentEvents");
+        if !matches!(memory.contains_resource(None, &ResourceId::from_raw_heap::<CurrentEvents>(), None), Some(true)) {
+            event!(Level::WARN, "CurrentEvents Not Found");   
+        }
     }
 
     fn tick(&mut self, memory: &Arc<Memory>, _kernel_program_id: ProgramId, _kernel_program_key: ProgramKey) -> Pin<Box<dyn Future<Output = ()> + Send>> {
@@ -24,12 +46,39 @@ impl KernelSystem for BlockerManager {
         Box::pin(async move {
             let mut next_blockers = memory.resolve::<Unique<NextBlockers>>(None, None, None, None).unwrap().unwrap();
             let mut current_blockers = memory.resolve::<Unique<CurrentBlockers>>(None, None, None, None).unwrap().unwrap();
+            let current_events = memory.resolve::<Shared<CurrentEvents>>(None, None, None, None).unwrap().unwrap();
+            let current_tick = memory.resolve::<Shared<TickAccumulator>>(None, None, None, None).unwrap().unwrap().load();
 
             event!(Level::DEBUG, old_current_blockers_count = current_blockers.len());
 
-            current_blockers.tick(next_blockers.drain());
+            for blocker_id in current_blockers.expire(current_tick, &current_events) {
+                event!(Level::TRACE, blocker_id = %blocker_id, "Blocker Expired");
+            }
+
+            for blocker_id in next_blockers.drain_unblocks() {
+                if current_blockers.remove(&blocker_id).is_some() {
+                    event!(Level::TRACE, blocker_id = %blocker_id, "Unblocked");
+                }
+            }
+
+            for (blocker_id, (blocker, source)) in next_blockers.drain() {
+                current_blockers.insert(blocker_id, blocker, source, current_tick);
+            }
+
             event!(Level::DEBUG, new_current_blockers_count = current_blockers.len());
             event!(Level::TRACE, current_blockers = ?current_blockers);
+
+            // Every processor's systems, so a masked read only or background system hides its events from blocking systems too
+            let blocking = memory.resolve::<Shared<ProcessorSystemRegistry>>(None, None, None, None).and_then(Result::ok);
+            let read_only = memory.resolve::<Shared<ReadOnlySystemRegistry>>(None, None, None, None).and_then(Result::ok);
+            let background = memory.resolve::<Shared<BackgroundProcessorSystemRegistry>>(None, None, None, None).and_then(Result::ok);
+            let system_registries = blocking.iter().map(|registry| &registry.0)
+                .chain(read_only.iter().map(|registry| registry.ref_generic()))
+                .chain(background.iter().map(|registry| registry.ref_generic()));
+
+            let mut blocked_events = memory.resolve::<Unique<BlockedEvents>>(None, None, None, None).unwrap().unwrap();
+            **blocked_events = BlockedEvents::collect(&current_blockers, system_registries);
+            event!(Level::TRACE, blocked_events = ?blocked_events);
         })
     }
 }
@@ -1,31 +1,110 @@
-use std::{collections::HashSet, ops::Range};
+use std::{collections::HashMap, ops::Range};
 
-use crate::prelude::BlockerId;
+use crate::prelude::{ActiveBlocker, Blocker, BlockerId, CurrentEvents, ProgramId, SystemId};
 
 #[derive(Debug, Default, Clone)]
-pub struct CurrentBlockers(HashSet<BlockerId>);
+pub struct CurrentBlockers(HashMap<BlockerId, ActiveBlocker>);
 
 impl CurrentBlockers {
-    pub fn tick(&mut self, new_blockers: impl Iterator<Item = BlockerId>) {
-        self.0.clear();
+    pub fn insert(&mut self, blocker_id: BlockerId, blocker: Blocker, source: Option<SystemId>, current_tick: u64) -> Option<ActiveBlocker> {
+        self.0.insert(blocker_id, ActiveBlocker::new(blocker, source, current_tick))
+    }
 
-        self.0.extend(new_blockers);
+    pub fn remove(&mut self, blocker_id: &BlockerId) -> Option<ActiveBlocker> {
+        self.0.remove(blocker_id)
     }
 
-    pub fn read(&self) -> impl Iterator<Item = &BlockerId> {
+    /// Removes the blockers whose lifetime has ended, returning them
+    pub fn expire(&mut self, current_tick: u64, current_events: &CurrentEvents) -> Vec<BlockerId> {
+        let expired = self.0.iter()
+            .filter(|(_, active_blocker)| active_blocker.is_expired(current_tick, current_events))
+            .map(|(blocker_id, _)| blocker_id.clone())
+            .collect::<Vec<_>>();
+
+        for blocker_id in &expired {
+            self.0.remove(blocker_id);
+        }
+
+        expired
+    }
+
+    pub fn read(&self) -> impl Iterator<Item = (&BlockerId, &ActiveBlocker)> {
         self.0.iter()
     }
 
+    pub fn get(&self, blocker_id: &BlockerId) -> Option<&ActiveBlocker> {
+        self.0.get(blocker_id)
+    }
+
+    /// Whether the named blocker is current
     pub fn blocks(&self, blocker: &BlockerId) -> bool {
-        self.0.contains(blocker)
+        self.0.contains_key(blocker)
+    }
+
+    pub fn blocks_system(&self, system_id: &SystemId, program_id: Option<&ProgramId>) -> bool {
+        self.blockers_of(system_id, program_id).next().is_some()
+    }
+
+    /// The current blockers targeting the system
+    pub fn blockers_of<'a>(&'a self, system_id: &'a SystemId, program_id: Option<&'a ProgramId>) -> impl Iterator<Item = (&'a BlockerId, &'a ActiveBlocker)> {
+        self.0.iter().filter(move |(_, active_blocker)| active_blocker.blocker().target().targets(system_id, program_id))
     }
 
     pub fn len(&self) -> usize {
         self.0.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     /// no guarantees about the ordering
     pub fn get_range(&self, amount: Range<usize>) -> impl Iterator<Item = &BlockerId> {
-        self.0.iter().take(amount.end)
+        self.0.keys().take(amount.end)
+    }
+}
+
+#[cfg(test)]
+mod current_blockers_tests {
+    use crate::prelude::EventId;
+
+    use super::*;
+
+    #[test]
+    fn lifetimes() {
+        let mut current_blockers = CurrentBlockers::default();
+        current_blockers.insert(BlockerId::from("Ticks"), Blocker::system("Foo").for_ticks(2), None, 0);
+        current_blockers.insert(BlockerId::from("Event"), Blocker::system("Bar").until_event("Resume"), None, 0);
+        current_blockers.insert(BlockerId::from("Unblocked"), Blocker::system("Baz").until_unblocked(), None, 0);
+
+        let mut current_events = CurrentEvents::default();
+        assert!(current_blockers.expire(1, &current_events).is_empty());
+        assert_eq!(current_blockers.expire(2, &current_events), vec![BlockerId::from("Ticks")]);
+
+        current_events.insert(EventId::from("Resume"));
+        assert_eq!(current_blockers.expire(3, &current_events), vec![BlockerId::from("Event")]);
+
+        assert!(current_blockers.expire(u64::MAX, &current_events).is_empty());
+        assert!(current_blockers.remove(&BlockerId::from("Unblocked")).is_some());
+        assert!(current_blockers.is_empty());
+    }
+
+    #[test]
+    fn targets() {
+        let program_id = ProgramId::from("Program");
+
+        let mut current_blockers = CurrentBlockers::default();
+        current_blockers.insert(BlockerId::from("Set"), Blocker::systems(["Foo", "Bar"]).with_reason("Maintenance"), Some(SystemId::from("Admin")), 0);
+        current_blockers.insert(BlockerId::from("Program"), Blocker::program(program_id.clone()), None, 0);
+
+        assert!(current_blockers.blocks_system(&SystemId::from("Foo"), None));
+        assert!(current_blockers.blocks_system(&SystemId::from("Baz"), Some(&program_id)));
+        assert!(!current_blockers.blocks_system(&SystemId::from("Baz"), None));
+
+        let system_id = SystemId::from("Bar");
+        let (blocker_id, active_blocker) = current_blockers.blockers_of(&system_id, None).next().unwrap();
+        assert_eq!(blocker_id, &BlockerId::from("Set"));
+        assert_eq!(active_blocker.blocker().reason(), Some("Maintenance"));
+        assert_eq!(active_blocker.source(), Some(&SystemId::from("Admin")));
     }
-}
\ No newline at end of file
+}
@@ -1,3 +1,5 @@
 pub mod current_blockers;
 pub mod next_blockers;
-pub mod blocker_manager;
\ No newline at end of file
+pub mod blocker;
+pub mod blocked_events;
+pub mod blocker_manager;
@@ -1,24 +1,51 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use crate::prelude::BlockerId;
+use crate::prelude::{Blocker, BlockerId, ProgramId, SystemId};
 
+/// Blockers & unblocks applied by the `BlockerManager` next tick
 #[derive(Debug, Default)]
-pub struct NextBlockers(HashSet<BlockerId>);
+pub struct NextBlockers {
+    blockers: HashMap<BlockerId, (Blocker, Option<SystemId>)>,
+    unblocks: HashSet<BlockerId>,
+}
 
 impl NextBlockers {
+    /// Blocks the system named `blocker` for 1 tick
     pub fn insert(&mut self, blocker: BlockerId) -> bool {
-        self.0.insert(blocker)
+        let system_id = SystemId::from(blocker.clone().into_id());
+        self.insert_blocker(blocker, Blocker::system(system_id), None).is_none()
+    }
+
+    pub fn insert_blocker(&mut self, blocker_id: BlockerId, blocker: Blocker, source: Option<SystemId>) -> Option<Blocker> {
+        self.blockers.insert(blocker_id, (blocker, source)).map(|(blocker, _)| blocker)
+    }
+
+    pub fn unblock(&mut self, blocker_id: BlockerId) -> bool {
+        self.unblocks.insert(blocker_id)
     }
 
     pub fn remove(&mut self, blocker: &BlockerId) -> bool {
-        self.0.remove(blocker)
+        self.blockers.remove(blocker).is_some()
+    }
+
+    pub fn drain(&mut self) -> impl Iterator<Item = (BlockerId, (Blocker, Option<SystemId>))> {
+        self.blockers.drain()
+    }
+
+    pub fn drain_unblocks(&mut self) -> impl Iterator<Item = BlockerId> {
+        self.unblocks.drain()
     }
 
-    pub fn drain(&mut self) -> impl Iterator<Item = BlockerId> {
-        self.0.drain()
+    /// Whether a cancelling blocker targets the system
+    pub fn cancels(&self, system_id: &SystemId, program_id: Option<&ProgramId>) -> bool {
+        self.blockers.values().any(|(blocker, _)| blocker.cancels() && blocker.target().targets(system_id, program_id))
     }
 
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.blockers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blockers.is_empty()
     }
-}
\ No newline at end of file
+}
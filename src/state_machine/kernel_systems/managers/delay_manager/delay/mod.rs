@@ -1,14 +1,46 @@
-use crate::prelude::EventId;
+use crate::prelude::{EventId, RegisteredDelay};
 
 pub mod registered_delay;
 
+/// An activated `RegisteredDelay`
+#[derive(Debug, Clone, PartialEq)]
 pub struct Delay {
+    registration: usize,
     ends_with: EventId,
-    delayed_by: Option<EventId>
+    delayed_by: Option<EventId>,
+    activated_tick: u64,
+    max_ticks: Option<u64>,
 }
 
 impl Delay {
-    pub fn new(then_inserts: EventId, delayed_by: Option<EventId>) -> Self {
-        Self { then_inserts, delayed_by }
+    pub fn new(registration: usize, registered_delay: &RegisteredDelay, activated_tick: u64) -> Self {
+        Self {
+            registration,
+            ends_with: registered_delay.ends_with().clone(),
+            delayed_by: registered_delay.delayed_by().clone(),
+            activated_tick,
+            max_ticks: registered_delay.max_ticks(),
+        }
+    }
+
+    /// Index into the `DelayRegistry`
+    pub fn registration(&self) -> usize {
+        self.registration
+    }
+
+    pub fn ends_with(&self) -> &EventId {
+        &self.ends_with
+    }
+
+    pub fn delayed_by(&self) -> &Option<EventId> {
+        &self.delayed_by
+    }
+
+    pub fn activated_tick(&self) -> u64 {
+        self.activated_tick
+    }
+
+    pub fn is_expired(&self, current_tick: u64) -> bool {
+        self.max_ticks.is_some_and(|max_ticks| current_tick >= self.activated_tick + max_ticks)
     }
 }
@@ -1,7 +1,49 @@
 use crate::prelude::EventId;
 
+/// When `when` is current, wait while `delayed_by` is current, then insert `ends_with`
+#[derive(Debug, Clone, PartialEq)]
 pub struct RegisteredDelay {
     when: EventId,
     ends_with: EventId,
-    delayed_by: Option<EventId>
-}
\ No newline at end of file
+    delayed_by: Option<EventId>,
+    max_ticks: Option<u64>,
+}
+
+impl RegisteredDelay {
+    /// Without `delayed_by` acts as Map(`when` -> `ends_with`)
+    pub fn new(when: impl Into<EventId>, ends_with: impl Into<EventId>) -> Self {
+        Self {
+            when: when.into(),
+            ends_with: ends_with.into(),
+            delayed_by: None,
+            max_ticks: None,
+        }
+    }
+
+    pub fn with_delayed_by(mut self, delayed_by: impl Into<EventId>) -> Self {
+        self.delayed_by = Some(delayed_by.into());
+        self
+    }
+
+    /// Inserts `ends_with` after this many ticks even if still delayed
+    pub fn with_max_ticks(mut self, max_ticks: u64) -> Self {
+        self.max_ticks = Some(max_ticks);
+        self
+    }
+
+    pub fn when(&self) -> &EventId {
+        &self.when
+    }
+
+    pub fn ends_with(&self) -> &EventId {
+        &self.ends_with
+    }
+
+    pub fn delayed_by(&self) -> &Option<EventId> {
+        &self.delayed_by
+    }
+
+    pub fn max_ticks(&self) -> Option<u64> {
+        self.max_ticks
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use tracing::{Level, event};
 
 use crate::prelude::{CurrentEvents, Delay, DelayRegistry, EventId, NextEvents};
 
@@ -6,26 +6,157 @@ use crate::prelude::{CurrentEvents, Delay, DelayRegistry, EventId, NextEvents};
 pub struct DelayBuffer(Vec<Delay>);
 
 impl DelayBuffer {
-    pub fn stage_activatable(&mut self, delay_registry: &DelayRegistry, current_events: &CurrentEvents) {
-        self.0.extend(delay_registry.get_activatable(current_events));
+    /// A registration already pending isn't activated again
+    pub fn stage_activatable(&mut self, delay_registry: &DelayRegistry, current_events: &CurrentEvents, current_tick: u64) {
+        for (registration, registered_delay) in delay_registry.get_activatable(current_events) {
+            if self.is_pending(registration) {
+                event!(Level::TRACE, registration, "Delay Already Pending");
+                continue;
+            }
+
+            self.0.push(Delay::new(registration, registered_delay, current_tick));
+        }
+    }
+
+    pub fn is_pending(&self, registration: usize) -> bool {
+        self.0.iter().any(|delay| delay.registration() == registration)
+    }
+
+    pub fn pending(&self) -> impl Iterator<Item = &Delay> {
+        self.0.iter()
     }
 
     pub fn get_delays(&self) -> impl Iterator<Item = EventId> {
-        self.0.iter().filter_map(|delay| Some(delay.delayed_by.clone()?) )
-    }
-
-    pub fn tick(&mut self, delay_registry: &DelayRegistry, current_events: &CurrentEvents, next_events: &mut NextEvents) {
-        // for each registered delay, if current events contains the activation event (`from`), become activated
-        self.stage_activatable(&delay_registry, &current_events);
-    
-        let current_delays = self.get_delays().collect::<HashSet<_>>();
-        
-        for delay in self.0.drain(..).collect::<Vec<_>>() {
-            match delay.delayed_by.as_ref() {
-                Some(delayed_by) if current_delays.contains(&delayed_by) => { self.0.push(delay); },
-                _ => { next_events.insert(delay.then_inserts); },
-            };
-        }
+        self.0.iter().filter_map(|delay| delay.delayed_by().clone())
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn tick(&mut self, delay_registry: &DelayRegistry, current_events: &CurrentEvents, next_events: &mut NextEvents, current_tick: u64) {
+        // for each registered delay, if current events contains the activation event (`when`), become activated
+        self.stage_activatable(delay_registry, current_events, current_tick);
+
+        self.0.retain(|delay| {
+            let delayed = delay.delayed_by().as_ref().is_some_and(|delayed_by| current_events.contains(delayed_by));
+
+            if delayed && !delay.is_expired(current_tick) {
+                return true;
+            }
+
+            if delayed {
+                event!(Level::TRACE, registration = delay.registration(), "Delay Expired");
+            }
+
+            next_events.insert(delay.ends_with().clone());
+            false
+        });
     }
 }
 
+#[cfg(test)]
+mod delay_buffer_tests {
+    use crate::prelude::RegisteredDelay;
+
+    use super::*;
+
+    // Runs the delays over the ticks' events, the inserted events being current the same tick as the kernel does.
+    // Returns when `watch` was current
+    fn run(registered_delays: Vec<RegisteredDelay>, ticks: &[&[&str]], watch: &str) -> Vec<usize> {
+        let mut delay_registry = DelayRegistry::default();
+        for registered_delay in registered_delays {
+            delay_registry.push(registered_delay);
+        }
+
+        let mut delay_buffer = DelayBuffer::default();
+        let mut current_events = CurrentEvents::default();
+        let mut current = Vec::new();
+
+        for (tick, events) in ticks.iter().enumerate() {
+            let mut next_events = NextEvents::default();
+            next_events.extend(events.iter().map(|&event| EventId::from(event)));
+
+            delay_buffer.tick(&delay_registry, &current_events, &mut next_events, tick as u64);
+            current_events.tick(next_events.drain());
+
+            if current_events.contains(&EventId::from(watch)) {
+                current.push(tick);
+            }
+        }
+
+        current
+    }
+
+    #[test]
+    fn regular() {
+        let delay = RegisteredDelay::new("Start", "End").with_delayed_by("During");
+        let ticks: &[&[&str]] = &[&["Start", "During"], &["During"], &["During"], &[], &[], &[]];
+
+        // During stops being current at tick 3, seen the tick after
+        assert_eq!(run(vec![delay], ticks, "End"), vec![4]);
+    }
+
+    #[test]
+    fn map() {
+        let delay = RegisteredDelay::new("Start", "End");
+        let ticks: &[&[&str]] = &[&["Start"], &[], &["Start"], &[]];
+
+        assert_eq!(run(vec![delay], ticks, "End"), vec![1, 3]);
+    }
+
+    #[test]
+    fn wait_one() {
+        let delay = RegisteredDelay::new("Start", "End").with_delayed_by("Start");
+        let ticks: &[&[&str]] = &[&["Start"], &[], &[], &[]];
+
+        assert_eq!(run(vec![delay], ticks, "End"), vec![2]);
+    }
+
+    #[test]
+    fn continuous_chain() {
+        let delay = RegisteredDelay::new("Start", "Start").with_delayed_by("Break");
+        let ticks: &[&[&str]] = &[&["Start"], &[], &[], &["Break"], &[], &[]];
+
+        // Break is seen at tick 4 so Start isnt inserted until Break stops
+        assert_eq!(run(vec![delay], ticks, "Start"), vec![0, 1, 2, 3, 5]);
+    }
+
+    #[test]
+    fn no_ends_in_a_row() {
+        let delay = RegisteredDelay::new("Start", "End").with_delayed_by("End");
+        let ticks: &[&[&str]] = &[&["Start"], &["Start"], &[], &[], &[]];
+
+        assert_eq!(run(vec![delay], ticks, "End"), vec![1, 3]);
+    }
+
+    #[test]
+    fn deduplicates_activations() {
+        let mut delay_registry = DelayRegistry::default();
+        delay_registry.push(RegisteredDelay::new("Start", "End").with_delayed_by("During"));
+
+        let mut delay_buffer = DelayBuffer::default();
+        let mut current_events = CurrentEvents::default();
+        current_events.tick([EventId::from("Start"), EventId::from("During")].into_iter());
+
+        for tick in 0..3 {
+            delay_buffer.tick(&delay_registry, &current_events, &mut NextEvents::default(), tick);
+        }
+
+        assert_eq!(delay_buffer.len(), 1);
+        assert_eq!(delay_buffer.pending().next().unwrap().activated_tick(), 0);
+    }
+
+    #[test]
+    fn max_ticks() {
+        let delay = RegisteredDelay::new("Start", "End").with_delayed_by("During").with_max_ticks(2);
+        let ticks: &[&[&str]] = &[&["Start", "During"], &["During"], &["During"], &["During"], &["During"]];
+
+        // activated at tick 1, expires at tick 3
+        assert_eq!(run(vec![delay], ticks, "End"), vec![3]);
+    }
+}
@@ -2,10 +2,19 @@ use std::{pin::Pin, sync::Arc};
 
 use tracing::{Level, event};
 
-use crate::prelude::{CurrentEvents, DelayBuffer, DelayRegistry, KernelSystem, Memory, NextEvents, ProgramId, ProgramKey, ResourceId, Shared, SystemId, Unique};
+use crate::prelude::{CurrentEvents, DelayBuffer, DelayRegistry, KernelSystem, Memory, NextEvents, ProgramId, ProgramKey, RegisteredDelay, ResourceId, Shared, StateMachine, SystemId, TickAccumulator, Unique};
 
+/// Inserts events once their `RegisteredDelay` stops being delayed
 pub struct DelayManager;
 
+impl DelayManager {
+    /// Returns the registration index
+    pub fn insert_delay(state_machine: &StateMachine, registered_delay: RegisteredDelay) -> Option<usize> {
+        let mut delay_registry = state_machine.resolve::<Unique<DelayRegistry>>(None, None, None, None)?.ok()?;
+        Some(delay_registry.push(registered_delay))
+    }
+}
+
 impl KernelSystem for DelayManager {
     fn system_id(&self) -> SystemId {
         SystemId::from("Delay Manager")
@@ -36,10 +45,12 @@ impl KernelSystem for DelayManager {
             let registry = memory.resolve::<Shared<DelayRegistry>>(None, None, None, None).unwrap().unwrap();
             let mut next_events = memory.resolve::<Unique<NextEvents>>(None, None, None, None).unwrap().unwrap();
             let current_events= memory.resolve::<Shared<CurrentEvents>>(None, None, None, None).unwrap().unwrap();
+            let current_tick = memory.resolve::<Shared<TickAccumulator>>(None, None, None, None).unwrap().unwrap().load();
             
             event!(Level::DEBUG, old_next_event_count = next_events.len());
 
-            buffer.tick(&registry, &current_events, &mut next_events);
+            buffer.tick(&registry, &current_events, &mut next_events, current_tick);
+            event!(Level::DEBUG, pending_delays = buffer.len());
             event!(Level::DEBUG, new_next_event_count = next_events.len());
             event!(Level::TRACE, next_events = ?next_events);
         })
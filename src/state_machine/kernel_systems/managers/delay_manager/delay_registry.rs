@@ -1,20 +1,30 @@
-use crate::prelude::{CurrentEvents, Delay, RegisteredDelay};
+use crate::prelude::{CurrentEvents, RegisteredDelay};
 
 #[derive(Default)]
 pub struct DelayRegistry(Vec<RegisteredDelay>);
 
 impl DelayRegistry {
-    pub fn push(&mut self, registered_delay: RegisteredDelay) {
+    /// Returns the registration index
+    pub fn push(&mut self, registered_delay: RegisteredDelay) -> usize {
         self.0.push(registered_delay);
+        self.0.len() - 1
     }
 
-    pub fn get_activatable(&self, current_events: &CurrentEvents) -> impl Iterator<Item = Delay> {
-        self.0.iter().filter_map(|registered_delay| {
-            if current_events.contains(&registered_delay.activated_by) {
-                return Some(Delay::new(registered_delay.then_inserts.clone(), registered_delay.delayed_by.clone()));
-            }
+    pub fn get(&self, registration: usize) -> Option<&RegisteredDelay> {
+        self.0.get(registration)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
 
-            None
-        })
+    pub fn get_activatable<'a>(&'a self, current_events: &'a CurrentEvents) -> impl Iterator<Item = (usize, &'a RegisteredDelay)> {
+        self.0.iter()
+            .enumerate()
+            .filter(|(_, registered_delay)| current_events.contains(registered_delay.when()))
     }
-}
\ No newline at end of file
+}
@@ -4,13 +4,14 @@ pub mod delay;
 pub mod delay_manager;
 
 // Regular use-case:
-// RegisteredDelay { activated_by: Start, then_inserts: End, delayed_by: During }
+// RegisteredDelay::new("Start", "End").with_delayed_by("During")
 // Have Event `Start` sent Once
 // Have Event `During` sent Continuously 
 // When `During` finishes, insert `End`
 
 // Edge use-cases:
 // If no `During` or `delayed_by` is none: Acts as Map(`Start` -> `End`)
-// If `activated_by` == `delayed_by`: If Start only once then: Acts as Wait(1), Map(`Start` -> `End`)
-// If `activated_by` == `then_inserts`: Acts as continuous chain. `delayed_by` breaks the chain
-// If `then_inserts` == `delayed_by`: Can be used to prevent 2 `then_inserts` in a row, if there are 2 `activated_by`
+// If `when` == `delayed_by`: If Start only once then: Acts as Wait(1), Map(`Start` -> `End`)
+// If `when` == `ends_with`: Acts as continuous chain. `delayed_by` breaks the chain
+// If `ends_with` == `delayed_by`: Can be used to prevent 2 `ends_with` in a row, if there are 2 `when`
+// A registered delay is only pending once at a time, and `with_max_ticks` inserts `ends_with` even if still delayed
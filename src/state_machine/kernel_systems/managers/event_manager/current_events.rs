@@ -1,35 +1,103 @@
 use std::{collections::HashSet, ops::Range};
 
-use crate::prelude::EventId;
+use crate::prelude::{EventId, QueuedEvent, SystemId};
 
 #[derive(Debug, Default, Clone)]
-pub struct CurrentEvents(HashSet<EventId>);
+pub struct CurrentEvents {
+    events: HashSet<EventId>,
+    queued: Vec<QueuedEvent>,
+}
 
 impl CurrentEvents {
     pub fn tick(&mut self, new_events: impl Iterator<Item = EventId>) {
-        self.0.clear();
+        self.events.clear();
+        self.queued.clear();
 
-        self.0.extend(new_events);
+        self.events.extend(new_events);
     }
 
+    /// Queued events also count towards `contains` & `read`
+    pub fn extend_queued(&mut self, queued_events: impl Iterator<Item = QueuedEvent>) {
+        for queued_event in queued_events {
+            self.events.insert(queued_event.event_id().clone());
+            self.queued.push(queued_event);
+        }
+    }
+
+    /// Each event once
     pub fn read(&self) -> impl Iterator<Item = &EventId> {
-        self.0.iter()
+        self.events.iter()
+    }
+
+    /// Queued events in emission order
+    pub fn queued(&self) -> impl Iterator<Item = &QueuedEvent> {
+        self.queued.iter()
+    }
+
+    /// Emitters of the event, in emission order
+    pub fn emitters<'a>(&'a self, event: &'a EventId) -> impl Iterator<Item = Option<&'a SystemId>> {
+        self.queued.iter()
+            .filter(move |queued_event| queued_event.event_id() == event)
+            .map(|queued_event| queued_event.emitter())
+    }
+
+    /// How many times the event was emitted, at most 1 for `EventMode::Set` events
+    pub fn count(&self, event: &EventId) -> usize {
+        match self.emitters(event).count() {
+            0 => self.contains(event) as usize,
+            count => count
+        }
     }
 
     pub fn contains(&self, event: &EventId) -> bool {
-        self.0.contains(event)
+        self.events.contains(event)
     }
 
     pub fn insert(&mut self, event: EventId) -> bool {
-        self.0.insert(event)
+        self.events.insert(event)
     }
 
+    /// Also removes its queued emissions
+    pub fn remove(&mut self, event: &EventId) -> bool {
+        let queued = self.queued.len();
+        self.queued.retain(|queued_event| queued_event.event_id() != event);
+
+        self.events.remove(event) || queued != self.queued.len()
+    }
+
+    /// Distinct events
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.events.len()
     }
 
     /// no guarantees about the ordering
     pub fn get_range(&self, amount: Range<usize>) -> impl Iterator<Item = &EventId> {
-        self.0.iter().take(amount.end)
+        self.events.iter().take(amount.end)
+    }
+}
+
+#[cfg(test)]
+mod current_events_tests {
+    use super::*;
+
+    #[test]
+    fn counts_queued() {
+        let mut current_events = CurrentEvents::default();
+        current_events.tick([EventId::from("Bar")].into_iter());
+        current_events.extend_queued([
+            QueuedEvent::new(EventId::from("Foo"), Some(SystemId::from("A"))),
+            QueuedEvent::new(EventId::from("Foo"), Some(SystemId::from("B"))),
+        ].into_iter());
+
+        let foo = EventId::from("Foo");
+        assert!(current_events.contains(&foo));
+        assert_eq!(current_events.count(&foo), 2);
+        assert_eq!(current_events.count(&EventId::from("Bar")), 1);
+        assert_eq!(current_events.count(&EventId::from("Baz")), 0);
+        assert_eq!(current_events.emitters(&foo).collect::<Vec<_>>(), vec![Some(&SystemId::from("A")), Some(&SystemId::from("B"))]);
+        assert_eq!(current_events.len(), 2);
+
+        current_events.tick(std::iter::empty());
+        assert_eq!(current_events.queued().count(), 0);
     }
-}
\ No newline at end of file
+}
@@ -2,10 +2,35 @@ use std::{pin::Pin, sync::Arc};
 
 use tracing::{Level, event};
 
-use crate::prelude::{CurrentEvents, EventMapper, KernelSystem, Memory, NextEvents, ProgramId, ProgramKey, SystemEventRegistry, SystemId, Unique};
+use crate::prelude::{CurrentEvents, CurrentTypedEvents, EventId, EventMode, KernelSystem, Memory, NextEvents, NextTypedEvents, ProgramId, ProgramKey, ResourceId, Shared, StateMachine, SystemEventRegistry, SystemId, TypedEventRegistry, Unique};
 
 pub struct EventManager;
 
+impl EventManager {
+    /// Makes `EventWriter<T>` & `EventReader<T>` resolvable. Some(false) if `T` was already registered
+    pub fn register_event<T: Send + Sync + 'static>(state_machine: &StateMachine) -> Option<bool> {
+        let mut typed_event_registry = state_machine.resolve::<Unique<TypedEventRegistry>>(None, None, None, None)?.ok()?;
+        if typed_event_registry.contains::<T>() {
+            return Some(false);
+        }
+
+        // Buffers first so a failed insert doesn't leave `T` registered without them
+        state_machine.insert(None, None, None, NextTypedEvents::<T>::default())?.ok()?;
+        if !matches!(state_machine.insert(None, None, None, CurrentTypedEvents::<T>::default()), Some(Ok(_))) {
+            state_machine.remove(None, &ResourceId::from_raw_heap::<NextTypedEvents<T>>(), None);
+            return None;
+        }
+
+        Some(typed_event_registry.register::<T>())
+    }
+
+    /// Returns the previous mode
+    pub fn set_event_mode(state_machine: &StateMachine, event: impl Into<EventId>, mode: EventMode) -> Option<EventMode> {
+        let mut next_events = state_machine.resolve::<Unique<NextEvents>>(None, None, None, None)?.ok()?;
+        Some(next_events.set_mode(event, mode))
+    }
+}
+
 impl KernelSystem for EventManager {
     fn system_id(&self) -> SystemId {
         SystemId::from("Event Manager")
@@ -18,11 +43,11 @@ impl KernelSystem for EventManager {
         event!(Level::DEBUG, "Inserting CurrentEvents");
         assert!(memory.insert(None, None, None, CurrentEvents::default()).unwrap().is_ok());
 
-        event!(Level::DEBUG, "Inserting EventMapper");
-        assert!(memory.insert(None, None, None, EventMapper::default()).unwrap().is_ok());
-
         event!(Level::DEBUG, "Inserting SystemEventRegistry");
         assert!(memory.insert(None, None, None, SystemEventRegistry::default()).unwrap().is_ok());
+
+        event!(Level::DEBUG, "Inserting TypedEventRegistry");
+        assert!(memory.insert(None, None, None, TypedEventRegistry::default()).unwrap().is_ok());
     }
 
     fn tick(&mut self, memory: &Arc<Memory>, _kernel_program_id: ProgramId, _kernel_program_key: ProgramKey) -> Pin<Box<dyn Future<Output = ()> + '_ + Send>> {
@@ -34,6 +59,15 @@ impl KernelSystem for EventManager {
             event!(Level::DEBUG, old_current_event_count = current_events.len());
 
             current_events.tick(next_events.drain());
+            current_events.extend_queued(next_events.drain_queued());
+
+            let typed_event_registry = memory.resolve::<Shared<TypedEventRegistry>>(None, None, None, None).unwrap().unwrap();
+            event!(Level::DEBUG, typed_event_count = typed_event_registry.len());
+
+            for event_id in typed_event_registry.tick(&memory) {
+                current_events.insert(event_id);
+            }
+
             event!(Level::DEBUG, new_current_event_count = current_events.len());
             event!(Level::TRACE, current_events = ?current_events);
         })
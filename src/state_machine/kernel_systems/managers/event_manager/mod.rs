@@ -1,5 +1,6 @@
 pub mod next_events;
 pub mod current_events;
 pub mod event_manager;
-pub mod event_mapper;
-pub mod system_event_registry;
\ No newline at end of file
+pub mod system_event_registry;
+pub mod typed_events;
+pub mod queued_event;
\ No newline at end of file
@@ -1,33 +1,131 @@
-use std::{collections::HashSet, ops::Range};
+use std::{collections::{HashMap, HashSet}, ops::Range};
 
-use crate::prelude::EventId;
+use crate::prelude::{EventId, EventMode, QueuedEvent, SystemId};
 
 #[derive(Debug, Default)]
-pub struct NextEvents(HashSet<EventId>);
+pub struct NextEvents {
+    events: HashSet<EventId>,
+    queued: Vec<QueuedEvent>,
+    // Persists across ticks
+    modes: HashMap<EventId, EventMode>,
+}
 
 impl NextEvents {
     pub fn insert<T: Into<EventId>>(&mut self, event: T) -> bool {
-        self.0.insert(event.into())
+        self.emit(event, None)
     }
 
+    /// False if the event is in `EventMode::Set` and was already emitted
+    pub fn emit<T: Into<EventId>>(&mut self, event: T, emitter: Option<SystemId>) -> bool {
+        let event = event.into();
+        match self.mode(&event) {
+            EventMode::Set => self.events.insert(event),
+            EventMode::Queue => {
+                self.queued.push(QueuedEvent::new(event, emitter));
+                true
+            }
+        }
+    }
+
+    /// Returns the previous mode
+    pub fn set_mode<T: Into<EventId>>(&mut self, event: T, mode: EventMode) -> EventMode {
+        self.modes.insert(event.into(), mode).unwrap_or_default()
+    }
+
+    pub fn mode(&self, event: &EventId) -> EventMode {
+        self.modes.get(event).copied().unwrap_or_default()
+    }
+
+    /// Removes the event and every queued emission of it
     pub fn remove(&mut self, event: &EventId) -> bool {
-        self.0.remove(event)
+        let queued = self.queued.len();
+        self.queued.retain(|queued_event| queued_event.event_id() != event);
+
+        self.events.remove(event) || queued != self.queued.len()
     }
 
     pub fn extend(&mut self, events: impl Iterator<Item = EventId>) {
-        self.0.extend(events);
+        for event in events {
+            self.insert(event);
+        }
     }
 
+    pub fn contains(&self, event: &EventId) -> bool {
+        self.events.contains(event) || self.queued.iter().any(|queued_event| queued_event.event_id() == event)
+    }
+
+    /// Only events in `EventMode::Set`
     pub fn drain(&mut self) -> impl Iterator<Item = EventId> {
-        self.0.drain()
+        self.events.drain()
+    }
+
+    /// Events in `EventMode::Queue`, in emission order
+    pub fn drain_queued(&mut self) -> impl Iterator<Item = QueuedEvent> {
+        self.queued.drain(..)
     }
 
+    /// Each event once, queued or not
+    pub fn read(&self) -> impl Iterator<Item = &EventId> {
+        self.events.iter().chain(self.queued.iter().map(QueuedEvent::event_id))
+    }
+
+    /// Counts every queued emission
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.events.len() + self.queued.len()
     }
 
     /// no guarantees about the ordering
     pub fn get_range(&self, amount: Range<usize>) -> impl Iterator<Item = &EventId> {
-        self.0.iter().take(amount.end)
+        self.events.iter().take(amount.end)
+    }
+}
+
+#[cfg(test)]
+mod next_events_tests {
+    use super::*;
+
+    #[test]
+    fn set_collapses() {
+        let mut next_events = NextEvents::default();
+
+        assert!(next_events.emit("Foo", Some(SystemId::from("A"))));
+        assert!(!next_events.emit("Foo", Some(SystemId::from("B"))));
+
+        assert_eq!(next_events.len(), 1);
+        assert_eq!(next_events.drain_queued().count(), 0);
+    }
+
+    #[test]
+    fn queue_keeps_order() {
+        let mut next_events = NextEvents::default();
+        assert_eq!(next_events.set_mode("Foo", EventMode::Queue), EventMode::Set);
+
+        next_events.emit("Foo", Some(SystemId::from("A")));
+        next_events.insert("Bar");
+        next_events.emit("Foo", Some(SystemId::from("B")));
+        next_events.insert("Foo");
+
+        assert_eq!(next_events.len(), 4);
+        assert!(next_events.contains(&EventId::from("Foo")));
+
+        let emitters = next_events.drain_queued().map(|queued_event| queued_event.emitter().cloned()).collect::<Vec<_>>();
+        assert_eq!(emitters, vec![Some(SystemId::from("A")), Some(SystemId::from("B")), None]);
+        assert_eq!(next_events.drain().collect::<Vec<_>>(), vec![EventId::from("Bar")]);
+
+        // Mode persists after draining
+        assert_eq!(next_events.mode(&EventId::from("Foo")), EventMode::Queue);
+    }
+
+    #[test]
+    fn remove_clears_queue() {
+        let mut next_events = NextEvents::default();
+        next_events.set_mode("Foo", EventMode::Queue);
+
+        next_events.insert("Foo");
+        next_events.insert("Foo");
+
+        assert!(next_events.remove(&EventId::from("Foo")));
+        assert!(!next_events.contains(&EventId::from("Foo")));
+        assert!(!next_events.remove(&EventId::from("Foo")));
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,35 @@
+use crate::prelude::{EventId, SystemId};
+
+/// How repeated emissions of an event within a tick are kept
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EventMode {
+    /// Emissions collapse into one
+    #[default]
+    Set,
+    /// Every emission is kept, in order, with its emitter
+    Queue,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedEvent {
+    event_id: EventId,
+    emitter: Option<SystemId>,
+}
+
+impl QueuedEvent {
+    pub fn new(event_id: EventId, emitter: Option<SystemId>) -> Self {
+        Self {
+            event_id,
+            emitter
+        }
+    }
+
+    pub fn event_id(&self) -> &EventId {
+        &self.event_id
+    }
+
+    /// None if not emitted by a system
+    pub fn emitter(&self) -> Option<&SystemId> {
+        self.emitter.as_ref()
+    }
+}
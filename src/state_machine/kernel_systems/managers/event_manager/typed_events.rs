@@ -0,0 +1,120 @@
+use std::{any::TypeId, collections::HashMap};
+
+use tracing::{Level, event};
+
+use crate::prelude::{EventId, Memory, Unique};
+
+/// Payloads written this tick, read next tick
+#[derive(Debug)]
+pub struct NextTypedEvents<T>(Vec<T>);
+
+impl<T> Default for NextTypedEvents<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<T> NextTypedEvents<T> {
+    pub fn send(&mut self, event: T) {
+        self.0.push(event);
+    }
+
+    pub fn extend(&mut self, events: impl IntoIterator<Item = T>) {
+        self.0.extend(events);
+    }
+
+    pub fn drain(&mut self) -> impl Iterator<Item = T> {
+        self.0.drain(..)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Payloads written last tick, in the order they were sent
+#[derive(Debug)]
+pub struct CurrentTypedEvents<T>(Vec<T>);
+
+impl<T> Default for CurrentTypedEvents<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<T> CurrentTypedEvents<T> {
+    pub fn tick(&mut self, new_events: impl Iterator<Item = T>) {
+        self.0.clear();
+
+        self.0.extend(new_events);
+    }
+
+    pub fn read(&self) -> impl Iterator<Item = &T> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+// Returns how many events are current after rotating, None if the buffers are inaccessible
+type RotateTypedEvents = Box<dyn Fn(&Memory) -> Option<usize> + Send + Sync>;
+
+/// The typed events the `EventManager` rotates each tick
+#[derive(Default)]
+pub struct TypedEventRegistry(HashMap<TypeId, (EventId, RotateTypedEvents)>);
+
+impl TypedEventRegistry {
+    /// False if `T` is already registered. Does not insert the buffers
+    pub fn register<T: Send + Sync + 'static>(&mut self) -> bool {
+        if self.contains::<T>() {
+            return false;
+        }
+
+        let rotate: RotateTypedEvents = Box::new(|memory| {
+            let mut next_events = memory.resolve::<Unique<NextTypedEvents<T>>>(None, None, None, None)?.ok()?;
+            let mut current_events = memory.resolve::<Unique<CurrentTypedEvents<T>>>(None, None, None, None)?.ok()?;
+
+            current_events.tick(next_events.drain());
+            Some(current_events.len())
+        });
+
+        self.0.insert(TypeId::of::<T>(), (EventId::of::<T>(), rotate));
+        true
+    }
+
+    pub fn contains<T: 'static>(&self) -> bool {
+        self.0.contains_key(&TypeId::of::<T>())
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Moves every typed event's next buffer into its current buffer, returning the ids of those with current events
+    pub fn tick(&self, memory: &Memory) -> Vec<EventId> {
+        self.0.values().filter_map(|(event_id, rotate)| {
+            match rotate(memory) {
+                Some(0) => None,
+                Some(_) => Some(event_id.clone()),
+                None => {
+                    event!(Level::WARN, event_id = ?event_id, "Failed to Rotate Typed Events");
+                    None
+                }
+            }
+        }).collect()
+    }
+}
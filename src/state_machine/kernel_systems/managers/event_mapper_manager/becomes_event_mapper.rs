@@ -0,0 +1,93 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::prelude::{CurrentEvents, EventId, IsEventMapper};
+
+/// Transitions: when `from` is current, every event it becomes is emitted into the next tick
+#[derive(Debug, Default)]
+pub struct BecomesEventMapper(HashMap<EventId, HashSet<EventId>>);
+
+impl BecomesEventMapper {
+    /// False if the mapping already existed
+    pub fn insert(&mut self, from: EventId, to: EventId) -> bool {
+        self.0.entry(from).or_default().insert(to)
+    }
+
+    pub fn get(&self, from: &EventId) -> Option<&HashSet<EventId>> {
+        self.0.get(from)
+    }
+
+    /// The shortest chain of transitions from `from` to (an alias of) `to`, treating aliases as the same event
+    pub fn path(&self, from: &EventId, to: &EventId, is_event_mapper: &IsEventMapper) -> Option<Vec<EventId>> {
+        let mut previous: HashMap<&EventId, &EventId> = HashMap::new();
+        let mut visited = HashSet::from([is_event_mapper.representative(from)]);
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(current) = queue.pop_front() {
+            if is_event_mapper.is_equivalent(current, to) {
+                let mut path = vec![current.clone()];
+                let mut current = current;
+                while let Some(&before) = previous.get(current) {
+                    path.push(before.clone());
+                    current = before;
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            let next = is_event_mapper.equivalents(current)
+                .filter_map(|alias| self.0.get(alias))
+                .flatten();
+
+            for next in next {
+                if visited.insert(is_event_mapper.representative(next)) {
+                    previous.insert(next, current);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// (current event, event it becomes)
+    pub fn tick<'a>(&'a self, current_events: &'a CurrentEvents) -> Vec<(&'a EventId, &'a EventId)> {
+        current_events.read()
+            .filter_map(|event| self.0.get(event).map(|to| (event, to)))
+            .flat_map(|(event, to)| to.iter().map(move |to| (event, to)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod becomes_event_mapper_tests {
+    use super::*;
+
+    #[test]
+    fn finds_paths_through_aliases() {
+        let mut becomes_event_mapper = BecomesEventMapper::default();
+        let mut is_event_mapper = IsEventMapper::default();
+
+        becomes_event_mapper.insert(EventId::from("A"), EventId::from("B"));
+        becomes_event_mapper.insert(EventId::from("C"), EventId::from("D"));
+
+        assert!(becomes_event_mapper.path(&EventId::from("A"), &EventId::from("D"), &is_event_mapper).is_none());
+
+        is_event_mapper.insert(EventId::from("B"), EventId::from("C"));
+        assert_eq!(
+            becomes_event_mapper.path(&EventId::from("A"), &EventId::from("D"), &is_event_mapper),
+            Some(vec![EventId::from("A"), EventId::from("B"), EventId::from("D")])
+        );
+    }
+
+    #[test]
+    fn ticks_current() {
+        let mut becomes_event_mapper = BecomesEventMapper::default();
+        becomes_event_mapper.insert(EventId::from("A"), EventId::from("B"));
+        becomes_event_mapper.insert(EventId::from("C"), EventId::from("D"));
+
+        let mut current_events = CurrentEvents::default();
+        current_events.tick([EventId::from("A")].into_iter());
+
+        assert_eq!(becomes_event_mapper.tick(&current_events), vec![(&EventId::from("A"), &EventId::from("B"))]);
+    }
+}
@@ -0,0 +1,100 @@
+use std::{pin::Pin, sync::Arc};
+
+use tracing::{Level, event};
+
+use crate::prelude::{BecomesEventMapper, CurrentEvents, EventId, IsEventMapper, KernelSystem, MappingError, Memory, NextEvents, ProgramId, ProgramKey, ResourceId, Shared, SystemId, Unique};
+
+/// "Is" happens before "Becomes", so an event becomes what its aliases become
+pub struct EventMapperManager;
+
+impl EventMapperManager {
+    /// Makes `a` & `b` (and their existing aliases) equivalent. Ok(false) if they already were
+    pub fn insert_is(memory: &Memory, a: EventId, b: EventId) -> Option<Result<bool, MappingError>> {
+        if a == b {
+            return Some(Err(MappingError::SelfMapping(a)));
+        }
+
+        let mut is_event_mapper = memory.resolve::<Unique<IsEventMapper>>(None, None, None, None)?.ok()?;
+        let becomes_event_mapper = memory.resolve::<Shared<BecomesEventMapper>>(None, None, None, None)?.ok()?;
+
+        // an existing transition between them would then transition into itself
+        if let Some(mut path) = becomes_event_mapper.path(&a, &b, &is_event_mapper)
+            .or_else(|| becomes_event_mapper.path(&b, &a, &is_event_mapper))
+            .filter(|path| path.len() > 1)
+        {
+            path.push(path[0].clone());
+            return Some(Err(MappingError::Cycle(path)));
+        }
+
+        Some(Ok(is_event_mapper.insert(a, b)))
+    }
+
+    /// `from` becomes `to` next tick. Ok(false) if the mapping already existed
+    pub fn insert_becomes(memory: &Memory, from: EventId, to: EventId) -> Option<Result<bool, MappingError>> {
+        if from == to {
+            return Some(Err(MappingError::SelfMapping(from)));
+        }
+
+        let is_event_mapper = memory.resolve::<Shared<IsEventMapper>>(None, None, None, None)?.ok()?;
+        let mut becomes_event_mapper = memory.resolve::<Unique<BecomesEventMapper>>(None, None, None, None)?.ok()?;
+
+        if let Some(mut path) = becomes_event_mapper.path(&to, &from, &is_event_mapper) {
+            path.insert(0, from);
+            return Some(Err(MappingError::Cycle(path)));
+        }
+
+        Some(Ok(becomes_event_mapper.insert(from, to)))
+    }
+}
+
+impl KernelSystem for EventMapperManager {
+    fn system_id(&self) -> SystemId {
+        SystemId::from("Event Mapper Manager")
+    }
+
+    fn init(&mut self, memory: &Memory, _kernel_program_id: &ProgramId, _kernel_program_key: &ProgramKey) {
+        event!(Level::DEBUG, "Inserting IsEventMapper");
+        assert!(memory.insert(None, None, None, IsEventMapper::default()).unwrap().is_ok());
+
+        event!(Level::DEBUG, "Inserting BecomesEventMapper");
+        assert!(memory.insert(None, None, None, BecomesEventMapper::default()).unwrap().is_ok());
+
+        event!(Level::DEBUG, "Checking CurrentEvents");
+        if !matches!(memory.contains_resource(None, &ResourceId::from_raw_heap::<CurrentEvents>(), None), Some(true)) {
+            event!(Level::WARN, "CurrentEvents Not Found")
+        }
+    }
+
+    fn tick(&mut self, memory: &Arc<Memory>, _kernel_program_id: ProgramId, _kernel_program_key: ProgramKey) -> Pin<Box<dyn Future<Output = ()> + '_ + Send>> {
+        let memory = Arc::clone(&memory);
+        Box::pin(async move {
+            let mut current_events = memory.resolve::<Unique<CurrentEvents>>(None, None, None, None).unwrap().unwrap();
+            let mut next_events = memory.resolve::<Unique<NextEvents>>(None, None, None, None).unwrap().unwrap();
+
+            let is_event_mapper = memory.resolve::<Shared<IsEventMapper>>(None, None, None, None).unwrap().unwrap();
+            let becomes_event_mapper = memory.resolve::<Shared<BecomesEventMapper>>(None, None, None, None).unwrap().unwrap();
+
+            let aliases = is_event_mapper.tick(&current_events)
+                .into_iter()
+                .map(|(event_id, alias)| {
+                    event!(Level::TRACE, event_id = ?event_id, alias = ?alias, "Is Mapped");
+                    alias.clone()
+                })
+                .collect::<Vec<_>>();
+
+            event!(Level::DEBUG, is_mapped_count = aliases.len());
+            for alias in aliases {
+                current_events.insert(alias);
+            }
+
+            let mut becomes_mapped_count = 0;
+            for (event_id, becomes) in becomes_event_mapper.tick(&current_events) {
+                event!(Level::TRACE, event_id = ?event_id, becomes = ?becomes, "Becomes Mapped");
+                next_events.insert(becomes.clone());
+                becomes_mapped_count += 1;
+            }
+
+            event!(Level::DEBUG, becomes_mapped_count);
+        })
+    }
+}
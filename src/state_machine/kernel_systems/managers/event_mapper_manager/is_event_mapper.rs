@@ -0,0 +1,104 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::prelude::{CurrentEvents, EventId};
+
+/// Aliases: when any event in a group is current, every event in the group is current
+#[derive(Debug, Default)]
+pub struct IsEventMapper {
+    // event -> representative of its group
+    representatives: HashMap<EventId, EventId>,
+    // representative -> group (including the representative)
+    groups: HashMap<EventId, HashSet<EventId>>,
+}
+
+impl IsEventMapper {
+    /// False if they were already equivalent
+    pub fn insert(&mut self, a: EventId, b: EventId) -> bool {
+        let a = self.representative(&a).clone();
+        let b = self.representative(&b).clone();
+
+        if a == b {
+            return false;
+        }
+
+        let mut group_a = self.groups.remove(&a).unwrap_or_else(|| HashSet::from([a.clone()]));
+        let mut group_b = self.groups.remove(&b).unwrap_or_else(|| HashSet::from([b.clone()]));
+
+        // merge the smaller group into the larger
+        let representative = if group_a.len() >= group_b.len() {
+            group_a.extend(group_b.drain());
+            a
+        } else {
+            group_b.extend(group_a.drain());
+            std::mem::swap(&mut group_a, &mut group_b);
+            b
+        };
+
+        for event in &group_a {
+            self.representatives.insert(event.clone(), representative.clone());
+        }
+
+        self.groups.insert(representative, group_a);
+        true
+    }
+
+    /// Events not aliased are their own representative
+    pub fn representative<'a>(&'a self, event: &'a EventId) -> &'a EventId {
+        self.representatives.get(event).unwrap_or(event)
+    }
+
+    pub fn is_equivalent(&self, a: &EventId, b: &EventId) -> bool {
+        self.representative(a) == self.representative(b)
+    }
+
+    /// Every event equivalent to `event`, including itself
+    pub fn equivalents<'a>(&'a self, event: &'a EventId) -> Box<dyn Iterator<Item = &'a EventId> + 'a> {
+        match self.groups.get(self.representative(event)) {
+            Some(group) => Box::new(group.iter()),
+            None => Box::new(std::iter::once(event)),
+        }
+    }
+
+    /// (current event, alias) for every alias that isn't already current
+    pub fn tick<'a>(&'a self, current_events: &'a CurrentEvents) -> Vec<(&'a EventId, &'a EventId)> {
+        let mut mapped = HashSet::new();
+
+        current_events.read()
+            .flat_map(|event| self.equivalents(event).map(move |alias| (event, alias)))
+            .filter(|(_, alias)| !current_events.contains(alias) && mapped.insert(*alias))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod is_event_mapper_tests {
+    use super::*;
+
+    #[test]
+    fn groups_merge() {
+        let mut is_event_mapper = IsEventMapper::default();
+
+        assert!(is_event_mapper.insert(EventId::from("A"), EventId::from("B")));
+        assert!(is_event_mapper.insert(EventId::from("C"), EventId::from("D")));
+        assert!(!is_event_mapper.insert(EventId::from("B"), EventId::from("A")));
+        assert!(is_event_mapper.insert(EventId::from("D"), EventId::from("A")));
+
+        assert!(is_event_mapper.is_equivalent(&EventId::from("B"), &EventId::from("C")));
+        assert!(!is_event_mapper.is_equivalent(&EventId::from("A"), &EventId::from("E")));
+        assert_eq!(is_event_mapper.equivalents(&EventId::from("C")).count(), 4);
+        assert_eq!(is_event_mapper.equivalents(&EventId::from("E")).collect::<Vec<_>>(), vec![&EventId::from("E")]);
+    }
+
+    #[test]
+    fn ticks_missing_aliases() {
+        let mut is_event_mapper = IsEventMapper::default();
+        is_event_mapper.insert(EventId::from("A"), EventId::from("B"));
+        is_event_mapper.insert(EventId::from("A"), EventId::from("C"));
+
+        let mut current_events = CurrentEvents::default();
+        current_events.tick([EventId::from("A"), EventId::from("B")].into_iter());
+
+        let mapped = is_event_mapper.tick(&current_events).into_iter().map(|(_, alias)| alias.clone()).collect::<Vec<_>>();
+        assert_eq!(mapped, vec![EventId::from("C")]);
+    }
+}
@@ -0,0 +1,9 @@
+use crate::prelude::EventId;
+
+#[derive(Debug, PartialEq)]
+pub enum MappingError {
+    /// An event mapped to itself
+    SelfMapping(EventId),
+    /// The mapping would make these events fire each other forever, in order
+    Cycle(Vec<EventId>),
+}
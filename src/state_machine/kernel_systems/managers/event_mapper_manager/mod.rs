@@ -0,0 +1,4 @@
+pub mod event_mapper_manager;
+pub mod is_event_mapper;
+pub mod becomes_event_mapper;
+pub mod mapping_error;
@@ -0,0 +1,23 @@
+/// What a run does when one of its stages fails, set per pipeline with `ExecutableManager::set_error_policy`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ErrorPolicy {
+    /// Fails the run
+    #[default]
+    Abort,
+    /// Continues as if the stage output its input
+    Skip,
+    /// Reruns the stage `backoff` ticks later, up to `attempts` times, before failing the run
+    Retry { attempts: u32, backoff: u64 },
+    /// Runs the named stage with the failing stage's input, then fails the run
+    DeadLetter(String),
+}
+
+impl ErrorPolicy {
+    pub fn retry(attempts: u32, backoff: u64) -> Self {
+        Self::Retry { attempts, backoff }
+    }
+
+    pub fn dead_letter(label: impl Into<String>) -> Self {
+        Self::DeadLetter(label.into())
+    }
+}
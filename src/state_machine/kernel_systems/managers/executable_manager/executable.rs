@@ -1,16 +1,67 @@
-use crate::prelude::EventId;
+use crate::prelude::{EventId, RunId, SystemId};
+
+/// When a triggered stage completes
+#[derive(Debug, Clone, PartialEq)]
+pub enum Completion {
+    /// The tick after it is triggered
+    Immediate,
+    /// On `EventId::completed(label, run_id)`, see `BufferedExecutable::completed`
+    Run,
+    /// On a shared event, completing every run waiting on the stage
+    Event(EventId),
+}
 
 #[derive(Clone)]
 pub struct Executable {
     pub label: String,
     pub trigger: EventId,
+    pub completion: Completion,
+    /// Fails the run if not complete after this many ticks
+    pub timeout: Option<u64>,
+    /// The system handling the stage, see `ExecutableInput` & `ExecutableOutput`.
+    /// Its `SystemResult::Error` fails the runs it resolved those for
+    pub system: Option<SystemId>,
 }
 
 impl Executable {
+    /// Completes on `EventId::completed(label, run_id)`
     pub fn new(label: String, trigger: EventId) -> Self {
         Self {
+            completion: Completion::Run,
             label,
-            trigger
+            trigger,
+            timeout: None,
+            system: None,
+        }
+    }
+
+    pub fn with_completion(mut self, completion: impl Into<EventId>) -> Self {
+        self.completion = Completion::Event(completion.into());
+        self
+    }
+
+    /// Doesn't wait for the triggered system
+    pub fn immediate(mut self) -> Self {
+        self.completion = Completion::Immediate;
+        self
+    }
+
+    pub fn with_timeout(mut self, ticks: u64) -> Self {
+        self.timeout = Some(ticks);
+        self
+    }
+
+    pub fn with_system(mut self, system: impl Into<SystemId>) -> Self {
+        self.system = Some(system.into());
+        self
+    }
+
+    /// The event completing the stage for `run_id`, None if immediate
+    pub fn completion_event(&self, run_id: RunId) -> Option<EventId> {
+        match &self.completion {
+            Completion::Immediate => None,
+            Completion::Run => Some(EventId::completed(&self.label, run_id)),
+            Completion::Event(event_id) => Some(event_id.clone()),
         }
     }
 }
@@ -1,23 +1,183 @@
-use crate::prelude::{ExecutableLabel, ExecutableMessage};
+use std::collections::{BTreeMap, HashMap};
 
-#[derive(Debug)]
+use crate::prelude::{EventId, ExecutableLabel, ExecutableMessage, RunId, SystemId};
+
+#[derive(Debug, Clone)]
 pub struct BufferedExecutable {
+    pub run_id: RunId,
     pub label: ExecutableLabel,
     pub source: ExecutableMessage,
-    pub target: ExecutableMessage
+    pub target: ExecutableMessage,
+    pub system: Option<SystemId>,
+    /// The tick the stage was triggered
+    pub tick: u64,
 }
 
 impl BufferedExecutable {
-    pub fn new(label: ExecutableLabel, source: ExecutableMessage, target: ExecutableMessage) -> Self {
-        Self { label, source, target }
+    pub fn new(run_id: RunId, label: ExecutableLabel, source: ExecutableMessage, target: ExecutableMessage) -> Self {
+        Self { run_id, label, source, target, system: None, tick: 0 }
+    }
+
+    pub fn with_system(mut self, system: Option<SystemId>) -> Self {
+        self.system = system;
+        self
+    }
+
+    pub fn with_tick(mut self, tick: u64) -> Self {
+        self.tick = tick;
+        self
+    }
+
+    /// Completes this stage of this run only, see `Completion::Run`
+    pub fn completed(&self) -> EventId {
+        EventId::completed(self.label.get_label(), self.run_id)
+    }
+
+    /// Fails this stage of this run only
+    pub fn failed(&self) -> EventId {
+        EventId::failed(self.label.get_label(), self.run_id)
     }
 }
 
+/// The stages currently running, keyed by run
 #[derive(Default)]
-pub struct ExecutableBuffer(Vec<BufferedExecutable>);
+pub struct ExecutableBuffer {
+    runs: BTreeMap<RunId, Vec<BufferedExecutable>>,
+    /// The runs of the stages last triggered for each system, see `claim`
+    claims: HashMap<SystemId, Vec<RunId>>,
+}
 
 impl ExecutableBuffer {
     pub fn push(&mut self, buffered_executable: BufferedExecutable) {
-        self.0.push(buffered_executable);
+        self.runs.entry(buffered_executable.run_id).or_default().push(buffered_executable);
+    }
+
+    /// Consumes the oldest matching stage
+    pub fn remove(&mut self, run_id: RunId, label: &str) -> Option<BufferedExecutable> {
+        let run = self.runs.get_mut(&run_id)?;
+        let index = run.iter().position(|buffered_executable| buffered_executable.label.get_label() == label)?;
+        let removed = run.remove(index);
+
+        if run.is_empty() {
+            self.runs.remove(&run_id);
+        }
+
+        if let Some(system_id) = &removed.system {
+            self.release(system_id, run_id);
+        }
+
+        Some(removed)
+    }
+
+    pub fn remove_run(&mut self, run_id: RunId) -> Vec<BufferedExecutable> {
+        let removed = self.runs.remove(&run_id).unwrap_or_default();
+        for system_id in removed.iter().filter_map(|buffered_executable| buffered_executable.system.as_ref()) {
+            self.release(system_id, run_id);
+        }
+
+        removed
+    }
+
+    /// Replaces the claims of each system addressed by a stage triggered at `tick` with the runs of these stages,
+    /// the ones its systems resolve that tick. Its `EventId::errored` only fails these
+    pub fn claim(&mut self, tick: u64) {
+        let mut claims: HashMap<SystemId, Vec<RunId>> = HashMap::new();
+        for buffered_executable in self.read().filter(|buffered_executable| buffered_executable.tick == tick) {
+            if let Some(system_id) = &buffered_executable.system {
+                claims.entry(system_id.clone()).or_default().push(buffered_executable.run_id);
+            }
+        }
+
+        self.claims.extend(claims);
+    }
+
+    pub fn claimed(&self, system_id: &SystemId, run_id: RunId) -> bool {
+        self.claims.get(system_id).is_some_and(|runs| runs.contains(&run_id))
+    }
+
+    fn release(&mut self, system_id: &SystemId, run_id: RunId) {
+        if let Some(runs) = self.claims.get_mut(system_id) {
+            runs.retain(|claimed| *claimed != run_id);
+            if runs.is_empty() {
+                self.claims.remove(system_id);
+            }
+        }
+    }
+
+    /// The stages of a run, oldest first
+    pub fn run(&self, run_id: RunId) -> &[BufferedExecutable] {
+        self.runs.get(&run_id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// The stages handled by `system_id`, oldest run first
+    pub fn addressed<'a>(&'a self, system_id: &'a SystemId) -> impl Iterator<Item = &'a BufferedExecutable> {
+        self.read().filter(move |buffered_executable| buffered_executable.system.as_ref() == Some(system_id))
+    }
+
+    /// Oldest run first
+    pub fn read(&self) -> impl Iterator<Item = &BufferedExecutable> {
+        self.runs.values().flatten()
+    }
+
+    pub fn len(&self) -> usize {
+        self.runs.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod executable_buffer_tests {
+    use crate::prelude::{BufferedExecutable, ExecutableBuffer, ExecutableLabel, ExecutableMessage, ResourceId, RunId, SystemId};
+
+    fn buffered(run_id: u64, label: &str, system: &str) -> BufferedExecutable {
+        let message = ExecutableMessage::ResourceId(ResourceId::from_raw_heap::<i32>());
+        BufferedExecutable::new(RunId::new(run_id), ExecutableLabel::new(label.to_string()), message.clone(), message)
+            .with_system(Some(SystemId::from(system)))
+    }
+
+    #[test]
+    fn keyed_by_run() {
+        let mut buffer = ExecutableBuffer::default();
+        buffer.push(buffered(1, "A", "A System"));
+        buffer.push(buffered(0, "A", "A System"));
+        buffer.push(buffered(0, "B", "B System"));
+        assert_eq!(buffer.len(), 3);
+
+        let system_id = SystemId::from("A System");
+        let runs: Vec<RunId> = buffer.addressed(&system_id).map(|buffered_executable| buffered_executable.run_id).collect();
+        assert_eq!(runs, vec![RunId::new(0), RunId::new(1)]);
+
+        assert!(buffer.remove(RunId::new(1), "B").is_none());
+        assert!(buffer.remove(RunId::new(1), "A").is_some());
+        assert!(buffer.run(RunId::new(1)).is_empty());
+
+        assert_eq!(buffer.remove_run(RunId::new(0)).len(), 2);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn claims_are_released_with_their_stage() {
+        let mut buffer = ExecutableBuffer::default();
+        buffer.push(buffered(0, "A", "A System"));
+        buffer.push(buffered(1, "A", "A System"));
+
+        let system_id = SystemId::from("A System");
+        buffer.claim(0);
+        assert!(buffer.remove(RunId::new(0), "A").is_some());
+        assert!(!buffer.claimed(&system_id, RunId::new(0)));
+        assert!(buffer.claimed(&system_id, RunId::new(1)));
+
+        // Replaced by the stages triggered later
+        buffer.push(buffered(2, "A", "A System").with_tick(1));
+        buffer.claim(1);
+        assert!(!buffer.claimed(&system_id, RunId::new(1)));
+        assert!(buffer.claimed(&system_id, RunId::new(2)));
+
+        // Kept while no stage is triggered for the system
+        buffer.claim(2);
+        assert!(buffer.claimed(&system_id, RunId::new(2)));
     }
 }
@@ -0,0 +1,97 @@
+use std::{any::Any, collections::{HashMap, HashSet}};
+
+use tracing::{Level, event};
+
+use crate::prelude::{ExecutableBuffer, HeapObject, Memory, RawHeapObject, Resource, ResourceId};
+
+/// The resources read by the stages addressed to systems, see `ExecutableInput`.
+/// Each is moved out of memory while such a stage is buffered, so reading it is declared through `Shared<ExecutableInputs>`
+#[derive(Default)]
+pub struct ExecutableInputs(HashMap<ResourceId, Box<dyn Any>>);
+
+impl ExecutableInputs {
+    /// None: Not lent (yet) or not a `T`
+    pub fn get<T: 'static>(&self, resource_id: &ResourceId) -> Option<&T> {
+        self.0.get(resource_id)?.downcast_ref()
+    }
+
+    pub fn contains(&self, resource_id: &ResourceId) -> bool {
+        self.0.contains_key(resource_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the resources no buffered stage reads anymore & lends the newly read ones, retried while accessed or missing.
+    /// A resource inserted again while lent replaces the lent one
+    pub fn lend(&mut self, memory: &Memory, buffer: &ExecutableBuffer) {
+        let read: HashSet<&ResourceId> = buffer.read()
+            .filter(|buffered_executable| buffered_executable.system.is_some())
+            .flat_map(|buffered_executable| buffered_executable.source.resource_ids())
+            .collect();
+
+        let returned: Vec<ResourceId> = self.0.keys().filter(|resource_id| !read.contains(resource_id)).cloned().collect();
+        for resource_id in returned {
+            let value = self.0.remove(&resource_id).unwrap();
+            if memory.contains_resource(None, &resource_id, None) == Some(true) {
+                event!(Level::TRACE, resource_id=?resource_id, "Executable Input Replaced");
+                continue;
+            }
+
+            if !matches!(memory.insert_resource(None, resource_id.clone(), None, Resource::Heap(HeapObject(RawHeapObject::new(value)))), Some(Ok(_))) {
+                event!(Level::WARN, resource_id=?resource_id, "Executable Input Dropped");
+            }
+        }
+
+        for resource_id in read {
+            if self.0.contains_key(resource_id) {
+                continue;
+            }
+
+            match memory.remove(None, resource_id, None) {
+                Some(Ok(Some(Resource::Heap(HeapObject(value))))) => {
+                    self.0.insert(resource_id.clone(), value.consume());
+                },
+                Some(Err(_)) => event!(Level::TRACE, resource_id=?resource_id, "Executable Input Accessed (Retrying)"),
+                // Not written yet
+                _ => {},
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod executable_inputs_tests {
+    use crate::prelude::{BufferedExecutable, ExecutableBuffer, ExecutableInputs, ExecutableLabel, ExecutableMessage, Memory, ResourceId, RunId, Shared, SystemId};
+
+    #[test]
+    fn lent_while_read() {
+        let memory = Memory::new();
+        let input = ResourceId::from_labelled_heap("In");
+        assert!(memory.insert(None, Some(input.clone()), None, 1_i32).unwrap().is_ok());
+
+        let mut buffer = ExecutableBuffer::default();
+        buffer.push(BufferedExecutable::new(
+            RunId::new(0),
+            ExecutableLabel::new("A".to_string()),
+            ExecutableMessage::ResourceId(input.clone()),
+            ExecutableMessage::ResourceId(ResourceId::from_labelled_heap("Out")),
+        ).with_system(Some(SystemId::from("A System"))));
+
+        let mut inputs = ExecutableInputs::default();
+        inputs.lend(&memory, &buffer);
+        assert_eq!(inputs.get::<i32>(&input), Some(&1));
+        assert_eq!(inputs.get::<u32>(&input), None);
+        assert!(memory.resolve::<Shared<i32>>(None, Some(&input), None, None).unwrap().is_err());
+
+        assert!(buffer.remove(RunId::new(0), "A").is_some());
+        inputs.lend(&memory, &buffer);
+        assert!(inputs.is_empty());
+        assert_eq!(**memory.resolve::<Shared<i32>>(None, Some(&input), None, None).unwrap().unwrap(), 1);
+    }
+}
@@ -1,22 +1,117 @@
-use std::{pin::Pin, sync::Arc};
+use std::{collections::HashMap, pin::Pin, sync::Arc};
 
 use tracing::{Level, event};
 
-use crate::prelude::{EventId, Executable, ExecutableBuffer, ExecutableMessage, ExecutableQueue, ExecutableRegistry, KernelSystem, Memory, NextEvents, ProgramId, ProgramKey, QueuedExecutable, ResourceId, Shared, StateMachine, SystemId, Unique, World};
+use crate::prelude::{CurrentEvents, ErrorPolicy, EventId, Executable, ExecutableBuffer, ExecutableInputs, ExecutableMessage, ExecutableOutputs, ExecutableQueue, ExecutableRegistry, ExecutableRegistryBuilder, ExecutableStatus, FailedExecutable, FailedExecutables, KernelSystem, Memory, NextEvents, Pipeline, PipelineError, ProgramId, ProgramKey, ResourceId, RunId, Shared, StateMachine, SystemId, TickAccumulator, Unique, World};
 
 pub struct ExecutableManager;
 
 impl ExecutableManager {
+    /// Completes the tick after it is triggered, see `register_executable` to wait for the system
     pub fn insert_executable(state_machine: &StateMachine, executable_name: &str, trigger_event: EventId) -> String {
         let mut executable_registry = state_machine.resolve::<Unique<ExecutableRegistry>>(None, None, None, None).unwrap().unwrap();
         let executable_label = format!("{executable_name}-Executable");
-        executable_registry.insert(executable_label.clone(), Executable::new(executable_label.clone(), trigger_event));
+        executable_registry.insert(executable_label.clone(), Executable::new(executable_label.clone(), trigger_event).immediate());
         executable_label
     }
 
-    pub fn queue_executable(state_machine: &StateMachine, executable_label: String, executable_message: ExecutableMessage) {
+    /// Registered under `executable.label`
+    pub fn register_executable(state_machine: &StateMachine, executable: Executable) -> Option<Executable> {
+        let mut executable_registry = state_machine.resolve::<Unique<ExecutableRegistry>>(None, None, None, None).unwrap().unwrap();
+        executable_registry.insert(executable.label.clone(), executable)
+    }
+
+    /// e.g. `"Foo-Executable>(Bar-Executable|Baz-Executable)"`, see [`PipelineParser`](crate::prelude::PipelineParser)
+    pub fn queue_executable(state_machine: &StateMachine, executable_label: String, executable_message: ExecutableMessage) -> Result<RunId, PipelineError> {
+        Self::queue(state_machine, executable_message, |executable_registry| {
+            let pipeline = executable_registry.parse_pipeline(&executable_label)?;
+            Ok((executable_label, pipeline))
+        })
+    }
+
+    /// Queues a pipeline composed in code, labelled by its rendered label so policies keyed by that label apply
+    pub fn queue_pipeline(state_machine: &StateMachine, pipeline: Pipeline, executable_message: ExecutableMessage) -> Result<RunId, PipelineError> {
+        Self::queue(state_machine, executable_message, |executable_registry| {
+            executable_registry.validate(&pipeline)?;
+            Ok((executable_registry.render(&pipeline), pipeline))
+        })
+    }
+
+    fn queue(
+        state_machine: &StateMachine,
+        executable_message: ExecutableMessage,
+        pipeline: impl FnOnce(&ExecutableRegistry) -> Result<(String, Pipeline), PipelineError>
+    ) -> Result<RunId, PipelineError> {
+        let (executable_label, pipeline, policy) = {
+            let executable_registry = state_machine.resolve::<Shared<ExecutableRegistry>>(None, None, None, None).unwrap().unwrap();
+            let (executable_label, pipeline) = pipeline(&executable_registry)?;
+            let policy = executable_registry.policy(&executable_label);
+
+            if let ErrorPolicy::DeadLetter(dead_letter) = &policy {
+                if executable_registry.get(dead_letter).is_none() {
+                    return Err(PipelineError::NotFound(dead_letter.clone()));
+                }
+            }
+
+            (executable_label, pipeline, policy)
+        };
+
+        let mut executable_queue = state_machine.resolve::<Unique<ExecutableQueue>>(None, None, None, None).unwrap().unwrap();
+        Ok(executable_queue.queue(executable_label, pipeline, executable_message, policy))
+    }
+
+    /// The label `queue_executable` would parse into the pipeline
+    pub fn render_pipeline(state_machine: &StateMachine, pipeline: &Pipeline) -> String {
+        let executable_registry = state_machine.resolve::<Shared<ExecutableRegistry>>(None, None, None, None).unwrap().unwrap();
+        executable_registry.render(pipeline)
+    }
+
+    /// Labels queued afterwards are parsed with the new delimiter, escape & skip token
+    pub fn configure_registry(state_machine: &StateMachine, builder: ExecutableRegistryBuilder) {
+        let mut executable_registry = state_machine.resolve::<Unique<ExecutableRegistry>>(None, None, None, None).unwrap().unwrap();
+        executable_registry.configure(builder);
+    }
+
+    /// Used by every run of the pipeline queued afterwards
+    pub fn set_error_policy(state_machine: &StateMachine, executable_label: String, policy: ErrorPolicy) -> Option<ErrorPolicy> {
+        let mut executable_registry = state_machine.resolve::<Unique<ExecutableRegistry>>(None, None, None, None).unwrap().unwrap();
+        executable_registry.insert_policy(executable_label, policy)
+    }
+
+    /// Oldest first
+    pub fn take_failed(state_machine: &StateMachine) -> Vec<FailedExecutable> {
+        let mut failed_executables = state_machine.resolve::<Unique<FailedExecutables>>(None, None, None, None).unwrap().unwrap();
+        failed_executables.drain().collect()
+    }
+
+    /// Finished runs are kept until taken or their retention ends
+    pub fn status(state_machine: &StateMachine, run_id: RunId) -> Option<ExecutableStatus> {
+        let executable_queue = state_machine.resolve::<Shared<ExecutableQueue>>(None, None, None, None)?.ok()?;
+        executable_queue.status(run_id).cloned()
+    }
+
+    /// The runs still queued or running
+    pub fn running(state_machine: &StateMachine) -> Vec<RunId> {
+        let executable_queue = state_machine.resolve::<Shared<ExecutableQueue>>(None, None, None, None).unwrap().unwrap();
+        executable_queue.running().map(|queued_executable| queued_executable.run_id).collect()
+    }
+
+    /// The message output by the final stage of a finished run, e.g. the resource it wrote
+    pub fn output(state_machine: &StateMachine, run_id: RunId) -> Option<ExecutableMessage> {
+        let executable_queue = state_machine.resolve::<Shared<ExecutableQueue>>(None, None, None, None)?.ok()?;
+        executable_queue.output(run_id).cloned()
+    }
+
+    /// See `ExecutableQueue::set_retention`
+    pub fn set_retention(state_machine: &StateMachine, ticks: u64) {
         let mut executable_queue = state_machine.resolve::<Unique<ExecutableQueue>>(None, None, None, None).unwrap().unwrap();
-        executable_queue.queue(QueuedExecutable::new(executable_label, executable_message));
+        executable_queue.set_retention(ticks);
+    }
+
+    /// Statuses of the runs finished since last taken
+    pub fn take_finished(state_machine: &StateMachine) -> HashMap<RunId, ExecutableStatus> {
+        let mut executable_queue = state_machine.resolve::<Unique<ExecutableQueue>>(None, None, None, None).unwrap().unwrap();
+        executable_queue.take_finished()
     }
 }
 
@@ -32,14 +127,28 @@ impl KernelSystem for ExecutableManager {
         event!(Level::DEBUG, "Inserting ExecutableBuffer");
         assert!(memory.insert(None, None, None, ExecutableBuffer::default()).unwrap().is_ok());
         
+        event!(Level::DEBUG, "Inserting ExecutableInputs");
+        assert!(memory.insert(None, None, None, ExecutableInputs::default()).unwrap().is_ok());
+
+        event!(Level::DEBUG, "Inserting ExecutableOutputs");
+        assert!(memory.insert(None, None, None, ExecutableOutputs::default()).unwrap().is_ok());
+
         event!(Level::DEBUG, "Inserting ExecutableRegistry");
         assert!(memory.insert(None, None, None, ExecutableRegistry::default()).unwrap().is_ok());
 
+        event!(Level::DEBUG, "Inserting FailedExecutables");
+        assert!(memory.insert(None, None, None, FailedExecutables::default()).unwrap().is_ok());
+
         event!(Level::DEBUG, "Checking NextEvents");
         if !matches!(memory.contains_resource(None, &ResourceId::from_raw_heap::<NextEvents>(), None), Some(true)) {
             event!(Level::WARN, "NextEvents Not Found");   
         }
 
+        event!(Level::DEBUG, "Checking CurrentEvents");
+        if !matches!(memory.contains_resource(None, &ResourceId::from_raw_heap::<CurrentEvents>(), None), Some(true)) {
+            event!(Level::WARN, "CurrentEvents Not Found");   
+        }
+
         event!(Level::DEBUG, "Checking World");
         if !matches!(memory.contains_resource(None, &ResourceId::from_raw_heap::<World>(), None), Some(true)) {
             // Only warn because path may never trigger a panic
@@ -52,14 +161,19 @@ impl KernelSystem for ExecutableManager {
         let memory = Arc::clone(&memory);
 
         Box::pin(async move {
+            // Before their stages complete, so the next stages read them
+            memory.resolve::<Unique<ExecutableOutputs>>(None, None, None, None).unwrap().unwrap().flush(&memory);
+
             let mut executable_queue = memory.resolve::<Unique<ExecutableQueue>>(None, None, None, None).unwrap().unwrap();
             let mut next_events = memory.resolve::<Unique<NextEvents>>(None, None, None, None).unwrap().unwrap();
             let executable_registry = memory.resolve::<Shared<ExecutableRegistry>>(None, None, None, None).unwrap().unwrap();
+            let current_events = memory.resolve::<Shared<CurrentEvents>>(None, None, None, None).unwrap().unwrap();
+            let current_tick = memory.resolve::<Shared<TickAccumulator>>(None, None, None, None).unwrap().unwrap().load();
 
             event!(Level::DEBUG, old_executable_queue_count = executable_queue.len());
             event!(Level::DEBUG, old_next_event_count = next_events.len());
 
-            executable_queue.tick(&memory, &executable_registry, &mut next_events);
+            executable_queue.tick(&memory, &executable_registry, &current_events, &mut next_events, current_tick);
             event!(Level::DEBUG, new_executable_queue_count = executable_queue.len());
             event!(Level::TRACE, executables_queued = ?executable_queue);
 
@@ -3,5 +3,27 @@ use crate::prelude::{EntityId, ResourceId};
 #[derive(Debug, Clone)]
 pub enum ExecutableMessage {
     ResourceId(ResourceId),
-    ECS(EntityId)
-}
\ No newline at end of file
+    ECS(EntityId),
+    /// The messages of each branch of a fan-out, in the order the branches are written
+    Joined(Vec<ExecutableMessage>),
+}
+
+impl ExecutableMessage {
+    /// Every resource the message carries, including joined ones
+    pub fn resource_ids(&self) -> Vec<&ResourceId> {
+        match self {
+            ExecutableMessage::ResourceId(resource_id) => vec![resource_id],
+            ExecutableMessage::ECS(_) => Vec::new(),
+            ExecutableMessage::Joined(messages) => messages.iter().flat_map(ExecutableMessage::resource_ids).collect(),
+        }
+    }
+
+    /// Every entity the message carries, including joined ones
+    pub fn entity_ids(&self) -> Vec<&EntityId> {
+        match self {
+            ExecutableMessage::ResourceId(_) => Vec::new(),
+            ExecutableMessage::ECS(entity_id) => vec![entity_id],
+            ExecutableMessage::Joined(messages) => messages.iter().flat_map(ExecutableMessage::entity_ids).collect(),
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use std::{any::Any, collections::HashMap};
+
+use tracing::{Level, event};
+
+use crate::prelude::{HeapObject, Memory, RawHeapObject, Resource, ResourceId};
+
+/// The resources written by the stages addressed to systems, see `ExecutableOutput`.
+/// Declared through `Unique<ExecutableOutputs>`, they are inserted into memory by the `ExecutableManager` before it completes their stages
+#[derive(Default)]
+pub struct ExecutableOutputs(HashMap<ResourceId, Box<dyn Any>>);
+
+impl ExecutableOutputs {
+    /// Replaces the pending write of `resource_id`
+    pub fn write<T: 'static>(&mut self, resource_id: ResourceId, value: T) {
+        self.0.insert(resource_id, Box::new(value));
+    }
+
+    pub fn contains(&self, resource_id: &ResourceId) -> bool {
+        self.0.contains_key(resource_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Inserts the pending writes into memory
+    pub fn flush(&mut self, memory: &Memory) {
+        for (resource_id, value) in self.0.drain() {
+            if !matches!(memory.insert_resource(None, resource_id.clone(), None, Resource::Heap(HeapObject(RawHeapObject::new(value)))), Some(Ok(_))) {
+                event!(Level::WARN, resource_id=?resource_id, "Executable Output Dropped");
+            }
+        }
+    }
+}
@@ -1,115 +1,638 @@
-// "Foo|FooBarAdapter|Bar|BarBazAdapter|Baz", FooInput
-// "FooBarAdapter|Bar|BarBazAdapter|Baz", FooOutput
-// "Bar|BarBazAdapter|Baz", BarInput 
-// "BarBazAdapter|Baz", BarOutput 
-// "Baz", BazInput
+// "Foo>FooBarAdapter>(Bar|Baz)>Qux", FooInput
+// Foo: FooInput -> FooOutput
+// FooBarAdapter: FooOutput -> BarInput
+// Bar: BarInput -> BarOutput, Baz: BarInput -> BazOutput
+// Qux: Joined([BarOutput, BazOutput]) -> QuxOutput
 // Complete
+// Each stage waits for its completion event (see `Executable`) before the next starts
+// Each stage writes a new resource (`{run}-{stage}-{label}`) read as the input of the next,
+// the intermediate ones are removed once the run finishes
+// A resource read by a stage addressed to a system is lent to `ExecutableInputs` meanwhile,
+// the resources its system writes are inserted from `ExecutableOutputs` next tick
+// An entity flows through every stage carrying its components, it is despawned once the finished run's retention ends
+// A failing stage is handled by the pipeline's `ErrorPolicy`, failed runs are recorded in `FailedExecutables`
 
-use std::ops::Range;
+use std::{collections::{HashMap, HashSet, VecDeque}, ops::Range};
 
 use tracing::{Level, event};
 
-use crate::{memory::Memory, prelude::{BufferedExecutable, EntityId, ExecutableBuffer, ExecutableLabel, ExecutableMessage, ExecutableRegistry, NextEvents, ParseResult, Unique, World}};
+use crate::{memory::Memory, prelude::{BufferedExecutable, CurrentEvents, EntityId, ErrorPolicy, EventId, Executable, ExecutableBuffer, ExecutableFailure, ExecutableInputs, ExecutableLabel, ExecutableMessage, ExecutableRegistry, ExecutableStatus, FailedExecutable, FailedExecutables, NextEvents, Pipeline, ResourceId, RunId, Shared, SystemId, Unique, World}};
 
+#[derive(Debug)]
+enum Step {
+    Run(Pipeline),
+    /// (join, branch)
+    Join(usize, usize),
+}
+
+/// A triggered stage
+#[derive(Debug)]
+struct WaitingStage {
+    label: String,
+    /// None completes next tick
+    completion: Option<EventId>,
+    started_tick: u64,
+    timeout: Option<u64>,
+    /// Fails the stage on `EventId::errored` if the run is claimed for it, see `ExecutableBuffer::claim`
+    system: Option<SystemId>,
+    /// Rerun or passed on when the stage fails
+    source: ExecutableMessage,
+}
+
+/// A branch of a run, `steps` is a stack
+#[derive(Debug)]
+struct Flow {
+    steps: Vec<Step>,
+    message: ExecutableMessage,
+    waiting: Option<WaitingStage>,
+    /// Retries of the current stage
+    attempt: u32,
+    /// Waits until this tick before continuing, see `ErrorPolicy::Retry`
+    resume_tick: u64,
+}
+
+impl Flow {
+    fn new(steps: Vec<Step>, message: ExecutableMessage) -> Self {
+        Self { steps, message, waiting: None, attempt: 0, resume_tick: 0 }
+    }
+}
+
+/// What a flow does after one of its stages failed
+enum Recovery {
+    Continue,
+    /// Until the retry
+    Wait,
+    Abort,
+}
+
+/// Waits for every branch of a fan-out before continuing with `steps`
+#[derive(Debug)]
+struct Join {
+    remaining: usize,
+    messages: Vec<Option<ExecutableMessage>>,
+    steps: Vec<Step>,
+}
+
+/// A run of a pipeline, each branch advances once its stage completes
 #[derive(Debug)]
 pub struct QueuedExecutable {
+    pub run_id: RunId,
     pub label: String,
-    pub message: ExecutableMessage
+    status: ExecutableStatus,
+    flows: Vec<Flow>,
+    joins: Vec<Option<Join>>,
+    /// The resources written by stages of this run
+    outputs: Vec<ResourceId>,
+    /// The message of the last flow to complete
+    output: Option<ExecutableMessage>,
+    /// The entities flowing through the run
+    entities: Vec<EntityId>,
+    policy: ErrorPolicy,
+    /// The failure that started the dead-letter stage
+    dead_letter: Option<(ExecutableFailure, ExecutableMessage)>,
 }
 
 impl QueuedExecutable {
-    pub fn new(label: String, message: ExecutableMessage) -> Self {
-        Self { label, message }
+    pub fn new(run_id: RunId, label: String, pipeline: Pipeline, message: ExecutableMessage) -> Self {
+        Self { 
+            run_id,
+            label, 
+            status: ExecutableStatus::Queued,
+            entities: message.entity_ids().into_iter().cloned().collect(),
+            flows: vec![Flow::new(vec![Step::Run(pipeline)], message)], 
+            joins: Vec::new(),
+            outputs: Vec::new(),
+            output: None,
+            policy: ErrorPolicy::default(),
+            dead_letter: None,
+        }
+    }
+
+    pub fn with_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn policy(&self) -> &ErrorPolicy {
+        &self.policy
+    }
+
+    pub fn status(&self) -> &ExecutableStatus {
+        &self.status
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.status.is_finished()
+    }
+
+    /// The stages waiting to complete
+    pub fn running_stages(&self) -> impl Iterator<Item = &str> {
+        self.flows.iter().filter_map(|flow| flow.waiting.as_ref().map(|waiting| waiting.label.as_str()))
+    }
+
+    /// The message output by the final stage, once done
+    pub fn output(&self) -> Option<&ExecutableMessage> {
+        self.output.as_ref()
+    }
+
+    pub fn entities(&self) -> &[EntityId] {
+        &self.entities
+    }
+
+    /// The resources to remove once finished, every output not part of the final one of a done run
+    pub fn intermediates(&self) -> Vec<ResourceId> {
+        let kept = match (&self.status, &self.output) {
+            (ExecutableStatus::Done, Some(output)) => output.resource_ids(),
+            _ => Vec::new(),
+        };
+        self.outputs.iter().filter(|output| !kept.contains(output)).cloned().collect()
+    }
+
+    /// Branches still running
+    pub fn flow_count(&self) -> usize {
+        self.flows.len()
+    }
+
+    /// `emitted`: The completion & failure events emitted since the last tick, see `ExecutableQueue::tick`
+    fn stage_failure(&self, memory: &Memory, waiting: &WaitingStage, completed: bool, emitted: &HashSet<EventId>, current_tick: u64) -> Option<ExecutableFailure> {
+        if emitted.contains(&EventId::failed(&waiting.label, self.run_id)) {
+            return Some(ExecutableFailure::Failed(waiting.label.clone()));
+        }
+
+        // Only if the system errored while working on this run
+        let errored = waiting.system.as_ref().is_some_and(|system| {
+            emitted.contains(&EventId::errored(system))
+                && memory.resolve::<Shared<ExecutableBuffer>>(None, None, None, None).unwrap().unwrap().claimed(system, self.run_id)
+        });
+        if errored {
+            return Some(ExecutableFailure::Errored(waiting.label.clone()));
+        }
+
+        if !completed && waiting.timeout.is_some_and(|timeout| current_tick >= waiting.started_tick + timeout) {
+            return Some(ExecutableFailure::TimedOut(waiting.label.clone()));
+        }
+
+        None
+    }
+
+    /// Applies the error policy to the flow whose stage `label` failed on `source`
+    fn recover(&mut self, flow: &mut Flow, pending: &mut Vec<Flow>, memory: &Memory, label: String, source: &ExecutableMessage, failure: &ExecutableFailure, current_tick: u64) -> Recovery {
+        // The dead-letter stage failed
+        if self.dead_letter.is_some() {
+            return Recovery::Abort;
+        }
+
+        match self.policy.clone() {
+            ErrorPolicy::Abort => Recovery::Abort,
+            ErrorPolicy::Skip => {
+                event!(Level::TRACE, run_id=%self.run_id, stage=label, failure=?failure, "Stage Skipped");
+                flow.message = source.clone();
+                flow.attempt = 0;
+                Recovery::Continue
+            },
+            ErrorPolicy::Retry { attempts, backoff } if flow.attempt < attempts => {
+                flow.attempt += 1;
+                event!(Level::TRACE, run_id=%self.run_id, stage=label, attempt=flow.attempt, failure=?failure, "Stage Retrying");
+
+                flow.resume_tick = current_tick + backoff;
+                flow.message = source.clone();
+                flow.steps.push(Step::Run(Pipeline::Stage(label)));
+                Recovery::Wait
+            },
+            ErrorPolicy::Retry { .. } => Recovery::Abort,
+            ErrorPolicy::DeadLetter(dead_letter) => {
+                event!(Level::TRACE, run_id=%self.run_id, stage=label, dead_letter=dead_letter, failure=?failure, "Stage Dead Lettered");
+
+                // Drops every other branch
+                pending.clear();
+                self.flows.clear();
+                self.joins.clear();
+                memory.resolve::<Unique<ExecutableBuffer>>(None, None, None, None).unwrap().unwrap().remove_run(self.run_id);
+
+                flow.steps = vec![Step::Run(Pipeline::Stage(dead_letter))];
+                flow.message = source.clone();
+                flow.attempt = 0;
+                self.dead_letter = Some((failure.clone(), source.clone()));
+                Recovery::Continue
+            },
+        }
+    }
+
+    fn fail(&mut self, memory: &Memory, next_events: &mut NextEvents, failure: ExecutableFailure, message: ExecutableMessage, current_tick: u64) {
+        event!(Level::WARN, run_id=%self.run_id, label=self.label, failure=?failure, "Executable Failed");
+
+        self.status = ExecutableStatus::Failed(failure.clone());
+        self.flows.clear();
+        self.joins.clear();
+
+        memory.resolve::<Unique<ExecutableBuffer>>(None, None, None, None).unwrap().unwrap().remove_run(self.run_id);
+
+        match memory.resolve::<Unique<FailedExecutables>>(None, None, None, None) {
+            Some(Ok(mut failed_executables)) => failed_executables.push(FailedExecutable {
+                run_id: self.run_id,
+                label: self.label.clone(),
+                failure,
+                message,
+                tick: current_tick,
+            }),
+            _ => event!(Level::WARN, "FailedExecutables Not Found"),
+        }
+
+        next_events.insert(EventId::executable_failed());
+    }
+
+    /// `emitted`: The events emitted since the last tick, each emission is only seen once
+    pub fn tick(
+        &mut self,
+        memory: &Memory,
+        executable_registry: &ExecutableRegistry,
+        current_events: &CurrentEvents,
+        emitted: &HashSet<EventId>,
+        next_events: &mut NextEvents,
+        current_tick: u64,
+    ) {
+        if self.is_finished() {
+            return;
+        }
+
+        let mut pending = std::mem::take(&mut self.flows);
+        pending.reverse();
+
+        'flows: while let Some(mut flow) = pending.pop() {
+            if current_tick < flow.resume_tick {
+                self.flows.push(flow);
+                continue 'flows;
+            }
+
+            if let Some(waiting) = flow.waiting.take() {
+                let completed = waiting.completion.as_ref().is_none_or(|completion| emitted.contains(completion));
+                let failure = self.stage_failure(memory, &waiting, completed, emitted, current_tick);
+
+                if failure.is_none() && !completed {
+                    flow.waiting = Some(waiting);
+                    self.flows.push(flow);
+                    continue 'flows;
+                }
+
+                memory.resolve::<Unique<ExecutableBuffer>>(None, None, None, None).unwrap().unwrap().remove(self.run_id, &waiting.label);
+
+                match failure {
+                    Some(failure) => match self.recover(&mut flow, &mut pending, memory, waiting.label, &waiting.source, &failure, current_tick) {
+                        Recovery::Continue => {},
+                        Recovery::Wait => {
+                            self.flows.push(flow);
+                            continue 'flows;
+                        },
+                        Recovery::Abort => return self.fail(memory, next_events, failure, waiting.source, current_tick),
+                    },
+                    None => {
+                        event!(Level::TRACE, run_id=%self.run_id, stage=waiting.label, "Stage Complete");
+                        flow.attempt = 0;
+                    },
+                }
+            }
+
+            while let Some(step) = flow.steps.pop() {
+                match step {
+                    Step::Run(Pipeline::Stage(label)) => {
+                        let Some(executable) = executable_registry.get(&label) else {
+                            event!(Level::WARN, key=label, "Executable Not Found");
+                            let failure = ExecutableFailure::NotFound(label.clone());
+                            let source = flow.message.clone();
+
+                            match self.recover(&mut flow, &mut pending, memory, label, &source, &failure, current_tick) {
+                                Recovery::Continue => continue,
+                                Recovery::Wait => {
+                                    self.flows.push(flow);
+                                    continue 'flows;
+                                },
+                                Recovery::Abort => return self.fail(memory, next_events, failure, source, current_tick),
+                            }
+                        };
+
+                        let stage = match self.status {
+                            ExecutableStatus::Running(stage) => stage,
+                            _ => 0,
+                        };
+
+                        let source = flow.message.clone();
+                        flow.message = Self::run_stage(memory, self.run_id, stage, executable, flow.message, next_events, current_tick);
+                        if let ExecutableMessage::ResourceId(output) = &flow.message {
+                            self.outputs.push(output.clone());
+                        }
+
+                        flow.waiting = Some(WaitingStage { 
+                            label, 
+                            completion: executable.completion_event(self.run_id),
+                            started_tick: current_tick, 
+                            timeout: executable.timeout,
+                            system: executable.system.clone(),
+                            source,
+                        });
+
+                        self.status = match self.status {
+                            ExecutableStatus::Running(stage) => ExecutableStatus::Running(stage + 1),
+                            _ => ExecutableStatus::Running(1),
+                        };
+
+                        self.flows.push(flow);
+                        continue 'flows;
+                    },
+                    Step::Run(Pipeline::Skip) => {
+                        self.flows.push(flow);
+                        continue 'flows;
+                    },
+                    Step::Run(Pipeline::Sequence(pipelines)) => flow.steps.extend(pipelines.into_iter().rev().map(Step::Run)),
+                    Step::Run(Pipeline::FanOut(pipelines)) => {
+                        let join = self.joins.len();
+                        self.joins.push(Some(Join {
+                            remaining: pipelines.len(),
+                            messages: vec![None; pipelines.len()],
+                            steps: flow.steps,
+                        }));
+
+                        event!(Level::TRACE, label=self.label, branches=pipelines.len(), "Fan Out");
+                        pending.extend(pipelines.into_iter().enumerate().rev().map(|(branch, pipeline)| Flow::new(
+                            vec![Step::Join(join, branch), Step::Run(pipeline)],
+                            flow.message.clone(),
+                        )));
+
+                        continue 'flows;
+                    },
+                    Step::Run(Pipeline::Branch(branches)) => {
+                        let taken = branches.into_iter().find(|(guard, _)| guard.holds(&flow.message, |event_id| current_events.contains(event_id)));
+                        match taken {
+                            Some((guard, pipeline)) => {
+                                event!(Level::TRACE, label=self.label, guard=?guard, "Branch Taken");
+                                flow.steps.push(Step::Run(pipeline));
+                            },
+                            None => event!(Level::TRACE, label=self.label, "No Branch Taken"),
+                        }
+                    },
+                    Step::Join(join, branch) => {
+                        let waiting = self.joins[join].as_mut().expect("Join only finishes once");
+                        waiting.messages[branch] = Some(flow.message);
+                        waiting.remaining -= 1;
+
+                        if waiting.remaining == 0 {
+                            let finished = self.joins[join].take().unwrap();
+                            event!(Level::TRACE, label=self.label, "Joined");
+                            pending.push(Flow::new(
+                                finished.steps,
+                                ExecutableMessage::Joined(finished.messages.into_iter().map(Option::unwrap).collect()),
+                            ));
+                        }
+
+                        continue 'flows;
+                    },
+                }
+            }
+
+            event!(Level::TRACE, label=self.label, "Flow Complete");
+            self.output = Some(flow.message);
+        }
+
+        if self.flows.is_empty() {
+            match self.dead_letter.take() {
+                Some((failure, message)) => self.fail(memory, next_events, failure, message, current_tick),
+                None => self.status = ExecutableStatus::Done,
+            }
+        }
+    }
+
+    fn run_stage(memory: &Memory, run_id: RunId, stage: usize, executable: &Executable, source: ExecutableMessage, next_events: &mut NextEvents, current_tick: u64) -> ExecutableMessage {
+        let event = executable.trigger.clone();
+        event!(Level::TRACE, event=?event, "New Event");
+        
+        // NextEvent ? (if put executable before EventManager)
+        next_events.insert(event);
+
+        let label = ExecutableLabel::new(executable.label.clone());
+        let target = Self::target_message(&source, format!("{run_id}-{stage}-{}", executable.label));
+
+        let mut buffer = memory.resolve::<Unique<ExecutableBuffer>>(None, None, None, None).unwrap().unwrap();
+        let buffered_executable = BufferedExecutable::new(run_id, label, source, target.clone()).with_system(executable.system.clone()).with_tick(current_tick);
+
+        event!(Level::TRACE, buffered_executable=?buffered_executable, "New Buffered Executable");
+
+        buffer.push(buffered_executable);
+
+        target
+    }
+
+    /// `output` labels the resource written by the stage
+    fn target_message(source: &ExecutableMessage, output: String) -> ExecutableMessage {
+        match source {
+            ExecutableMessage::ResourceId(_) => ExecutableMessage::ResourceId(ResourceId::from_labelled_heap(output)),
+            // Stages add to & modify the components of the same entity
+            ExecutableMessage::ECS(entity_id) => ExecutableMessage::ECS(entity_id.clone()),
+            // The joining stage outputs a single message
+            ExecutableMessage::Joined(messages) => match messages.first() {
+                Some(message) => Self::target_message(message, output),
+                None => source.clone(),
+            },
+        }
     }
 }
 
-#[derive(Default, Debug)]
-pub struct ExecutableQueue(Vec<QueuedExecutable>);
+#[derive(Debug)]
+pub struct ExecutableQueue {
+    next_run_id: u64,
+    queue: Vec<QueuedExecutable>,
+    /// Statuses of finished runs not taken yet
+    finished: HashMap<RunId, ExecutableStatus>,
+    outputs: HashMap<RunId, ExecutableMessage>,
+    /// Intermediate resources still accessed when their run finished
+    cleanup: Vec<ResourceId>,
+    /// Of finished runs
+    entities: HashMap<RunId, Vec<EntityId>>,
+    /// (tick, run) Finished runs in the order their retention ends
+    expiring: VecDeque<(u64, RunId)>,
+    retention: u64,
+    /// Entities of expired runs, retried while `World` is accessed
+    despawn: Vec<EntityId>,
+    /// `NextEvents` last tick, already seen when they become current
+    last_next_events: HashSet<EventId>,
+}
+
+impl Default for ExecutableQueue {
+    fn default() -> Self {
+        Self {
+            next_run_id: 0,
+            queue: Vec::new(),
+            finished: HashMap::new(),
+            outputs: HashMap::new(),
+            cleanup: Vec::new(),
+            entities: HashMap::new(),
+            expiring: VecDeque::new(),
+            retention: Self::DEFAULT_RETENTION,
+            despawn: Vec::new(),
+            last_next_events: HashSet::new(),
+        }
+    }
+}
 
 impl ExecutableQueue {
-    pub fn queue(&mut self, executable: QueuedExecutable) {
-        self.0.push(executable);
+    /// Ticks a finished run is kept for, see `set_retention`
+    pub const DEFAULT_RETENTION: u64 = 16;
+
+    pub fn queue(&mut self, label: String, pipeline: Pipeline, message: ExecutableMessage, policy: ErrorPolicy) -> RunId {
+        let run_id = RunId::new(self.next_run_id);
+        self.next_run_id += 1;
+
+        self.queue.push(QueuedExecutable::new(run_id, label, pipeline, message).with_policy(policy));
+        run_id
     }
 
     pub fn drain(&mut self) -> impl Iterator<Item = QueuedExecutable> {
-        self.0.drain(..)
+        self.queue.drain(..)
     }
 
     pub fn extend<T>(&mut self, iter: T) 
         where T: IntoIterator<Item = QueuedExecutable> 
     {
-        self.0.extend(iter);
+        self.queue.extend(iter);
+    }
+
+    pub fn get(&self, run_id: RunId) -> Option<&QueuedExecutable> {
+        self.queue.iter().find(|queued_executable| queued_executable.run_id == run_id)
+    }
+
+    /// Finished runs are kept until taken or their retention ends
+    pub fn status(&self, run_id: RunId) -> Option<&ExecutableStatus> {
+        self.get(run_id).map(QueuedExecutable::status).or_else(|| self.finished.get(&run_id))
+    }
+
+    pub fn running(&self) -> impl Iterator<Item = &QueuedExecutable> {
+        self.queue.iter()
+    }
+
+    pub fn finished(&self) -> impl Iterator<Item = (&RunId, &ExecutableStatus)> {
+        self.finished.iter()
+    }
+
+    /// The message output by the final stage of a finished run, until its retention ends
+    pub fn output(&self, run_id: RunId) -> Option<&ExecutableMessage> {
+        self.outputs.get(&run_id)
+    }
+
+    /// Only the statuses, outputs & entities are kept until the retention ends
+    pub fn take_finished(&mut self) -> HashMap<RunId, ExecutableStatus> {
+        std::mem::take(&mut self.finished)
+    }
+
+    /// Ticks a finished run's status, output & entities are kept for, its entities are despawned afterwards.
+    /// Applies to runs finishing afterwards
+    pub fn set_retention(&mut self, ticks: u64) {
+        self.retention = ticks;
+    }
+
+    pub fn retention(&self) -> u64 {
+        self.retention
     }
 
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
     }
 
     /// no guarantees about the ordering
     pub fn get_range(&self, amount: Range<usize>) -> impl Iterator<Item = &QueuedExecutable> {
-        self.0.iter().take(amount.end)
+        self.queue.iter().take(amount.end)
     }
 
     pub fn tick(
         &mut self,
         memory: &Memory,
         executable_registry: &ExecutableRegistry,
+        current_events: &CurrentEvents,
         next_events: &mut NextEvents,
+        current_tick: u64,
     ) {
-        let mut new_executable_queue = ExecutableQueue::default();
-        for queued_executable in self.drain() {
-            let (executable, remaining) = executable_registry.parse_mapping(&queued_executable.label);
-
-            match &executable {
-                Err(ParseResult::NotFound(key)) => event!(Level::WARN, key=key, "Executable Not Found (Skipping)"),
-                _ => ()
-            };
-
-            let new_source_message = if let Ok(executable) = executable {
-                let event = executable.trigger;
-                event!(Level::TRACE, event=?event, "New Event");
-                
-                // NextEvent ? (if put executable before EventManager)
-                next_events.insert(event);
-
-                let label = ExecutableLabel::new(executable.label);
-
-                let target_message = match &queued_executable.message {
-                    ExecutableMessage::ResourceId(source_id) => {
-                        // TODO use an event to change the resource id
-                        let target_id = source_id.clone();
-                        ExecutableMessage::ResourceId(target_id)
-                    },
-                    ExecutableMessage::ECS(_) => {
-                        let mut world = memory.resolve::<Unique<World>>(None, None, None, None).unwrap().unwrap();
-                        let world = world.get_mut_hecs().expect("hecs::World in World");
+        // Events are seen in `NextEvents`, or in `CurrentEvents` if they were emitted after the last tick,
+        // so a completion doesn't also complete the next stage waiting on it
+        let next: HashSet<EventId> = next_events.read().cloned().collect();
+        let emitted: HashSet<EventId> = current_events.read()
+            .filter(|event_id| !self.last_next_events.contains(event_id))
+            .chain(next.iter())
+            .cloned()
+            .collect();
+        self.last_next_events = next;
 
-                        let target_id = EntityId::new_hecs(world.reserve_entity());
-                        ExecutableMessage::ECS(target_id)
-                    },
-                };  
+        for queued_executable in &mut self.queue {
+            queued_executable.tick(memory, executable_registry, current_events, &emitted, next_events, current_tick);
+        }
+
+        let cleanup = std::mem::take(&mut self.cleanup);
+        for queued_executable in self.queue.extract_if(.., |queued_executable| queued_executable.is_finished()) {
+            event!(Level::TRACE, run_id=%queued_executable.run_id, label=queued_executable.label, status=?queued_executable.status, "Executable Finished");
+            
+            self.cleanup.extend(queued_executable.intermediates());
+            if !queued_executable.entities.is_empty() {
+                self.entities.insert(queued_executable.run_id, queued_executable.entities);
+            }
 
-                let source = queued_executable.message;
-                let target = target_message.clone();
+            if let (ExecutableStatus::Done, Some(output)) = (&queued_executable.status, queued_executable.output) {
+                self.outputs.insert(queued_executable.run_id, output);
+            }
 
-                let mut buffer = memory.resolve::<Unique<ExecutableBuffer>>(None, None, None, None).unwrap().unwrap();
-                let buffered_executable = BufferedExecutable::new(label, source, target);
+            self.finished.insert(queued_executable.run_id, queued_executable.status);
+            self.expiring.push_back((current_tick + self.retention, queued_executable.run_id));
+        }
+        self.cleanup.extend(cleanup);
 
-                event!(Level::TRACE, buffered_executable=?buffered_executable, "New Buffered Executable");
+        let mut buffer = memory.resolve::<Unique<ExecutableBuffer>>(None, None, None, None).unwrap().unwrap();
+        buffer.claim(current_tick);
+        // Returned before the intermediate ones are removed
+        memory.resolve::<Unique<ExecutableInputs>>(None, None, None, None).unwrap().unwrap().lend(memory, &buffer);
+        drop(buffer);
 
-                buffer.push(buffered_executable);
+        self.cleanup.retain(|resource_id| match memory.remove(None, resource_id, None) {
+            Some(Err(_)) => {
+                event!(Level::TRACE, resource_id=?resource_id, "Intermediate Resource Accessed (Retrying)");
+                true
+            },
+            _ => false,
+        });
 
-                target_message
-            } else {
-                queued_executable.message
-            };
-            
-            if let Some(remaining) = remaining {
-                event!(Level::TRACE, remaining=remaining, new_source_message=?new_source_message, "Queuing Executable");
-                new_executable_queue.queue(QueuedExecutable::new(remaining.to_string(), new_source_message));
+        self.expire(current_tick);
+        self.despawn_entities(memory);
+    }
+
+    /// Drops the finished runs whose retention ended, queueing their entities to despawn
+    fn expire(&mut self, current_tick: u64) {
+        while let Some(&(tick, run_id)) = self.expiring.front() {
+            if tick > current_tick {
+                break;
             }
+
+            event!(Level::TRACE, run_id=%run_id, "Finished Executable Expired");
+            self.expiring.pop_front();
+            self.finished.remove(&run_id);
+            self.outputs.remove(&run_id);
+            self.despawn.extend(self.entities.remove(&run_id).unwrap_or_default());
         }
+    }
 
-        self.extend(new_executable_queue.drain());
+    fn despawn_entities(&mut self, memory: &Memory) {
+        if self.despawn.is_empty() {
+            return;
+        }
+
+        let Some(Ok(mut world)) = memory.resolve::<Unique<World>>(None, None, None, None) else {
+            event!(Level::TRACE, count=self.despawn.len(), "World Unavailable (Retrying Despawn)");
+            return;
+        };
+        let world = world.get_mut_hecs().expect("hecs::World in World");
+
+        for entity_id in self.despawn.drain(..) {
+            if let Some(entity) = entity_id.get_hecs() {
+                // Already despawned by a stage
+                let _ = world.despawn(*entity);
+            }
+        }
     }
 }
@@ -1,35 +1,81 @@
 use std::collections::HashMap;
 
-use crate::prelude::Executable;
+use crate::prelude::{ErrorPolicy, Executable, Guard, Pipeline, PipelineError, PipelineParser};
 
-// String is what is first mapped
-pub struct ExecutableRegistry {
+/// The label syntax of an `ExecutableRegistry`, see [`PipelineParser`]
+pub struct ExecutableRegistryBuilder {
     delimiter: char,
-    skip_message: String,
-    registry: HashMap<String, Executable>
+    escape: char,
+    skip: String,
 }
 
-impl Default for ExecutableRegistry {
+impl Default for ExecutableRegistryBuilder {
+    /// `A>_>B` with `\` escapes
     fn default() -> Self {
-        let skip_message =  rand::random::<u64>().to_string();
+        Self {
+            delimiter: '>',
+            escape: '\\',
+            skip: "_".to_string(),
+        }
+    }
+}
 
-        let delimiter = '>';
+impl ExecutableRegistryBuilder {
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
 
-        Self {
-            delimiter,
-            skip_message,
-            registry: HashMap::new()
+    pub fn with_escape(mut self, escape: char) -> Self {
+        self.escape = escape;
+        self
+    }
+
+    /// The stage name that runs nothing for a tick
+    pub fn with_skip(mut self, skip: impl Into<String>) -> Self {
+        self.skip = skip.into();
+        self
+    }
+
+    pub fn build(self) -> ExecutableRegistry {
+        ExecutableRegistry {
+            delimiter: self.delimiter,
+            escape: self.escape,
+            skip_message: self.skip,
+            registry: HashMap::new(),
+            policies: HashMap::new(),
         }
     }
 }
 
-// #[derive(PartialEq)]
-pub enum ParseResult {
-    Skip,
-    NotFound(String)
+// String is what is first mapped
+pub struct ExecutableRegistry {
+    delimiter: char,
+    escape: char,
+    skip_message: String,
+    registry: HashMap<String, Executable>,
+    /// Keyed by pipeline label
+    policies: HashMap<String, ErrorPolicy>,
+}
+
+impl Default for ExecutableRegistry {
+    fn default() -> Self {
+        ExecutableRegistryBuilder::default().build()
+    }
 }
 
 impl ExecutableRegistry {
+    pub fn builder() -> ExecutableRegistryBuilder {
+        ExecutableRegistryBuilder::default()
+    }
+
+    /// Swaps the label syntax, keeping what is registered
+    pub fn configure(&mut self, builder: ExecutableRegistryBuilder) {
+        self.delimiter = builder.delimiter;
+        self.escape = builder.escape;
+        self.skip_message = builder.skip;
+    }
+
     pub fn get_skip(&self) -> &str {
         &self.skip_message
     }
@@ -38,21 +84,137 @@ impl ExecutableRegistry {
         &self.delimiter
     }
 
-    pub fn parse_mapping<'a>(&self, sequence: &'a str) -> (Result<Executable, ParseResult>, Option<&'a str>) {
-        let (current, then) = if let Some((current, then)) = sequence.split_once(*self.get_delim()) {
-            (current, Some(then))
-        } else {
-            (sequence, None)
-        };
+    pub fn get_escape(&self) -> &char {
+        &self.escape
+    }
+
+    /// Parses & checks every stage is registered, see [`PipelineParser`]
+    pub fn parse_pipeline(&self, label: &str) -> Result<Pipeline, PipelineError> {
+        let pipeline = PipelineParser::new(label)
+            .with_delimiter(*self.get_delim())
+            .with_escape(*self.get_escape())
+            .with_skip(self.get_skip())
+            .parse()?;
+
+        self.validate(&pipeline)?;
+        Ok(pipeline)
+    }
 
-        if current == self.get_skip() {
-            return (Err(ParseResult::Skip), then);
+    /// Every stage is registered and no sequence, fan-out or branch is empty
+    pub fn validate(&self, pipeline: &Pipeline) -> Result<(), PipelineError> {
+        if pipeline.has_empty() {
+            return Err(PipelineError::Empty);
         }
 
-        (self.registry.get(current).cloned().ok_or(ParseResult::NotFound(current.to_string())), then)
+        match pipeline.stages().into_iter().find(|stage| !self.registry.contains_key(*stage)) {
+            Some(stage) => Err(PipelineError::NotFound(stage.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    /// The label `parse_pipeline` parses into the pipeline, escaping names where needed
+    pub fn render(&self, pipeline: &Pipeline) -> String {
+        let join = |pipelines: &[Pipeline], separator: &str| pipelines.iter().map(|pipeline| self.render(pipeline)).collect::<Vec<_>>().join(separator);
+
+        match pipeline {
+            Pipeline::Stage(label) => self.escape_name(label),
+            Pipeline::Skip => self.skip_message.clone(),
+            Pipeline::Sequence(pipelines) => join(pipelines, &self.delimiter.to_string()),
+            Pipeline::FanOut(pipelines) => format!("({})", join(pipelines, "|")),
+            Pipeline::Branch(branches) => {
+                let branches: Vec<String> = branches.iter().map(|(guard, pipeline)| {
+                    let guard = match guard {
+                        Guard::Event(event_id) => self.escape_name(&event_id.get_id().to_string()),
+                        Guard::Resource => "$resource".to_string(),
+                        Guard::Entity => "$entity".to_string(),
+                        Guard::Default => "_".to_string(),
+                    };
+
+                    format!("{guard}: {}", self.render(pipeline))
+                }).collect();
+
+                format!("[{}]", branches.join(" | "))
+            },
+        }
+    }
+
+    fn escape_name(&self, name: &str) -> String {
+        let mut escaped = String::with_capacity(name.len());
+        // Keeps names from being read as the skip token or a guard
+        let keyword = name == self.skip_message || name == "_" || name.starts_with('$');
+
+        for (position, c) in name.char_indices() {
+            let trimmed = c.is_whitespace() && (position == 0 || position + c.len_utf8() == name.len());
+            if (position == 0 && keyword) || trimmed || c == self.delimiter || c == self.escape || matches!(c, '(' | ')' | '[' | ']' | '|' | ':') {
+                escaped.push(self.escape);
+            }
+            escaped.push(c);
+        }
+
+        escaped
+    }
+
+    pub fn get(&self, label: &str) -> Option<&Executable> {
+        self.registry.get(label)
     }
 
     pub fn insert(&mut self, label: String, executable: Executable) -> Option<Executable> {
         self.registry.insert(label, executable)
     }
+
+    /// Defaults to `ErrorPolicy::Abort`
+    pub fn policy(&self, pipeline_label: &str) -> ErrorPolicy {
+        self.policies.get(pipeline_label).cloned().unwrap_or_default()
+    }
+
+    pub fn insert_policy(&mut self, pipeline_label: String, policy: ErrorPolicy) -> Option<ErrorPolicy> {
+        self.policies.insert(pipeline_label, policy)
+    }
+}
+
+#[cfg(test)]
+mod executable_registry_tests {
+    use crate::prelude::{EventId, Executable, ExecutableRegistry, Guard, Pipeline, PipelineError};
+
+    fn registry(executable_registry: ExecutableRegistry, labels: &[&str]) -> ExecutableRegistry {
+        let mut executable_registry = executable_registry;
+        for label in labels {
+            executable_registry.insert(label.to_string(), Executable::new(label.to_string(), EventId::from(*label)));
+        }
+        executable_registry
+    }
+
+    #[test]
+    fn configurable_syntax() {
+        let executable_registry = registry(ExecutableRegistry::builder().with_delimiter(',').with_escape('/').with_skip("skip").build(), &["A>B", "C,D"]);
+
+        assert_eq!(
+            executable_registry.parse_pipeline("A>B, skip, C/,D").unwrap(),
+            Pipeline::Sequence(vec![Pipeline::stage("A>B"), Pipeline::Skip, Pipeline::stage("C,D")])
+        );
+        assert_eq!(executable_registry.parse_pipeline("A>B,E"), Err(PipelineError::NotFound("E".to_string())));
+    }
+
+    #[test]
+    fn render_round_trips() {
+        let executable_registry = registry(ExecutableRegistry::default(), &["A>B", "_", "$C", "D (1)", " E"]);
+
+        let pipeline = Pipeline::stage("A>B")
+            .then(Pipeline::Skip)
+            .then(Pipeline::fan_out([Pipeline::stage("_"), Pipeline::stage("$C").then(Pipeline::stage("D (1)"))]))
+            .then(Pipeline::branch([
+                (Guard::Event(EventId::from("_")), Pipeline::stage(" E")),
+                (Guard::Resource, Pipeline::stage("_")),
+                (Guard::Default, Pipeline::Skip),
+            ]));
+
+        let label = executable_registry.render(&pipeline);
+        assert_eq!(executable_registry.parse_pipeline(&label).unwrap(), pipeline);
+    }
+
+    #[test]
+    fn rejects_empty() {
+        let executable_registry = registry(ExecutableRegistry::default(), &["A"]);
+        assert_eq!(executable_registry.validate(&Pipeline::stage("A").then(Pipeline::fan_out([]))), Err(PipelineError::Empty));
+    }
 }
\ No newline at end of file
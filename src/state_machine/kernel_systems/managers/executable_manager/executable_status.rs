@@ -0,0 +1,26 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExecutableFailure {
+    /// The stage didn't complete in time
+    TimedOut(String),
+    /// `EventId::failed` was emitted for the stage of the run
+    Failed(String),
+    /// The system handling the stage returned `SystemResult::Error` while working on the run
+    Errored(String),
+    /// The stage isn't registered
+    NotFound(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExecutableStatus {
+    Queued,
+    /// Running the Nth stage, branches count as separate stages
+    Running(usize),
+    Done,
+    Failed(ExecutableFailure),
+}
+
+impl ExecutableStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Done | Self::Failed(_))
+    }
+}
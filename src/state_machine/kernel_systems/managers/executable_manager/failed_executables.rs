@@ -0,0 +1,43 @@
+use crate::prelude::{ExecutableFailure, ExecutableMessage, RunId};
+
+#[derive(Debug, Clone)]
+pub struct FailedExecutable {
+    pub run_id: RunId,
+    /// The pipeline label
+    pub label: String,
+    pub failure: ExecutableFailure,
+    /// The input of the failing stage
+    pub message: ExecutableMessage,
+    pub tick: u64,
+}
+
+/// Runs that failed, kept until drained. `EventId::executable_failed` is emitted for each
+#[derive(Debug, Default)]
+pub struct FailedExecutables(Vec<FailedExecutable>);
+
+impl FailedExecutables {
+    pub fn push(&mut self, failed_executable: FailedExecutable) {
+        self.0.push(failed_executable);
+    }
+
+    pub fn get(&self, run_id: RunId) -> Option<&FailedExecutable> {
+        self.0.iter().find(|failed_executable| failed_executable.run_id == run_id)
+    }
+
+    /// Oldest first
+    pub fn read(&self) -> impl Iterator<Item = &FailedExecutable> {
+        self.0.iter()
+    }
+
+    pub fn drain(&mut self) -> impl Iterator<Item = FailedExecutable> {
+        self.0.drain(..)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
@@ -1,7 +1,14 @@
 pub mod executable_buffer;
+pub mod executable_inputs;
+pub mod executable_outputs;
 pub mod executable_label;
 pub mod executable_manager;
 pub mod executable_message;
 pub mod executable_queue;
 pub mod executable_registry;
 pub mod executable;
+pub mod executable_status;
+pub mod error_policy;
+pub mod failed_executables;
+pub mod pipeline;
+pub mod pipeline_parser;
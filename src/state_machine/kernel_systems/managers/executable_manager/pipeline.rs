@@ -0,0 +1,117 @@
+use crate::prelude::{EventId, ExecutableMessage, PipelineParseError};
+
+/// Which branch of a `Pipeline::Branch` is taken
+#[derive(Debug, Clone, PartialEq)]
+pub enum Guard {
+    /// `Event:` taken if the event is current
+    Event(EventId),
+    /// `$resource:` taken if the message is a `ExecutableMessage::ResourceId`
+    Resource,
+    /// `$entity:` taken if the message is a `ExecutableMessage::ECS`
+    Entity,
+    /// `_:` always taken
+    Default,
+}
+
+impl Guard {
+    pub fn holds(&self, message: &ExecutableMessage, is_current: impl Fn(&EventId) -> bool) -> bool {
+        match self {
+            Self::Event(event_id) => is_current(event_id),
+            Self::Resource => matches!(message, ExecutableMessage::ResourceId(_)),
+            Self::Entity => matches!(message, ExecutableMessage::ECS(_)),
+            Self::Default => true,
+        }
+    }
+}
+
+/// A parsed executable label, see [`PipelineParser`](crate::prelude::PipelineParser)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pipeline {
+    /// An `Executable` label
+    Stage(String),
+    /// Does nothing for a tick
+    Skip,
+    /// `A>B>C`
+    Sequence(Vec<Pipeline>),
+    /// `(A|B)` runs every branch, what follows waits for all of them
+    FanOut(Vec<Pipeline>),
+    /// `[Event: A | _: B]` runs the first branch whose guard holds, none if none hold
+    Branch(Vec<(Guard, Pipeline)>),
+}
+
+impl Pipeline {
+    pub fn stage(label: impl Into<String>) -> Self {
+        Self::Stage(label.into())
+    }
+
+    pub fn sequence(pipelines: impl IntoIterator<Item = Pipeline>) -> Self {
+        Self::Sequence(pipelines.into_iter().collect())
+    }
+
+    pub fn fan_out(pipelines: impl IntoIterator<Item = Pipeline>) -> Self {
+        Self::FanOut(pipelines.into_iter().collect())
+    }
+
+    pub fn branch(branches: impl IntoIterator<Item = (Guard, Pipeline)>) -> Self {
+        Self::Branch(branches.into_iter().collect())
+    }
+
+    /// Runs `next` after this, appending to a sequence
+    pub fn then(self, next: Pipeline) -> Self {
+        match (self, next) {
+            (Self::Sequence(mut pipelines), Self::Sequence(next)) => {
+                pipelines.extend(next);
+                Self::Sequence(pipelines)
+            },
+            (Self::Sequence(mut pipelines), next) => {
+                pipelines.push(next);
+                Self::Sequence(pipelines)
+            },
+            (pipeline, Self::Sequence(mut next)) => {
+                next.insert(0, pipeline);
+                Self::Sequence(next)
+            },
+            (pipeline, next) => Self::Sequence(vec![pipeline, next]),
+        }
+    }
+
+    /// Whether any sequence, fan-out or branch has nothing in it
+    pub fn has_empty(&self) -> bool {
+        match self {
+            Self::Stage(_) | Self::Skip => false,
+            Self::Sequence(pipelines) | Self::FanOut(pipelines) => pipelines.is_empty() || pipelines.iter().any(Pipeline::has_empty),
+            Self::Branch(branches) => branches.is_empty() || branches.iter().any(|(_, pipeline)| pipeline.has_empty()),
+        }
+    }
+
+    /// Every `Executable` label in the pipeline
+    pub fn stages(&self) -> Vec<&str> {
+        let mut stages = Vec::new();
+        self.collect_stages(&mut stages);
+        stages
+    }
+
+    fn collect_stages<'a>(&'a self, stages: &mut Vec<&'a str>) {
+        match self {
+            Self::Stage(label) => stages.push(label),
+            Self::Skip => (),
+            Self::Sequence(pipelines) | Self::FanOut(pipelines) => pipelines.iter().for_each(|pipeline| pipeline.collect_stages(stages)),
+            Self::Branch(branches) => branches.iter().for_each(|(_, pipeline)| pipeline.collect_stages(stages)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PipelineError {
+    Parse(PipelineParseError),
+    /// No `Executable` registered with the label
+    NotFound(String),
+    /// A sequence, fan-out or branch has nothing in it
+    Empty,
+}
+
+impl From<PipelineParseError> for PipelineError {
+    fn from(value: PipelineParseError) -> Self {
+        Self::Parse(value)
+    }
+}
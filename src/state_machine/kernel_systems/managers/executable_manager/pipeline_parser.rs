@@ -0,0 +1,267 @@
+use std::{iter::Peekable, str::CharIndices};
+
+use crate::prelude::{EventId, Guard, Pipeline};
+
+#[derive(Debug, PartialEq)]
+pub enum PipelineParseError {
+    UnexpectedEnd,
+    /// (position, found)
+    UnexpectedChar(usize, char),
+    /// Position where a stage or guard name was expected
+    ExpectedName(usize),
+    /// Position of the unknown `$` guard
+    UnknownGuard(usize),
+}
+
+/// Parses executable labels:
+/// - `A>B` runs `A` then `B` (the delimiter is configurable)
+/// - `(A|B)` runs `A` & `B`, what follows waits for both
+/// - `[Event: A | $resource: B | $entity: C | _: D]` runs the first branch whose guard holds
+/// - the skip token (e.g. `A>_>B`) runs nothing for a tick
+/// - names are trimmed, the escape character (`\` by default) makes the next character part of the name,
+///   e.g. `A\>B` is the stage `A>B`. An escaped name is never the skip token nor a `$`/`_` guard
+pub struct PipelineParser<'a> {
+    source: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+    delimiter: char,
+    escape: char,
+    skip: Option<&'a str>,
+}
+
+impl<'a> PipelineParser<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            chars: source.char_indices().peekable(),
+            delimiter: '>',
+            escape: '\\',
+            skip: None,
+        }
+    }
+
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    pub fn with_escape(mut self, escape: char) -> Self {
+        self.escape = escape;
+        self
+    }
+
+    pub fn with_skip(mut self, skip: &'a str) -> Self {
+        self.skip = Some(skip);
+        self
+    }
+
+    fn is_name_char(&self, c: char) -> bool {
+        c != self.delimiter && c != self.escape && !matches!(c, '(' | ')' | '[' | ']' | '|' | ':')
+    }
+
+    pub fn parse(mut self) -> Result<Pipeline, PipelineParseError> {
+        let pipeline = self.parse_sequence()?;
+
+        self.skip_whitespace();
+        match self.chars.next() {
+            None => Ok(pipeline),
+            Some((position, c)) => Err(PipelineParseError::UnexpectedChar(position, c)),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+
+    fn peek(&mut self) -> Option<(usize, char)> {
+        self.skip_whitespace();
+        self.chars.peek().copied()
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), PipelineParseError> {
+        match self.peek() {
+            Some((_, c)) if c == expected => {
+                self.chars.next();
+                Ok(())
+            },
+            Some((position, c)) => Err(PipelineParseError::UnexpectedChar(position, c)),
+            None => Err(PipelineParseError::UnexpectedEnd),
+        }
+    }
+
+    fn parse_sequence(&mut self) -> Result<Pipeline, PipelineParseError> {
+        let mut pipelines = vec![self.parse_stage()?];
+
+        while self.peek().is_some_and(|(_, c)| c == self.delimiter) {
+            self.chars.next();
+            pipelines.push(self.parse_stage()?);
+        }
+
+        Ok(if pipelines.len() == 1 { pipelines.pop().unwrap() } else { Pipeline::Sequence(pipelines) })
+    }
+
+    fn parse_stage(&mut self) -> Result<Pipeline, PipelineParseError> {
+        match self.peek() {
+            None => Err(PipelineParseError::UnexpectedEnd),
+            Some((_, '(')) => {
+                self.chars.next();
+                let mut pipelines = vec![self.parse_sequence()?];
+
+                while let Some((_, '|')) = self.peek() {
+                    self.chars.next();
+                    pipelines.push(self.parse_sequence()?);
+                }
+
+                self.expect(')')?;
+                Ok(Pipeline::FanOut(pipelines))
+            },
+            Some((_, '[')) => {
+                self.chars.next();
+                let mut branches = vec![self.parse_branch()?];
+
+                while let Some((_, '|')) = self.peek() {
+                    self.chars.next();
+                    branches.push(self.parse_branch()?);
+                }
+
+                self.expect(']')?;
+                Ok(Pipeline::Branch(branches))
+            },
+            Some(_) => {
+                let (name, escaped) = self.parse_name()?;
+                if !escaped && self.skip == Some(name.as_str()) {
+                    Ok(Pipeline::Skip)
+                } else {
+                    Ok(Pipeline::Stage(name))
+                }
+            }
+        }
+    }
+
+    fn parse_branch(&mut self) -> Result<(Guard, Pipeline), PipelineParseError> {
+        let position = self.peek().map_or(self.source.len(), |(position, _)| position);
+
+        let guard = match self.parse_name()? {
+            (name, true) => Guard::Event(EventId::from(name)),
+            (name, false) => match name.as_str() {
+                "_" => Guard::Default,
+                "$resource" => Guard::Resource,
+                "$entity" => Guard::Entity,
+                name if name.starts_with('$') => return Err(PipelineParseError::UnknownGuard(position)),
+                _ => Guard::Event(EventId::from(name)),
+            },
+        };
+
+        self.expect(':')?;
+        Ok((guard, self.parse_sequence()?))
+    }
+
+    /// (name, whether any character was escaped)
+    fn parse_name(&mut self) -> Result<(String, bool), PipelineParseError> {
+        let start = self.peek().map_or(self.source.len(), |(position, _)| position);
+        let mut name = String::new();
+        // Escaped whitespace isn't trimmed
+        let mut escaped_len = 0;
+
+        while let Some((_, c)) = self.chars.peek().copied() {
+            if c == self.escape {
+                self.chars.next();
+                let (_, escaped) = self.chars.next().ok_or(PipelineParseError::UnexpectedEnd)?;
+                name.push(escaped);
+                escaped_len = name.len();
+                continue;
+            }
+
+            if !self.is_name_char(c) {
+                break;
+            }
+
+            self.chars.next();
+            name.push(c);
+        }
+
+        name.truncate(name.trim_end().len().max(escaped_len));
+        if name.is_empty() {
+            return match self.chars.peek() {
+                Some(_) => Err(PipelineParseError::ExpectedName(start)),
+                None => Err(PipelineParseError::UnexpectedEnd),
+            };
+        }
+
+        Ok((name, escaped_len > 0))
+    }
+}
+
+#[cfg(test)]
+mod pipeline_parser_tests {
+    use super::*;
+
+    fn stage(label: &str) -> Pipeline {
+        Pipeline::Stage(label.to_string())
+    }
+
+    #[test]
+    fn sequences() {
+        assert_eq!(PipelineParser::new("Foo").parse().unwrap(), stage("Foo"));
+        assert_eq!(
+            PipelineParser::new("Foo > Foo Bar Adapter>Bar").parse().unwrap(), 
+            Pipeline::Sequence(vec![stage("Foo"), stage("Foo Bar Adapter"), stage("Bar")])
+        );
+        assert_eq!(
+            PipelineParser::new("Foo,Skip,Bar").with_delimiter(',').with_skip("Skip").parse().unwrap(), 
+            Pipeline::Sequence(vec![stage("Foo"), Pipeline::Skip, stage("Bar")])
+        );
+    }
+
+    #[test]
+    fn fan_out_and_branches() {
+        assert_eq!(
+            PipelineParser::new("A>(B|C>E)>D").parse().unwrap(),
+            Pipeline::Sequence(vec![
+                stage("A"),
+                Pipeline::FanOut(vec![stage("B"), Pipeline::Sequence(vec![stage("C"), stage("E")])]),
+                stage("D"),
+            ])
+        );
+
+        assert_eq!(
+            PipelineParser::new("A>[Big: B | $entity: C | $resource: D | _: (E|F)]").parse().unwrap(),
+            Pipeline::Sequence(vec![
+                stage("A"),
+                Pipeline::Branch(vec![
+                    (Guard::Event(EventId::from("Big")), stage("B")),
+                    (Guard::Entity, stage("C")),
+                    (Guard::Resource, stage("D")),
+                    (Guard::Default, Pipeline::FanOut(vec![stage("E"), stage("F")])),
+                ]),
+            ])
+        );
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(
+            PipelineParser::new(r"A\>B > C\(1\) \\").parse().unwrap(),
+            Pipeline::Sequence(vec![stage("A>B"), stage(r"C(1) \")])
+        );
+        assert_eq!(
+            PipelineParser::new("A/,B,_,/_").with_delimiter(',').with_escape('/').with_skip("_").parse().unwrap(),
+            Pipeline::Sequence(vec![stage("A,B"), Pipeline::Skip, stage("_")])
+        );
+        assert_eq!(
+            PipelineParser::new(r"[\_: A | \$entity: B]").parse().unwrap(),
+            Pipeline::Branch(vec![(Guard::Event(EventId::from("_")), stage("A")), (Guard::Event(EventId::from("$entity")), stage("B"))])
+        );
+        assert_eq!(PipelineParser::new(r"A\").parse().unwrap_err(), PipelineParseError::UnexpectedEnd);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(PipelineParser::new("").parse().unwrap_err(), PipelineParseError::UnexpectedEnd);
+        assert_eq!(PipelineParser::new("A>").parse().unwrap_err(), PipelineParseError::UnexpectedEnd);
+        assert_eq!(PipelineParser::new("A>>B").parse().unwrap_err(), PipelineParseError::ExpectedName(2));
+        assert_eq!(PipelineParser::new("(A|B").parse().unwrap_err(), PipelineParseError::UnexpectedEnd);
+        assert_eq!(PipelineParser::new("A)").parse().unwrap_err(), PipelineParseError::UnexpectedChar(1, ')'));
+        assert_eq!(PipelineParser::new("[A]").parse().unwrap_err(), PipelineParseError::UnexpectedChar(2, ']'));
+        assert_eq!(PipelineParser::new("[$foo: A]").parse().unwrap_err(), PipelineParseError::UnknownGuard(1));
+    }
+}
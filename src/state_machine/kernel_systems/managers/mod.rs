@@ -1,5 +1,8 @@
 pub mod blocker_manager;
 pub mod delay_manager;
 pub mod event_manager;
+pub mod event_mapper_manager;
 pub mod executable_manager;
-pub mod orchestration_manager;
\ No newline at end of file
+pub mod orchestration_manager;
+pub mod time_event_manager;
+pub mod while_manager;
\ No newline at end of file
@@ -0,0 +1,65 @@
+use std::{sync::Mutex, time::{Duration, Instant, SystemTime}};
+
+/// Where the `TimeEventManager` gets the time from
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+
+    /// Wall-clock time, used by cron schedules
+    fn system_now(&self) -> SystemTime;
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn system_now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock which only moves when advanced
+#[derive(Debug)]
+pub struct MockClock {
+    instant: Instant,
+    system_time: SystemTime,
+    elapsed: Mutex<Duration>,
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new(SystemTime::UNIX_EPOCH)
+    }
+}
+
+impl MockClock {
+    /// Starts at `system_time` on the wall-clock
+    pub fn new(system_time: SystemTime) -> Self {
+        Self {
+            instant: Instant::now(),
+            system_time,
+            elapsed: Mutex::new(Duration::ZERO)
+        }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        *self.elapsed.lock().unwrap() += by;
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.instant + self.elapsed()
+    }
+
+    fn system_now(&self) -> SystemTime {
+        self.system_time + self.elapsed()
+    }
+}
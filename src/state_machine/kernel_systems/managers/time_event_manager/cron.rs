@@ -0,0 +1,179 @@
+use std::{str::FromStr, time::SystemTime};
+
+#[derive(Debug, PartialEq)]
+pub enum CronParseError {
+    /// Expected 5 fields
+    FieldCount(usize),
+    /// (field index, field)
+    InvalidField(usize, String),
+}
+
+/// `minute hour day-of-month month day-of-week` in UTC, each field being `*`, `*/n`, `a`, `a-b`, `a-b/n` or a `,` list of those.
+/// Like cron, if both day fields are restricted either may match
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    /// 0 is Sunday
+    days_of_week: u64,
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(source: &str) -> Result<Self, CronParseError> {
+        let fields = source.split_whitespace().collect::<Vec<_>>();
+        if fields.len() != 5 {
+            return Err(CronParseError::FieldCount(fields.len()));
+        }
+
+        let field = |index: usize, min: u64, max: u64| {
+            Self::parse_field(fields[index], min, max).ok_or_else(|| CronParseError::InvalidField(index, fields[index].to_string()))
+        };
+
+        Ok(Self {
+            minutes: field(0, 0, 59)?,
+            hours: field(1, 0, 23)?,
+            days_of_month: field(2, 1, 31)?,
+            months: field(3, 1, 12)?,
+            days_of_week: field(4, 0, 6)?,
+            any_day_of_month: fields[2] == "*",
+            any_day_of_week: fields[4] == "*",
+        })
+    }
+
+    // bitset of allowed values
+    fn parse_field(field: &str, min: u64, max: u64) -> Option<u64> {
+        let mut bits = 0;
+
+        for part in field.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => (range, step.parse::<u64>().ok().filter(|&step| step > 0)?),
+                None => (part, 1),
+            };
+
+            let (start, end) = match range {
+                "*" => (min, max),
+                range => match range.split_once('-') {
+                    Some((start, end)) => (start.parse().ok()?, end.parse().ok()?),
+                    None => {
+                        let value = range.parse().ok()?;
+                        // `a/n` runs from a to the max
+                        (value, if step > 1 { max } else { value })
+                    }
+                }
+            };
+
+            if start < min || end > max || start > end {
+                return None;
+            }
+
+            for value in (start..=end).step_by(step as usize) {
+                bits |= 1 << value;
+            }
+        }
+
+        Some(bits)
+    }
+
+    pub fn matches(&self, time: SystemTime) -> bool {
+        let Ok(since_epoch) = time.duration_since(SystemTime::UNIX_EPOCH) else {
+            return false;
+        };
+
+        let seconds = since_epoch.as_secs();
+        let days = seconds / 86_400;
+        let minute = (seconds / 60) % 60;
+        let hour = (seconds / 3_600) % 24;
+        // 1970-01-01 was a Thursday
+        let day_of_week = (days + 4) % 7;
+        let (_, month, day_of_month) = civil_from_days(days);
+
+        let has = |bits: u64, value: u64| bits & (1 << value) != 0;
+
+        let day_of_month_matches = has(self.days_of_month, day_of_month);
+        let day_of_week_matches = has(self.days_of_week, day_of_week);
+        let day_matches = match (self.any_day_of_month, self.any_day_of_week) {
+            (false, false) => day_of_month_matches || day_of_week_matches,
+            _ => day_of_month_matches && day_of_week_matches,
+        };
+
+        has(self.minutes, minute) && has(self.hours, hour) && has(self.months, month) && day_matches
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = CronParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// (year, month, day) of days since 1970-01-01, from Howard Hinnant's `civil_from_days`
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + (month <= 2) as u64;
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod cron_tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn at(seconds: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn civil_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(59), (1970, 3, 1));
+        // 2000-02-29
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+    }
+
+    #[test]
+    fn matches_fields() {
+        let every_five = CronSchedule::parse("*/5 * * * *").unwrap();
+        assert!(every_five.matches(at(0)));
+        assert!(every_five.matches(at(5 * 60 + 30)));
+        assert!(!every_five.matches(at(6 * 60)));
+
+        // 1970-01-01 was a Thursday
+        let thursday_noon = CronSchedule::parse("0 12 * * 4").unwrap();
+        assert!(thursday_noon.matches(at(12 * 3_600)));
+        assert!(!thursday_noon.matches(at(86_400 + 12 * 3_600)));
+
+        // day of month or day of week
+        let first_or_friday = CronSchedule::parse("0 0 1 * 5").unwrap();
+        assert!(first_or_friday.matches(at(0)));
+        assert!(first_or_friday.matches(at(86_400)));
+        assert!(!first_or_friday.matches(at(2 * 86_400)));
+
+        let list = CronSchedule::parse("1,3-4,10/20 * * * *").unwrap();
+        for (minute, matches) in [(1, true), (2, false), (3, true), (4, true), (10, true), (30, true), (50, true), (51, false)] {
+            assert_eq!(list.matches(at(minute * 60)), matches, "{minute}");
+        }
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(CronSchedule::parse("* * *"), Err(CronParseError::FieldCount(3)));
+        assert_eq!(CronSchedule::parse("60 * * * *"), Err(CronParseError::InvalidField(0, String::from("60"))));
+        assert_eq!(CronSchedule::parse("* * 0 * *"), Err(CronParseError::InvalidField(2, String::from("0"))));
+        assert_eq!(CronSchedule::parse("*/0 * * * *"), Err(CronParseError::InvalidField(0, String::from("*/0"))));
+    }
+}
@@ -0,0 +1,6 @@
+pub mod clock;
+pub mod cron;
+pub mod schedule;
+pub mod timed_event;
+pub mod time_event_registry;
+pub mod time_event_manager;
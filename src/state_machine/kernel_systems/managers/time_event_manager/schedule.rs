@@ -0,0 +1,23 @@
+use std::time::{Duration, Instant};
+
+use crate::prelude::CronSchedule;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Schedule {
+    /// Every n ticks, starting n ticks after it is first seen
+    EveryTicks(u64),
+    /// Every duration, starting a duration after it is first seen
+    Every(Duration),
+    /// Once, on the first tick at or after this one
+    AtTick(u64),
+    /// Once, on the first tick at or after this instant
+    At(Instant),
+    /// Once per matching wall-clock minute
+    Cron(CronSchedule),
+}
+
+impl Schedule {
+    pub fn is_one_shot(&self) -> bool {
+        matches!(self, Self::AtTick(_) | Self::At(_))
+    }
+}
@@ -0,0 +1,55 @@
+use std::{pin::Pin, sync::Arc};
+
+use tracing::{Level, event};
+
+use crate::prelude::{CurrentEvents, KernelSystem, Memory, NextEvents, ProgramId, ProgramKey, ResourceId, ScheduleId, Shared, StateMachine, SystemId, TickAccumulator, TimeEventRegistry, TimedEvent, Unique};
+
+/// Emits events on tick & wall-clock schedules into `NextEvents`
+pub struct TimeEventManager;
+
+impl TimeEventManager {
+    pub fn insert_timed_event(state_machine: &StateMachine, schedule_id: impl Into<ScheduleId>, timed_event: TimedEvent) -> Option<Option<TimedEvent>> {
+        let mut time_event_registry = state_machine.resolve::<Unique<TimeEventRegistry>>(None, None, None, None)?.ok()?;
+        Some(time_event_registry.insert(schedule_id, timed_event))
+    }
+}
+
+impl KernelSystem for TimeEventManager {
+    fn system_id(&self) -> SystemId {
+        SystemId::from("Time Event Manager")
+    }
+
+    fn init(&mut self, memory: &Memory, _kernel_program_id: &ProgramId, _kernel_program_key: &ProgramKey) {
+        event!(Level::DEBUG, "Inserting TimeEventRegistry");
+        assert!(memory.insert(None, None, None, TimeEventRegistry::default()).unwrap().is_ok());
+
+        event!(Level::DEBUG, "Checking NextEvents");
+        if !matches!(memory.contains_resource(None, &ResourceId::from_raw_heap::<NextEvents>(), None), Some(true)) {
+            event!(Level::WARN, "NextEvents Not Found");   
+        }
+
+        event!(Level::DEBUG, "Checking CurrentEvents");
+        if !matches!(memory.contains_resource(None, &ResourceId::from_raw_heap::<CurrentEvents>(), None), Some(true)) {
+            event!(Level::WARN, "CurrentEvents Not Found");   
+        }
+    }
+
+    fn tick(&mut self, memory: &Arc<Memory>, _kernel_program_id: ProgramId, _kernel_program_key: ProgramKey) -> Pin<Box<dyn Future<Output = ()> + '_ + Send>> {
+        let memory = Arc::clone(&memory);
+        Box::pin(async move {
+            let mut registry = memory.resolve::<Unique<TimeEventRegistry>>(None, None, None, None).unwrap().unwrap();
+            let mut next_events = memory.resolve::<Unique<NextEvents>>(None, None, None, None).unwrap().unwrap();
+            let current_events = memory.resolve::<Shared<CurrentEvents>>(None, None, None, None).unwrap().unwrap();
+            let current_tick = memory.resolve::<Shared<TickAccumulator>>(None, None, None, None).unwrap().unwrap().load();
+
+            event!(Level::DEBUG, schedule_count = registry.len());
+
+            for (schedule_id, event_id) in registry.tick(current_tick, &current_events) {
+                event!(Level::TRACE, schedule_id = ?schedule_id, event_id = ?event_id, "Timed Event");
+                next_events.insert(event_id);
+            }
+
+            event!(Level::DEBUG, new_next_event_count = next_events.len());
+        })
+    }
+}
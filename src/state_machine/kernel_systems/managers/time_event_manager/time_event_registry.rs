@@ -0,0 +1,100 @@
+use std::{collections::HashMap, sync::Arc};
+
+use tracing::{Level, event};
+
+use crate::prelude::{Clock, CurrentEvents, EventId, ScheduleId, SystemClock, TimedEvent};
+
+pub struct TimeEventRegistry {
+    clock: Arc<dyn Clock>,
+    timed_events: HashMap<ScheduleId, TimedEvent>,
+}
+
+impl Default for TimeEventRegistry {
+    fn default() -> Self {
+        Self {
+            clock: Arc::new(SystemClock),
+            timed_events: HashMap::new()
+        }
+    }
+}
+
+impl TimeEventRegistry {
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    /// e.g. a `MockClock` for tests
+    pub fn replace_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    pub fn insert(&mut self, schedule_id: impl Into<ScheduleId>, timed_event: TimedEvent) -> Option<TimedEvent> {
+        self.timed_events.insert(schedule_id.into(), timed_event)
+    }
+
+    pub fn get(&self, schedule_id: &ScheduleId) -> Option<&TimedEvent> {
+        self.timed_events.get(schedule_id)
+    }
+
+    pub fn cancel(&mut self, schedule_id: &ScheduleId) -> Option<TimedEvent> {
+        self.timed_events.remove(schedule_id)
+    }
+
+    /// False if there is no such schedule
+    pub fn pause(&mut self, schedule_id: &ScheduleId) -> bool {
+        self.timed_events.get_mut(schedule_id).map(TimedEvent::pause).is_some()
+    }
+
+    /// False if there is no such schedule
+    pub fn resume(&mut self, schedule_id: &ScheduleId) -> bool {
+        self.timed_events.get_mut(schedule_id).map(TimedEvent::resume).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.timed_events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.timed_events.is_empty()
+    }
+
+    /// Applies the control events then returns the events due this tick.
+    /// Cancelled & finished schedules are removed
+    pub fn tick(&mut self, current_tick: u64, current_events: &CurrentEvents) -> Vec<(ScheduleId, EventId)> {
+        let now = self.clock.now();
+        let system_now = self.clock.system_now();
+
+        let contains = |event: &Option<EventId>| event.as_ref().is_some_and(|event| current_events.contains(event));
+
+        self.timed_events.retain(|schedule_id, timed_event| {
+            let cancelled = contains(timed_event.cancel_on());
+            if cancelled {
+                event!(Level::TRACE, schedule_id = ?schedule_id, "Schedule Cancelled");
+            }
+
+            !cancelled
+        });
+
+        let mut due = Vec::new();
+        for (schedule_id, timed_event) in &mut self.timed_events {
+            if contains(timed_event.pause_on()) && !timed_event.is_paused() {
+                event!(Level::TRACE, schedule_id = ?schedule_id, "Schedule Paused");
+                timed_event.pause();
+            }
+
+            // resuming wins if both are current
+            if contains(timed_event.resume_on()) && timed_event.is_paused() {
+                event!(Level::TRACE, schedule_id = ?schedule_id, "Schedule Resumed");
+                timed_event.resume();
+            }
+
+            if timed_event.poll(current_tick, now, system_now) {
+                due.extend(timed_event.events().iter().map(|event_id| (schedule_id.clone(), event_id.clone())));
+            }
+        }
+
+        self.timed_events.retain(|_, timed_event| !timed_event.is_finished());
+
+        due
+    }
+}
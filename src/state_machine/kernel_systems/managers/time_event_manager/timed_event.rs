@@ -0,0 +1,207 @@
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::prelude::{EventId, Schedule};
+
+/// Events emitted on a schedule, which can be paused, resumed & cancelled by other events
+#[derive(Debug, Clone)]
+pub struct TimedEvent {
+    schedule: Schedule,
+    events: Vec<EventId>,
+    pause_on: Option<EventId>,
+    resume_on: Option<EventId>,
+    cancel_on: Option<EventId>,
+
+    paused: bool,
+    fired: u64,
+    next_tick: Option<u64>,
+    next_instant: Option<Instant>,
+    last_minute: Option<u64>,
+}
+
+impl TimedEvent {
+    pub fn new(schedule: Schedule, events: Vec<EventId>) -> Self {
+        Self {
+            schedule,
+            events,
+            pause_on: None,
+            resume_on: None,
+            cancel_on: None,
+            paused: false,
+            fired: 0,
+            next_tick: None,
+            next_instant: None,
+            last_minute: None,
+        }
+    }
+
+    /// Stops emitting while paused, missed emissions aren't replayed
+    pub fn with_pause_on(mut self, event: impl Into<EventId>) -> Self {
+        self.pause_on = Some(event.into());
+        self
+    }
+
+    pub fn with_resume_on(mut self, event: impl Into<EventId>) -> Self {
+        self.resume_on = Some(event.into());
+        self
+    }
+
+    pub fn with_cancel_on(mut self, event: impl Into<EventId>) -> Self {
+        self.cancel_on = Some(event.into());
+        self
+    }
+
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    pub fn events(&self) -> &Vec<EventId> {
+        &self.events
+    }
+
+    pub fn pause_on(&self) -> &Option<EventId> {
+        &self.pause_on
+    }
+
+    pub fn resume_on(&self) -> &Option<EventId> {
+        &self.resume_on
+    }
+
+    pub fn cancel_on(&self) -> &Option<EventId> {
+        &self.cancel_on
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// How many times the events have been emitted
+    pub fn fired(&self) -> u64 {
+        self.fired
+    }
+
+    /// A fired one-shot is finished
+    pub fn is_finished(&self) -> bool {
+        self.schedule.is_one_shot() && self.fired > 0
+    }
+
+    /// True if the events should be emitted this tick
+    pub fn poll(&mut self, tick: u64, now: Instant, system_now: SystemTime) -> bool {
+        let due = match &self.schedule {
+            Schedule::EveryTicks(ticks) => {
+                let ticks = (*ticks).max(1);
+                let next_tick = *self.next_tick.get_or_insert(tick + ticks);
+                if tick >= next_tick {
+                    self.next_tick = Some(tick + ticks);
+                    true
+                } else {
+                    false
+                }
+            },
+            Schedule::Every(duration) => {
+                let next_instant = *self.next_instant.get_or_insert(now + *duration);
+                if now >= next_instant {
+                    // if we fell behind by more than an interval, don't burst
+                    let next_instant = next_instant + *duration;
+                    self.next_instant = Some(if next_instant <= now { now + *duration } else { next_instant });
+                    true
+                } else {
+                    false
+                }
+            },
+            Schedule::AtTick(at) => self.fired == 0 && tick >= *at,
+            Schedule::At(at) => self.fired == 0 && now >= *at,
+            Schedule::Cron(cron) => {
+                let minute = system_now.duration_since(UNIX_EPOCH).map(|since_epoch| since_epoch.as_secs() / 60).ok();
+                if minute.is_some() && minute != self.last_minute && cron.matches(system_now) {
+                    self.last_minute = minute;
+                    true
+                } else {
+                    false
+                }
+            }
+        };
+
+        // schedules keep advancing while paused
+        if due && !self.paused {
+            self.fired += 1;
+            return true;
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod timed_event_tests {
+    use std::time::Duration;
+
+    use crate::prelude::CronSchedule;
+
+    use super::*;
+
+    #[test]
+    fn every_ticks() {
+        let mut timed_event = TimedEvent::new(Schedule::EveryTicks(3), vec![]);
+        let now = Instant::now();
+
+        let fired = (5..15).filter(|&tick| timed_event.poll(tick, now, SystemTime::UNIX_EPOCH)).collect::<Vec<_>>();
+        assert_eq!(fired, vec![8, 11, 14]);
+    }
+
+    #[test]
+    fn every_duration() {
+        let mut timed_event = TimedEvent::new(Schedule::Every(Duration::from_secs(2)), vec![]);
+        let start = Instant::now();
+
+        assert!(!timed_event.poll(0, start, SystemTime::UNIX_EPOCH));
+        assert!(!timed_event.poll(1, start + Duration::from_secs(1), SystemTime::UNIX_EPOCH));
+        assert!(timed_event.poll(2, start + Duration::from_secs(2), SystemTime::UNIX_EPOCH));
+        // fell far behind, fires once
+        assert!(timed_event.poll(3, start + Duration::from_secs(9), SystemTime::UNIX_EPOCH));
+        assert!(!timed_event.poll(4, start + Duration::from_secs(10), SystemTime::UNIX_EPOCH));
+        assert!(timed_event.poll(5, start + Duration::from_secs(11), SystemTime::UNIX_EPOCH));
+    }
+
+    #[test]
+    fn one_shots_finish() {
+        let mut timed_event = TimedEvent::new(Schedule::AtTick(2), vec![]);
+        let now = Instant::now();
+
+        assert!(!timed_event.poll(1, now, SystemTime::UNIX_EPOCH));
+        assert!(timed_event.poll(3, now, SystemTime::UNIX_EPOCH));
+        assert!(timed_event.is_finished());
+        assert!(!timed_event.poll(4, now, SystemTime::UNIX_EPOCH));
+    }
+
+    #[test]
+    fn cron_once_per_minute() {
+        let mut timed_event = TimedEvent::new(Schedule::Cron(CronSchedule::parse("* * * * *").unwrap()), vec![]);
+        let now = Instant::now();
+
+        assert!(timed_event.poll(0, now, SystemTime::UNIX_EPOCH));
+        assert!(!timed_event.poll(1, now, SystemTime::UNIX_EPOCH + Duration::from_secs(30)));
+        assert!(timed_event.poll(2, now, SystemTime::UNIX_EPOCH + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn paused_skips() {
+        let mut timed_event = TimedEvent::new(Schedule::EveryTicks(1), vec![]);
+        let now = Instant::now();
+
+        assert!(!timed_event.poll(0, now, SystemTime::UNIX_EPOCH));
+        assert!(timed_event.poll(1, now, SystemTime::UNIX_EPOCH));
+        timed_event.pause();
+        assert!(!timed_event.poll(2, now, SystemTime::UNIX_EPOCH));
+        timed_event.resume();
+        assert!(timed_event.poll(3, now, SystemTime::UNIX_EPOCH));
+        assert_eq!(timed_event.fired(), 2);
+    }
+}
@@ -0,0 +1,9 @@
+pub mod while_event;
+pub mod while_registry;
+pub mod while_manager;
+
+// Delay (while do), like a kettle: A turn on; B boiling in progress; D use boiled water
+// WhileEvent::new(A, B).with_finally([D])
+
+// Wait (do until), like a tap: A turn on; C water; B turn off
+// WhileEvent::until(A, B).with_do([C])
@@ -0,0 +1,82 @@
+use crate::prelude::EventId;
+
+/// When `when` is current, activate; then while `condition` is current (or until it is) emit `do_events` each tick.
+/// Once the loop ends emit `finally_events`
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhileEvent {
+    when: EventId,
+    condition: EventId,
+    do_events: Vec<EventId>,
+    finally_events: Vec<EventId>,
+    is_until: bool,
+    max_concurrent: Option<usize>,
+}
+
+impl WhileEvent {
+    /// Loops while `condition` is current
+    pub fn new(when: impl Into<EventId>, condition: impl Into<EventId>) -> Self {
+        Self {
+            when: when.into(),
+            condition: condition.into(),
+            do_events: Vec::new(),
+            finally_events: Vec::new(),
+            is_until: false,
+            max_concurrent: None,
+        }
+    }
+
+    /// Loops until `condition` is current
+    pub fn until(when: impl Into<EventId>, condition: impl Into<EventId>) -> Self {
+        Self {
+            is_until: true,
+            ..Self::new(when, condition)
+        }
+    }
+
+    /// Empty can mean Delay
+    pub fn with_do(mut self, events: impl IntoIterator<Item = impl Into<EventId>>) -> Self {
+        self.do_events = events.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Empty can mean Faucet
+    pub fn with_finally(mut self, events: impl IntoIterator<Item = impl Into<EventId>>) -> Self {
+        self.finally_events = events.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Activations while `max_concurrent` instances are active are ignored
+    pub fn with_max_concurrent(mut self, max_concurrent: usize) -> Self {
+        self.max_concurrent = Some(max_concurrent);
+        self
+    }
+
+    pub fn when(&self) -> &EventId {
+        &self.when
+    }
+
+    pub fn condition(&self) -> &EventId {
+        &self.condition
+    }
+
+    pub fn do_events(&self) -> &Vec<EventId> {
+        &self.do_events
+    }
+
+    pub fn finally_events(&self) -> &Vec<EventId> {
+        &self.finally_events
+    }
+
+    pub fn is_until(&self) -> bool {
+        self.is_until
+    }
+
+    pub fn max_concurrent(&self) -> Option<usize> {
+        self.max_concurrent
+    }
+
+    /// Whether the loop keeps going given the events
+    pub fn holds(&self, contains_condition: bool) -> bool {
+        contains_condition != self.is_until
+    }
+}
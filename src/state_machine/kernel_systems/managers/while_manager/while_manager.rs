@@ -0,0 +1,53 @@
+use std::{pin::Pin, sync::Arc};
+
+use tracing::{Level, event};
+
+use crate::prelude::{CurrentEvents, KernelSystem, LoopId, Memory, NextEvents, ProgramId, ProgramKey, ResourceId, Shared, StateMachine, SystemId, TickAccumulator, Unique, WhileEvent, WhileRegistry};
+
+/// Runs `WhileEvent` loops over the events
+pub struct WhileManager;
+
+impl WhileManager {
+    pub fn insert_loop(state_machine: &StateMachine, loop_id: impl Into<LoopId>, while_event: WhileEvent) -> Option<Option<WhileEvent>> {
+        let mut while_registry = state_machine.resolve::<Unique<WhileRegistry>>(None, None, None, None)?.ok()?;
+        Some(while_registry.insert(loop_id, while_event))
+    }
+}
+
+impl KernelSystem for WhileManager {
+    fn system_id(&self) -> SystemId {
+        SystemId::from("While Manager")
+    }
+
+    fn init(&mut self, memory: &Memory, _kernel_program_id: &ProgramId, _kernel_program_key: &ProgramKey) {
+        event!(Level::DEBUG, "Inserting WhileRegistry");
+        assert!(memory.insert(None, None, None, WhileRegistry::default()).unwrap().is_ok());
+
+        event!(Level::DEBUG, "Checking NextEvents");
+        if !matches!(memory.contains_resource(None, &ResourceId::from_raw_heap::<NextEvents>(), None), Some(true)) {
+            event!(Level::WARN, "NextEvents Not Found");   
+        }
+
+        event!(Level::DEBUG, "Checking CurrentEvents");
+        if !matches!(memory.contains_resource(None, &ResourceId::from_raw_heap::<CurrentEvents>(), None), Some(true)) {
+            event!(Level::WARN, "CurrentEvents Not Found");   
+        }
+    }
+
+    fn tick(&mut self, memory: &Arc<Memory>, _kernel_program_id: ProgramId, _kernel_program_key: ProgramKey) -> Pin<Box<dyn Future<Output = ()> + '_ + Send>> {
+        let memory = Arc::clone(&memory);
+        Box::pin(async move {
+            let mut registry = memory.resolve::<Unique<WhileRegistry>>(None, None, None, None).unwrap().unwrap();
+            let mut next_events = memory.resolve::<Unique<NextEvents>>(None, None, None, None).unwrap().unwrap();
+            let current_events = memory.resolve::<Shared<CurrentEvents>>(None, None, None, None).unwrap().unwrap();
+            let current_tick = memory.resolve::<Shared<TickAccumulator>>(None, None, None, None).unwrap().unwrap().load();
+
+            for (loop_id, event_id) in registry.tick(current_tick, &current_events) {
+                event!(Level::TRACE, loop_id = ?loop_id, event_id = ?event_id, "Loop Event");
+                next_events.insert(event_id);
+            }
+
+            event!(Level::DEBUG, active_loops = ?registry.active().collect::<Vec<_>>());
+        })
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+use tracing::{Level, event};
+
+use crate::prelude::{CurrentEvents, EventId, LoopId, WhileEvent};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WhileState {
+    /// No instances
+    Idle,
+    /// Emitting `do_events` each tick
+    Active,
+    /// Emitted `finally_events` this tick, removed next tick
+    Finishing,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhileInstance {
+    state: WhileState,
+    started_tick: u64,
+    iterations: u64,
+}
+
+impl WhileInstance {
+    pub fn state(&self) -> WhileState {
+        self.state
+    }
+
+    pub fn started_tick(&self) -> u64 {
+        self.started_tick
+    }
+
+    /// How many times `do_events` were emitted
+    pub fn iterations(&self) -> u64 {
+        self.iterations
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct WhileRegistry(HashMap<LoopId, (WhileEvent, Vec<WhileInstance>)>);
+
+impl WhileRegistry {
+    pub fn insert(&mut self, loop_id: impl Into<LoopId>, while_event: WhileEvent) -> Option<WhileEvent> {
+        self.0.insert(loop_id.into(), (while_event, Vec::new())).map(|(while_event, _)| while_event)
+    }
+
+    /// Drops any running instances without emitting `finally_events`
+    pub fn remove(&mut self, loop_id: &LoopId) -> Option<WhileEvent> {
+        self.0.remove(loop_id).map(|(while_event, _)| while_event)
+    }
+
+    pub fn get(&self, loop_id: &LoopId) -> Option<&WhileEvent> {
+        self.0.get(loop_id).map(|(while_event, _)| while_event)
+    }
+
+    pub fn instances(&self, loop_id: &LoopId) -> &[WhileInstance] {
+        self.0.get(loop_id).map_or(&[], |(_, instances)| instances)
+    }
+
+    /// Active if any instance is, None if there is no such loop
+    pub fn state(&self, loop_id: &LoopId) -> Option<WhileState> {
+        let (_, instances) = self.0.get(loop_id)?;
+
+        Some(instances.iter().map(WhileInstance::state).fold(WhileState::Idle, |state, instance| {
+            match (state, instance) {
+                (WhileState::Active, _) | (_, WhileState::Active) => WhileState::Active,
+                _ => WhileState::Finishing,
+            }
+        }))
+    }
+
+    /// Loops with an active instance, and how many
+    pub fn active(&self) -> impl Iterator<Item = (&LoopId, usize)> {
+        self.0.iter().filter_map(|(loop_id, (_, instances))| {
+            match instances.iter().filter(|instance| instance.state == WhileState::Active).count() {
+                0 => None,
+                count => Some((loop_id, count))
+            }
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the events to emit next
+    pub fn tick(&mut self, current_tick: u64, current_events: &CurrentEvents) -> Vec<(LoopId, EventId)> {
+        let mut emitting = Vec::new();
+
+        for (loop_id, (while_event, instances)) in &mut self.0 {
+            instances.retain(|instance| instance.state != WhileState::Finishing);
+
+            if current_events.contains(while_event.when()) {
+                if while_event.max_concurrent().is_none_or(|max_concurrent| instances.len() < max_concurrent) {
+                    event!(Level::TRACE, loop_id = ?loop_id, "Loop Activated");
+                    instances.push(WhileInstance { state: WhileState::Active, started_tick: current_tick, iterations: 0 });
+                } else {
+                    event!(Level::TRACE, loop_id = ?loop_id, "Loop Activation Ignored");
+                }
+            }
+
+            let holds = while_event.holds(current_events.contains(while_event.condition()));
+            for instance in instances.iter_mut() {
+                let events = if holds {
+                    instance.iterations += 1;
+                    while_event.do_events()
+                } else {
+                    event!(Level::TRACE, loop_id = ?loop_id, iterations = instance.iterations, "Loop Finishing");
+                    instance.state = WhileState::Finishing;
+                    while_event.finally_events()
+                };
+
+                emitting.extend(events.iter().map(|event_id| (loop_id.clone(), event_id.clone())));
+            }
+        }
+
+        emitting
+    }
+}
+
+#[cfg(test)]
+mod while_registry_tests {
+    use super::*;
+
+    fn current(events: &[&str]) -> CurrentEvents {
+        let mut current_events = CurrentEvents::default();
+        current_events.tick(events.iter().map(|&event| EventId::from(event)));
+        current_events
+    }
+
+    fn emitted(emitting: Vec<(LoopId, EventId)>) -> Vec<String> {
+        emitting.into_iter().map(|(_, event_id)| event_id.get_id().to_string()).collect()
+    }
+
+    #[test]
+    fn while_do_finally() {
+        let mut registry = WhileRegistry::default();
+        let loop_id = LoopId::from("Kettle");
+        registry.insert(loop_id.clone(), WhileEvent::new("On", "Boiling").with_do(["Bubble"]).with_finally(["Boiled"]));
+
+        assert_eq!(registry.state(&loop_id), Some(WhileState::Idle));
+
+        assert_eq!(emitted(registry.tick(0, &current(&["On", "Boiling"]))), vec!["Bubble"]);
+        assert_eq!(registry.state(&loop_id), Some(WhileState::Active));
+
+        assert_eq!(emitted(registry.tick(1, &current(&["Boiling"]))), vec!["Bubble"]);
+        assert_eq!(registry.instances(&loop_id)[0].iterations(), 2);
+
+        assert_eq!(emitted(registry.tick(2, &current(&[]))), vec!["Boiled"]);
+        assert_eq!(registry.state(&loop_id), Some(WhileState::Finishing));
+
+        assert!(registry.tick(3, &current(&[])).is_empty());
+        assert_eq!(registry.state(&loop_id), Some(WhileState::Idle));
+    }
+
+    #[test]
+    fn until() {
+        let mut registry = WhileRegistry::default();
+        registry.insert("Tap", WhileEvent::until("On", "Off").with_do(["Water"]));
+
+        assert_eq!(emitted(registry.tick(0, &current(&["On"]))), vec!["Water"]);
+        assert_eq!(emitted(registry.tick(1, &current(&[]))), vec!["Water"]);
+        assert!(registry.tick(2, &current(&["Off"])).is_empty());
+        assert_eq!(registry.active().count(), 0);
+    }
+
+    #[test]
+    fn concurrent_activations() {
+        let mut registry = WhileRegistry::default();
+        let loop_id = LoopId::from("Tap");
+        registry.insert(loop_id.clone(), WhileEvent::until("On", "Off").with_do(["Water"]).with_max_concurrent(2));
+
+        registry.tick(0, &current(&["On"]));
+        registry.tick(1, &current(&["On"]));
+        assert_eq!(emitted(registry.tick(2, &current(&["On"]))), vec!["Water", "Water"]);
+        assert_eq!(registry.active().collect::<Vec<_>>(), vec![(&loop_id, 2)]);
+        assert_eq!(registry.instances(&loop_id).iter().map(WhileInstance::started_tick).collect::<Vec<_>>(), vec![0, 1]);
+    }
+}
@@ -20,4 +20,4 @@ pub trait KernelSystem {
 pub type StoredKernelSystem = Box<dyn KernelSystem>;
 
     // todo!("then start/end non blocking can use that");
-    // todo!("then read only can get blocking for threadpool & runtime (& init)");
\ No newline at end of file
+    // todo!("then read only can get blocking for executor & runtime (& init)");
\ No newline at end of file
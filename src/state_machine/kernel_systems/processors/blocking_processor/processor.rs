@@ -2,7 +2,7 @@ use std::{pin::Pin, sync::Arc};
 
 use tracing::{Level, event, span};
 
-use crate::prelude::{ExecutionGraph, KernelSystem, Memory, NextBlockers, NextEvents, Processor, ProcessorSystemRegistry, ProgramId, ProgramKey, ResourceId, Shared, StateMachine, StoredSystem, SystemEventRegistry, SystemId, SystemMetadata, Unique};
+use crate::prelude::{ExecutionGraph, Executor, KernelSystem, Memory, NextBlockers, NextEvents, Processor, ProcessorSystemRegistry, ProgramId, ProgramKey, ResourceId, Shared, StateMachine, StoredSystem, SystemEventRegistry, SystemId, SystemMetadata, Unique};
 
 #[derive(Debug)]
 pub struct BlockingProcessor;
@@ -38,14 +38,9 @@ impl KernelSystem for BlockingProcessor {
             event!(Level::WARN, "SystemEventRegistry Not Found")
         }
 
-        event!(Level::DEBUG, "Checking Arc<tokio::runtime::Runtime>");
-        if !matches!(memory.contains_resource(Some(kernel_program_id), &ResourceId::from_raw_heap::<Arc<tokio::runtime::Runtime>>(), Some(kernel_program_key)), Some(true)) {
-            event!(Level::WARN, "Arc<tokio::runtime::Runtime> Not Found")
-        }
-        
-        event!(Level::DEBUG, "Checking threadpool::ThreadPool");
-        if !matches!(memory.contains_resource(Some(kernel_program_id), &ResourceId::from_raw_heap::<threadpool::ThreadPool>(), Some(kernel_program_key)), Some(true)) {
-            event!(Level::WARN, "threadpool::ThreadPool Not Found")
+        event!(Level::DEBUG, "Checking Executor");
+        if !matches!(memory.contains_resource(Some(kernel_program_id), &ResourceId::from_raw_heap::<Executor>(), Some(kernel_program_key)), Some(true)) {
+            event!(Level::WARN, "Executor Not Found")
         }
     }
 
@@ -65,7 +60,7 @@ impl KernelSystem for BlockingProcessor {
                 for &id in systems.keys() {
                     let event_id = id.clone().into_id();
                     event!(Level::TRACE, event=?event_id, "New Event");
-                    next_events.insert(event_id);
+                    next_events.emit(event_id, Some(id.clone()));
                 }
             }
     
@@ -82,14 +77,7 @@ impl KernelSystem for BlockingProcessor {
                 })
                 .collect::<Vec<_>>();
 
-            let runtime = memory.resolve::<Shared<Arc<tokio::runtime::Runtime>>>(
-                Some(&kernel_program_id), 
-                None, 
-                None, 
-                Some(&kernel_program_key)
-            ).unwrap().unwrap();
-
-            let threadpool = memory.resolve::<Shared<threadpool::ThreadPool>>(
+            let executor = memory.resolve::<Shared<Executor>>(
                 Some(&kernel_program_id), 
                 None, 
                 None, 
@@ -101,10 +89,12 @@ impl KernelSystem for BlockingProcessor {
                 &memory,
                 Arc::new(execution_graphs),
                 &system_registry.0,
-                &threadpool,
-                &runtime
+                &executor
             ).await;
 
+            drop(system_registry);
+            Processor::remove_spent(&memory, &mut memory.resolve::<Unique<ProcessorSystemRegistry>>(None, None, None, None).unwrap().unwrap().0);
+
             let mut next_events = memory.resolve::<Unique<NextEvents>>(None, None, None, None).unwrap().unwrap();
             let mut next_blockers = memory.resolve::<Unique<NextBlockers>>(None, None, None, None).unwrap().unwrap();
 
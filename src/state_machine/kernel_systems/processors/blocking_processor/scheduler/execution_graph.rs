@@ -29,6 +29,14 @@ impl<T> ExecutionGraph<T> {
         &self.finished
     }
 
+    pub fn len(&self) -> usize {
+        self.current_flow.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.current_flow.is_empty()
+    }
+
     pub fn create_flow(nodes: &HashMap<T, HashSet<T>>) -> impl Iterator<Item = (&T, (AtomicUsize, AtomicU8))> {
         nodes.iter().map(|(node, after)| {
             (node, (AtomicUsize::new(after.len()), AtomicU8::new(Self::INIT)))
@@ -216,21 +224,31 @@ impl<T> ExecutionGraph<T> where T: Eq + Hash + Clone {
         None
     }
 
-    pub fn mark_as_complete(&mut self, marking: &T) {
+    /// Returns the nodes which became leaves because of this completion
+    pub fn mark_as_complete(&mut self, marking: &T) -> Vec<T> {
+        let mut ready = Vec::new();
+
         let previous_state = self.current_flow.get_mut(marking).unwrap().1.swap(Self::FINISHED, Ordering::Release);
         if previous_state == Self::FINISHED {
-            return;
+            return ready;
         }
 
         for (t, afters) in &self.nodes {
             if afters.contains(marking) {
                 // Can't change between lines of code because &mut self
-                let value = self.current_flow.get(t).unwrap().0.load(Ordering::Acquire);
+                let (dependents, status) = self.current_flow.get(t).unwrap();
+                let value = dependents.load(Ordering::Acquire);
                 if value != 0 {
-                    self.current_flow.get_mut(t).unwrap().0.store(value - 1, Ordering::Release);   
+                    dependents.store(value - 1, Ordering::Release);
+
+                    if value == 1 && status.load(Ordering::Acquire) != Self::FINISHED {
+                        ready.push(t.clone());
+                    }
                 }
             }
         }
+
+        ready
     }
 
     pub fn mark_as_pending(&mut self, pending: &T) {
@@ -297,10 +315,24 @@ mod execution_graph {
             assert_eq!(graph.current_flow.len(), should_see.len());
             assert_eq!(graph.nodes.len(), should_see.len());
             
-            can_end_check(graph, should_see);
+            can_end_check(graph, should_see.clone());
+            ready_flow_check(ExecutionGraph::new(&inputs), should_see);
         }
     }
 
+    fn ready_flow_check(mut graph: ExecutionGraph<T>, mut should_see: HashSet<T>) {
+        // check that following only the nodes returned by `mark_as_complete` sees every node exactly once
+        let mut ready = graph.leaves().map(|(leaf, _)| leaf).cloned().collect::<Vec<_>>();
+
+        while let Some(node) = ready.pop() {
+            assert!(should_see.remove(&node));
+            ready.extend(graph.mark_as_complete(&node));
+        }
+
+        assert!(should_see.is_empty());
+        assert_eq!(graph.leaves().count(), 0);
+    }
+
     fn can_end_check(mut graph: ExecutionGraph<T>, mut should_see: HashSet<T>) {
         // check if can reach the end and see all nodes only once and that there are leaves at the start
         if graph.nodes.len() > 0 {
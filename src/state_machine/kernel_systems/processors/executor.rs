@@ -0,0 +1,539 @@
+use std::{any::Any, collections::VecDeque, panic::AssertUnwindSafe, pin::Pin, sync::{Arc, Condvar, Mutex, Weak, atomic::{AtomicBool, AtomicU64, Ordering}}, task::{Context, Poll, Wake, Waker}, thread::JoinHandle, time::{Duration, Instant}};
+
+type Job = Box<dyn FnOnce(usize) + Send + 'static>;
+type BackgroundJob = Box<dyn FnOnce() + Send + 'static>;
+
+/// A job which panicked while running on a worker
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobPanic {
+    worker: usize,
+    message: String,
+}
+
+impl JobPanic {
+    pub fn worker(&self) -> usize {
+        self.worker
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// The result of a job spawned onto the background workers, `Err` holds the panic message
+pub struct JobHandle<T>(Arc<Mutex<Option<Result<T, String>>>>);
+
+impl<T> JobHandle<T> {
+    fn new() -> Self {
+        Self(Arc::new(Mutex::new(None)))
+    }
+
+    /// False again once the result is taken
+    pub fn is_finished(&self) -> bool {
+        self.0.lock().unwrap().is_some()
+    }
+
+    /// None until the job finishes
+    pub fn take(&self) -> Option<Result<T, String>> {
+        self.0.lock().unwrap().take()
+    }
+}
+
+/// Snapshot of how much a worker has been used since the executor started
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkerUtilization {
+    pub worker: usize,
+    pub jobs: u64,
+    pub busy: Duration,
+    /// `busy` as a fraction of the executor's uptime
+    pub utilization: f64,
+}
+
+#[derive(Default)]
+struct WorkerStats {
+    jobs: AtomicU64,
+    busy_nanos: AtomicU64,
+}
+
+struct ExecutorState {
+    // Jobs any worker may take
+    injector: VecDeque<Job>,
+    // Jobs only the owning worker may take
+    pinned: Vec<VecDeque<Job>>,
+    // Queued + running jobs
+    pending: usize,
+    shutdown: bool,
+}
+
+struct ExecutorShared {
+    state: Mutex<ExecutorState>,
+    work: Condvar,
+    idle: Condvar,
+    stats: Vec<WorkerStats>,
+    panics: Mutex<Vec<JobPanic>>,
+}
+
+#[derive(Default)]
+struct BackgroundState {
+    jobs: VecDeque<BackgroundJob>,
+    // Workers waiting for a job
+    idle: usize,
+    spawned: usize,
+    shutdown: bool,
+}
+
+/// Workers for jobs which outlive a tick, spawned on demand and exiting once idle for `KEEP_ALIVE`
+struct Background {
+    state: Mutex<BackgroundState>,
+    work: Condvar,
+    runtime: Option<tokio::runtime::Handle>,
+}
+
+impl Background {
+    const KEEP_ALIVE: Duration = Duration::from_secs(10);
+
+    fn queue(self: &Arc<Self>, job: BackgroundJob) {
+        let mut state = self.state.lock().unwrap();
+        if state.shutdown {
+            return;
+        }
+
+        state.jobs.push_back(job);
+        if state.jobs.len() <= state.idle {
+            drop(state);
+            self.work.notify_one();
+            return;
+        }
+
+        let worker = state.spawned;
+        state.spawned += 1;
+        drop(state);
+
+        let background = Arc::clone(self);
+        std::thread::Builder::new()
+            .name(format!("aion-background-{worker}"))
+            .spawn(move || {
+                let _runtime = background.runtime.as_ref().map(|runtime| runtime.enter());
+                background.work();
+            })
+            .unwrap();
+    }
+
+    fn work(&self) {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(job) = state.jobs.pop_front() {
+                drop(state);
+                // Jobs catch their own panics
+                job();
+                state = self.state.lock().unwrap();
+                continue;
+            }
+
+            if state.shutdown {
+                return;
+            }
+
+            state.idle += 1;
+            let (guard, wait) = self.work.wait_timeout(state, Self::KEEP_ALIVE).unwrap();
+            state = guard;
+            state.idle -= 1;
+
+            if wait.timed_out() && state.jobs.is_empty() {
+                return;
+            }
+        }
+    }
+
+    fn shutdown(&self) {
+        self.state.lock().unwrap().shutdown = true;
+        self.work.notify_all();
+    }
+}
+
+/// A spawned future, polled on a background worker each time it is woken
+struct FutureTask {
+    future: Mutex<Option<Pin<Box<dyn Future<Output = ()> + Send>>>>,
+    panicked: Box<dyn Fn(String) + Send + Sync>,
+    // Stops a future woken many times between polls being queued more than once
+    queued: AtomicBool,
+    background: Weak<Background>,
+}
+
+impl FutureTask {
+    fn poll(self: Arc<Self>) {
+        self.queued.store(false, Ordering::Release);
+
+        let mut future = self.future.lock().unwrap();
+        let Some(inner) = future.as_mut() else {
+            return;
+        };
+
+        let waker = Waker::from(Arc::clone(&self));
+        match std::panic::catch_unwind(AssertUnwindSafe(|| inner.as_mut().poll(&mut Context::from_waker(&waker)))) {
+            Ok(Poll::Pending) => (),
+            Ok(Poll::Ready(())) => *future = None,
+            Err(payload) => {
+                *future = None;
+                (self.panicked)(panic_message(payload.as_ref()));
+            }
+        }
+    }
+}
+
+impl Wake for FutureTask {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        if self.queued.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        // Dropped with the executor
+        if let Some(background) = self.background.upgrade() {
+            let task = Arc::clone(self);
+            background.queue(Box::new(move || task.poll()));
+        }
+    }
+}
+
+/// Fixed set of worker threads which run both sync systems and poll async systems, 
+/// plus background workers for systems which run across ticks.
+/// Every worker enters the tokio runtime (if given), which only drives the timers & IO used by async systems
+pub struct Executor {
+    shared: Arc<ExecutorShared>,
+    workers: Vec<JoinHandle<()>>,
+    background: Arc<Background>,
+    runtime: Option<tokio::runtime::Runtime>,
+    started: Instant,
+}
+
+impl Executor {
+    pub fn new(threads: usize, runtime: Option<tokio::runtime::Runtime>) -> Self {
+        let threads = threads.max(1);
+        let handle = runtime.as_ref().map(|runtime| runtime.handle().clone());
+
+        let shared = Arc::new(ExecutorShared {
+            state: Mutex::new(ExecutorState {
+                injector: VecDeque::new(),
+                pinned: (0..threads).map(|_| VecDeque::new()).collect(),
+                pending: 0,
+                shutdown: false,
+            }),
+            work: Condvar::new(),
+            idle: Condvar::new(),
+            stats: (0..threads).map(|_| WorkerStats::default()).collect(),
+            panics: Mutex::new(Vec::new()),
+        });
+
+        let workers = (0..threads).map(|worker| {
+            let shared = Arc::clone(&shared);
+            let runtime = handle.clone();
+
+            std::thread::Builder::new()
+                .name(format!("aion-worker-{worker}"))
+                .spawn(move || {
+                    let _runtime = runtime.as_ref().map(|runtime| runtime.enter());
+                    Self::work(&shared, worker);
+                })
+                .unwrap()
+        }).collect();
+
+        Self {
+            shared,
+            workers,
+            background: Arc::new(Background {
+                state: Mutex::new(BackgroundState::default()),
+                work: Condvar::new(),
+                runtime: handle,
+            }),
+            runtime,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Queues a job for any worker, the job is given the index of the worker running it
+    pub fn execute<F: FnOnce(usize) + Send + 'static>(&self, job: F) {
+        self.queue(None, Box::new(job));
+    }
+
+    /// Queues a job which only runs on `worker` (modulo the thread count)
+    pub fn execute_on<F: FnOnce(usize) + Send + 'static>(&self, worker: usize, job: F) {
+        self.queue(Some(worker % self.threads()), Box::new(job));
+    }
+
+    /// Runs the job on a background worker, `join` doesn't wait for it
+    pub fn spawn<T: Send + 'static, F: FnOnce() -> T + Send + 'static>(&self, job: F) -> JobHandle<T> {
+        let handle = JobHandle::new();
+        let result = Arc::clone(&handle.0);
+
+        self.background.queue(Box::new(move || {
+            let finished = std::panic::catch_unwind(AssertUnwindSafe(job)).map_err(|payload| panic_message(payload.as_ref()));
+            *result.lock().unwrap() = Some(finished);
+        }));
+
+        handle
+    }
+
+    /// Polls the future on the background workers whenever it is woken, `join` doesn't wait for it
+    pub fn spawn_future<T: Send + 'static, F: Future<Output = T> + Send + 'static>(&self, future: F) -> JobHandle<T> {
+        let handle = JobHandle::new();
+        let (result, panicked) = (Arc::clone(&handle.0), Arc::clone(&handle.0));
+
+        let task = Arc::new(FutureTask {
+            future: Mutex::new(Some(Box::pin(async move {
+                let output = future.await;
+                *result.lock().unwrap() = Some(Ok(output));
+            }))),
+            panicked: Box::new(move |message| *panicked.lock().unwrap() = Some(Err(message))),
+            queued: AtomicBool::new(false),
+            background: Arc::downgrade(&self.background),
+        });
+        task.wake_by_ref();
+
+        handle
+    }
+
+    /// Queued + running jobs
+    pub fn pending(&self) -> usize {
+        self.shared.state.lock().unwrap().pending
+    }
+
+    /// Blocks until every queued job has finished, returning the panics caught since the last `join`
+    pub fn join(&self) -> Vec<JobPanic> {
+        let mut state = self.shared.state.lock().unwrap();
+        while state.pending > 0 {
+            state = self.shared.idle.wait(state).unwrap();
+        }
+        drop(state);
+
+        self.take_panics()
+    }
+
+    /// Panics caught since the last `join`/`take_panics`
+    pub fn take_panics(&self) -> Vec<JobPanic> {
+        std::mem::take(&mut *self.shared.panics.lock().unwrap())
+    }
+
+    pub fn utilization(&self) -> Vec<WorkerUtilization> {
+        let uptime = self.started.elapsed().as_secs_f64();
+
+        self.shared.stats.iter().enumerate().map(|(worker, stats)| {
+            let busy = Duration::from_nanos(stats.busy_nanos.load(Ordering::Acquire));
+            WorkerUtilization {
+                worker,
+                jobs: stats.jobs.load(Ordering::Acquire),
+                busy,
+                utilization: if uptime > 0.0 { (busy.as_secs_f64() / uptime).min(1.0) } else { 0.0 },
+            }
+        }).collect()
+    }
+
+    fn queue(&self, worker: Option<usize>, job: Job) {
+        let mut state = self.shared.state.lock().unwrap();
+        match worker {
+            Some(worker) => state.pinned[worker].push_back(job),
+            None => state.injector.push_back(job),
+        }
+        state.pending += 1;
+        drop(state);
+
+        // Pinned jobs need a specific worker so wake everyone
+        if worker.is_some() {
+            self.shared.work.notify_all();
+        } else {
+            self.shared.work.notify_one();
+        }
+    }
+
+    fn work(shared: &ExecutorShared, worker: usize) {
+        loop {
+            let job = {
+                let mut state = shared.state.lock().unwrap();
+                loop {
+                    if let Some(job) = state.pinned[worker].pop_front().or_else(|| state.injector.pop_front()) {
+                        break job;
+                    }
+
+                    if state.shutdown {
+                        return;
+                    }
+
+                    state = shared.work.wait(state).unwrap();
+                }
+            };
+
+            let start = Instant::now();
+            let result = std::panic::catch_unwind(AssertUnwindSafe(|| job(worker)));
+
+            let stats = &shared.stats[worker];
+            stats.jobs.fetch_add(1, Ordering::AcqRel);
+            stats.busy_nanos.fetch_add(start.elapsed().as_nanos() as u64, Ordering::AcqRel);
+
+            if let Err(payload) = result {
+                shared.panics.lock().unwrap().push(JobPanic { worker, message: panic_message(payload.as_ref()) });
+            }
+
+            let mut state = shared.state.lock().unwrap();
+            state.pending -= 1;
+            if state.pending == 0 {
+                shared.idle.notify_all();
+            }
+        }
+    }
+}
+
+impl Drop for Executor {
+    fn drop(&mut self) {
+        self.shared.state.lock().unwrap().shutdown = true;
+        self.shared.work.notify_all();
+
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+
+        // Background workers may be stuck in a long running job so aren't joined
+        self.background.shutdown();
+
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("Unknown panic payload")
+    }
+}
+
+#[cfg(test)]
+mod executor_tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use super::*;
+
+    #[test]
+    fn runs_all_jobs() {
+        let executor = Executor::new(3, None);
+        let count = Arc::new(AtomicUsize::new(0));
+
+        for _ in 0..50 {
+            let count = Arc::clone(&count);
+            executor.execute(move |_| {
+                count.fetch_add(1, Ordering::Relaxed);
+            });
+        }
+
+        assert!(executor.join().is_empty());
+        assert_eq!(count.load(Ordering::Relaxed), 50);
+        assert_eq!(executor.pending(), 0);
+        assert_eq!(executor.utilization().iter().map(|worker| worker.jobs).sum::<u64>(), 50);
+    }
+
+    #[test]
+    fn pinned_jobs_run_on_worker() {
+        let executor = Executor::new(4, None);
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        for worker in 0..8 {
+            let tx = tx.clone();
+            executor.execute_on(worker, move |current| {
+                tx.send((worker % 4, current)).unwrap();
+            });
+        }
+
+        drop(tx);
+        assert!(executor.join().is_empty());
+        for (expected, current) in rx.iter() {
+            assert_eq!(expected, current);
+        }
+    }
+
+    #[test]
+    fn panics_are_caught() {
+        let executor = Executor::new(2, None);
+
+        executor.execute_on(1, |_| panic!("Foo"));
+        executor.execute(|_| ());
+
+        let panics = executor.join();
+        assert_eq!(panics, vec![JobPanic { worker: 1, message: String::from("Foo") }]);
+
+        // Workers survive the panic
+        let (tx, rx) = std::sync::mpsc::channel();
+        executor.execute_on(1, move |current| tx.send(current).unwrap());
+        assert!(executor.join().is_empty());
+        assert_eq!(rx.recv().unwrap(), 1);
+    }
+
+    #[test]
+    fn enters_runtime() {
+        let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap();
+        let executor = Executor::new(1, Some(runtime));
+
+        executor.execute(|_| {
+            pollster::block_on(tokio::time::sleep(Duration::from_millis(1)));
+        });
+
+        assert!(executor.join().is_empty());
+    }
+
+    fn wait_for<T>(handle: &JobHandle<T>) -> Result<T, String> {
+        let started = Instant::now();
+        loop {
+            if let Some(result) = handle.take() {
+                return result;
+            }
+
+            assert!(started.elapsed() < Duration::from_secs(5), "Job never finished");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn background_jobs_outlive_join() {
+        let executor = Executor::new(1, None);
+        let (tx, rx) = std::sync::mpsc::channel::<()>();
+
+        let blocked = executor.spawn(move || rx.recv().is_ok());
+        let panics = executor.spawn::<(), _>(|| panic!("Foo"));
+
+        // Neither holds up the workers
+        executor.execute(|_| ());
+        assert!(executor.join().is_empty());
+        assert_eq!(wait_for(&panics), Err(String::from("Foo")));
+        assert!(!blocked.is_finished());
+
+        tx.send(()).unwrap();
+        assert_eq!(wait_for(&blocked), Ok(true));
+    }
+
+    #[test]
+    fn background_futures_are_polled_when_woken() {
+        let runtime = tokio::runtime::Builder::new_multi_thread().worker_threads(1).enable_all().build().unwrap();
+        let executor = Executor::new(1, Some(runtime));
+
+        let sleeps = executor.spawn_future(async {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            7
+        });
+        let panics = executor.spawn_future::<(), _>(async {
+            tokio::task::yield_now().await;
+            panic!("Bar");
+        });
+
+        assert_eq!(wait_for(&sleeps), Ok(7));
+        assert_eq!(wait_for(&panics), Err(String::from("Bar")));
+    }
+}
@@ -1,19 +1,23 @@
-use std::{collections::{HashMap, HashSet}, sync::{Arc, RwLock, atomic::Ordering}, task::{Context, Poll, Waker}};
+use std::{any::Any, collections::{HashMap, HashSet}, panic::AssertUnwindSafe, pin::Pin, sync::{Arc, PoisonError, RwLock, atomic::Ordering, mpsc::Sender}, task::{Context, Poll, Waker}, time::Instant};
 
-use crate::prelude::{CurrentBlockers, CurrentEvents, DummyWaker, ExecutionGraph, FinishedGraphTracker, Memory, Shared, StateMachine, StoredSystem, System, SystemId, SystemMetadata, SystemRegistry, SystemResult, SystemStatus, Unique, Unwinder};
+use crate::prelude::{BackgroundTasks, BlockedEvents, CriteriaContext, CurrentBlockers, CurrentEvents, EventId, NextBlockers, NextEvents, ExecutionGraph, Executor, FinishedGraphTracker, JobHandle, JobPanic, Memory, ReadyQueue, Shared, StateMachine, StoredSystem, StoredSystemMetadata, System, SystemId, SystemMetadata, SystemRegistry, SystemResult, SystemStatus, Task, TaskSignal, TaskWaker, TickAccumulator, Unique};
 
-use pollster::FutureExt;
-use tracing::{Instrument, Level, event, field, span};
+use executor::panic_message;
+use tracing::{Instrument, Level, event, field, instrument::Instrumented, span};
 
 pub mod tasks;
-pub mod unwinder;
+pub mod executor;
 pub mod finished_graphs;
+pub mod ready_queue;
 
 pub mod non_blocking_processor;
 pub mod blocking_processor;
 pub mod read_only_processor;
 pub mod system;
 
+/// An async system's future, held by the worker polling it
+type PendingSystem<'a> = Instrumented<Pin<Box<dyn Future<Output = Option<SystemResult>> + Send + 'a>>>;
+
 pub struct Processor;
 
 impl Processor {
@@ -32,10 +36,33 @@ impl Processor {
         Some(system_registry.insert(system_id, system_metadata))
     }
 
+    /// The events hidden by `BlockedEvents` this tick, removing the consumed ones from `CurrentEvents`
+    pub fn apply_blocker_modes(memory: &Memory) -> BlockedEvents {
+        let Some(Ok(blocked_events)) = memory.resolve::<Shared<BlockedEvents>>(None, None, None, None) else {
+            return BlockedEvents::default();
+        };
+
+        // Only the first processor of the tick finds them
+        let mut consumed = blocked_events.consumed().peekable();
+        if consumed.peek().is_some() {
+            if let Some(Ok(mut current_events)) = memory.resolve::<Unique<CurrentEvents>>(None, None, None, None) {
+                for event_id in consumed {
+                    current_events.remove(event_id);
+                }
+            } else {
+                event!(Level::WARN, "Failed to Consume Events");
+            }
+        }
+
+        BlockedEvents::clone(&blocked_events)
+    }
+
     pub fn get_systems<'a>(
         memory: &Memory, 
         system_registry: &'a SystemRegistry,
     ) -> HashMap<&'a SystemId, &'a SystemMetadata> {
+        let blocked_events = Self::apply_blocker_modes(memory);
+
         let current_blockers = if let Ok(current_blockers) = memory.resolve::<Shared<CurrentBlockers>>(None, None, None, None).unwrap() {
             &*current_blockers
         } else {
@@ -50,13 +77,16 @@ impl Processor {
             &CurrentEvents::default()
         };
     
-        let events = current_events.read().collect::<HashSet<_>>();
+        let events = current_events.read().filter(|event_id| !blocked_events.hides(event_id)).collect::<HashSet<_>>();
+
+        let current_tick = Self::current_tick(memory);
+        let now = Instant::now();
 
         let span = span!(Level::DEBUG, "Get Systems");
         let _enter = span.enter();
         system_registry.read()
             // Blocking Stage
-            .filter(|&(id, _)| !current_blockers.blocks(&id.clone().into()))
+            .filter(|&(id, system_metadata)| !current_blockers.blocks_system(id, system_metadata.stored_system_metadata().program_id().as_ref()))
             .inspect(|(id, _)| 
                 event!(
                     Level::TRACE, 
@@ -64,13 +94,60 @@ impl Processor {
                     "Passed Blocking Stage"
                 ) 
             )
-            // Event Stage
-            .filter(|(_, system_metadata)| system_metadata.test(&events))
+            // Disabled Stage
+            .filter(|(_, system_metadata)| {
+                let stored_system_metadata = system_metadata.stored_system_metadata();
+                memory.resolve::<Shared<StoredSystem>>(
+                    stored_system_metadata.program_id().as_ref(), 
+                    Some(stored_system_metadata.resource_id()), 
+                    None, 
+                    None
+                ).is_some_and(|system| system.is_ok_and(|system| !system.is_disabled()))
+            })
+            .inspect(|(id, _)| 
+                event!(
+                    Level::TRACE, 
+                    system_id = ?id, 
+                    "Passed Disabled Stage"
+                ) 
+            )
+            // Run Condition Stage
+            .filter(|(_, system_metadata)| {
+                let stored_system_metadata = system_metadata.stored_system_metadata();
+                memory.resolve::<Shared<StoredSystem>>(
+                    stored_system_metadata.program_id().as_ref(), 
+                    Some(stored_system_metadata.resource_id()), 
+                    None, 
+                    None
+                ).is_some_and(|system| system.is_ok_and(|system| system_metadata.run_conditions().allows(system.run_state(), current_tick, now)))
+            })
             .inspect(|(id, _)| 
                 event!(
                     Level::TRACE, 
                     system_id = ?id, 
-                    "Passed Event Stage"
+                    "Passed Run Condition Stage"
+                ) 
+            )
+            // Criteria Stage
+            .filter(|(id, system_metadata)| {
+                let stored_system_metadata = system_metadata.stored_system_metadata();
+                let context = CriteriaContext::new(&events)
+                    .with_blockers(current_blockers)
+                    .with_memory(
+                        memory, 
+                        stored_system_metadata.program_id().as_ref(), 
+                        Some(id), 
+                        stored_system_metadata.key().as_ref()
+                    );
+
+                system_metadata.evaluate(&context)
+            })
+            .inspect(|(id, system_metadata)| 
+                event!(
+                    Level::TRACE, 
+                    system_id = ?id, 
+                    criteria = %system_metadata.criteria(),
+                    "Passed Criteria Stage"
                 ) 
             )
             // Resource Stage
@@ -165,19 +242,199 @@ impl Processor {
         new_systems.into_iter()
     }
 
+    pub fn current_tick(memory: &Memory) -> u64 {
+        memory.resolve::<Shared<TickAccumulator>>(None, None, None, None)
+            .and_then(|tick_accumulator| tick_accumulator.ok())
+            .map_or(0, |tick_accumulator| tick_accumulator.load())
+    }
+
+    /// Unregisters systems whose `RunConditions` mean they can never run again.
+    /// Systems still running in the background are left until they are restored
+    pub fn remove_spent(memory: &Memory, system_registry: &mut SystemRegistry) -> Vec<SystemId> {
+        let spent = system_registry.read()
+            .filter(|(_, system_metadata)| system_metadata.run_conditions().is_once())
+            .filter(|(_, system_metadata)| {
+                let stored_system_metadata = system_metadata.stored_system_metadata();
+                memory.resolve::<Shared<StoredSystem>>(
+                    stored_system_metadata.program_id().as_ref(), 
+                    Some(stored_system_metadata.resource_id()), 
+                    None, 
+                    stored_system_metadata.key().as_ref()
+                ).is_some_and(|stored_system| stored_system.is_ok_and(|stored_system| 
+                    stored_system.has_system() && system_metadata.run_conditions().is_spent(stored_system.run_state())
+                ))
+            })
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+
+        for id in &spent {
+            event!(Level::DEBUG, system_id=?id, "Unregistering Spent System");
+            system_registry.remove(id);
+        }
+
+        spent
+    }
+
+    /// Converts a panic caught while running a system into an error and releases whatever the system still had reserved
+    fn system_panicked(
+        memory: &Memory, 
+        system_metadata: &StoredSystemMetadata, 
+        system_id: &SystemId, 
+        payload: &(dyn Any + Send), 
+        panicked_tx: &Sender<SystemId>
+    ) -> SystemResult {
+        let message = panic_message(payload);
+        event!(Level::ERROR, system_id=?system_id, message=message, "System Panicked");
+
+        memory.unreserve(system_metadata.program_id().as_ref(), system_id, system_metadata.key().as_ref());
+        let _ = panicked_tx.send(system_id.clone());
+
+        SystemResult::Error(anyhow::anyhow!("System {system_id:?} panicked: {message}"))
+    }
+
+    /// Cancels the future if it runs past `timeout`, releasing whatever the system still had reserved
+    pub fn with_timeout<'a>(
+        memory: Arc<Memory>,
+        system_metadata: StoredSystemMetadata,
+        system_id: SystemId,
+        future: Pin<Box<dyn Future<Output = Option<SystemResult>> + Send + 'a>>,
+    ) -> Pin<Box<dyn Future<Output = Option<SystemResult>> + Send + 'a>> {
+        let Some(timeout) = *system_metadata.timeout() else {
+            return future;
+        };
+
+        Box::pin(async move {
+            match tokio::time::timeout(timeout, future).await {
+                Ok(result) => result,
+                Err(_) => {
+                    // The future (and any accesses it held) has been dropped by now
+                    event!(Level::WARN, system_id=?system_id, timeout=?timeout, "System Timed Out");
+                    memory.unreserve(system_metadata.program_id().as_ref(), &system_id, system_metadata.key().as_ref());
+                    Some(SystemResult::TimedOut(timeout))
+                }
+            }
+        })
+    }
+
+    /// Drops the future once the system is cancelled, see `BackgroundTasks::cancel`
+    pub fn with_cancellation<'a>(
+        memory: Arc<Memory>,
+        system_metadata: StoredSystemMetadata,
+        system_id: SystemId,
+        signal: TaskSignal,
+        mut future: Pin<Box<dyn Future<Output = Option<SystemResult>> + Send + 'a>>,
+    ) -> Pin<Box<dyn Future<Output = Option<SystemResult>> + Send + 'a>> {
+        Box::pin(async move {
+            let mut cancelled = Box::pin(async move { signal.cancelled().await });
+            let result = std::future::poll_fn(|cx| {
+                if cancelled.as_mut().poll(cx).is_ready() {
+                    return Poll::Ready(None);
+                }
+
+                future.as_mut().poll(cx).map(Some)
+            }).await;
+
+            match result {
+                Some(result) => result,
+                None => {
+                    drop(future);
+                    event!(Level::DEBUG, system_id=?system_id, "System Cancelled");
+                    memory.unreserve(system_metadata.program_id().as_ref(), &system_id, system_metadata.key().as_ref());
+                    Some(SystemResult::Cancelled)
+                }
+            }
+        })
+    }
+
+    /// Errors the systems a panicked worker was still running, so the panic doesn't take the rest of the tick with it.
+    /// Must be called once every worker has stopped
+    fn workers_panicked(
+        memory: &Memory,
+        system_map: &HashMap<SystemId, StoredSystemMetadata>,
+        panics: &[JobPanic],
+        results_tx: &Sender<(SystemId, SystemResult)>,
+        panicked_tx: &Sender<SystemId>,
+    ) {
+        for panic in panics {
+            event!(Level::ERROR, thread_id=panic.worker(), message=panic.message(), "Thread Panicked");
+        }
+
+        if panics.is_empty() {
+            return;
+        }
+
+        // Anything still executing or pending was dropped mid-run by its worker unwinding
+        for (system_id, system_metadata) in system_map {
+            let Some(Ok(stored_system)) = memory.resolve::<Shared<StoredSystem>>(
+                system_metadata.program_id().as_ref(), 
+                Some(system_metadata.resource_id()), 
+                None, 
+                system_metadata.key().as_ref()
+            ) else {
+                continue;
+            };
+
+            let mut status = stored_system.status().lock().unwrap_or_else(PoisonError::into_inner);
+            if !matches!(*status, SystemStatus::Executing | SystemStatus::Pending) {
+                continue;
+            }
+
+            // Not counted as a run
+            *status = SystemStatus::Ready;
+            drop(status);
+
+            event!(Level::ERROR, system_id=?system_id, "System Dropped by Panicked Thread");
+            memory.unreserve(system_metadata.program_id().as_ref(), system_id, system_metadata.key().as_ref());
+            let _ = panicked_tx.send(system_id.clone());
+            let _ = results_tx.send((system_id.clone(), SystemResult::Error(anyhow::anyhow!("System {system_id:?} was running on a panicked thread"))));
+        }
+    }
+
+    /// Applies each panicked system's `PanicPolicy`, must be called once the systems are restored
+    fn record_panics(memory: &Memory, system_registry: &SystemRegistry, panicked: impl Iterator<Item = SystemId>) {
+        for system_id in panicked {
+            let Some(system_metadata) = system_registry.get(&system_id) else {
+                continue;
+            };
+
+            let stored_system_metadata = system_metadata.stored_system_metadata();
+            let Some(Ok(mut stored_system)) = memory.resolve::<Unique<StoredSystem>>(
+                stored_system_metadata.program_id().as_ref(), 
+                Some(stored_system_metadata.resource_id()), 
+                None, 
+                stored_system_metadata.key().as_ref()
+            ) else {
+                continue;
+            };
+
+            if stored_system.record_panic(system_metadata.panic_policy()) {
+                event!(Level::WARN, system_id=?system_id, panics=stored_system.panics(), "System Disabled");
+            }
+        }
+    }
+
     pub async fn execute(
         memory: &Arc<Memory>,
         execution_graphs: Arc<Vec<RwLock<ExecutionGraph<SystemId>>>>, 
         system_registry: &SystemRegistry,
-        threadpool: &threadpool::ThreadPool,
-        async_runtime: &Arc<tokio::runtime::Runtime>,
+        executor: &Executor,
     ) -> Vec<(SystemId, SystemResult)> {
-        let threads = (threadpool.max_count() - threadpool.active_count()).max(1);
+        let threads = executor.threads();
         event!(Level::DEBUG, thread_count=threads, "Thread Count");
         
         let graph_count = execution_graphs.len();
-        let chunk_size = graph_count / threads;
         let finished_graphs = Arc::new(FinishedGraphTracker::new(graph_count));
+
+        let system_count = execution_graphs.iter().map(|graph| graph.read().unwrap().len()).sum();
+        let ready_queue = Arc::new(ReadyQueue::new(threads, system_count));
+        for (graph_index, graph) in execution_graphs.iter().enumerate() {
+            let graph = graph.read().unwrap();
+            if graph.is_empty() {
+                finished_graphs.complete(graph_index);
+            }
+
+            ready_queue.extend(graph_index % threads, graph.leaves().map(|(id, _)| Task::Run(graph_index, id.clone())));
+        }
         
         let system_map = Arc::new(system_registry.read()
             .map(|(system_id, system_metadata)| (system_id.clone(), system_metadata.stored_system_metadata().clone()))
@@ -185,289 +442,296 @@ impl Processor {
         let system_cell_mapping = Arc::new(system_registry.into_system_cell_map(&memory));
         
         let (results_tx, results_rx) = std::sync::mpsc::channel();
-        let (unwinder_tx, unwinder_rx) = std::sync::mpsc::channel();
+        let (panicked_tx, panicked_rx) = std::sync::mpsc::channel();
+        let (cancelled_tx, cancelled_rx) = std::sync::mpsc::channel();
         
         let span = span!(Level::DEBUG, "Execute");
         let _enter = span.enter();
 
         for current_thread in 0..threads {
-            let start_graph = current_thread * chunk_size;
-
             let memory = Arc::clone(&memory);
 
-            let async_runtime = Arc::clone(&async_runtime);
-
             let execution_graphs = Arc::clone(&execution_graphs);
             let finished_graphs = Arc::clone(&finished_graphs);
-            let unwinder = Unwinder::new(unwinder_tx.clone(), current_thread);
+            let ready_queue = Arc::clone(&ready_queue);
             
             let system_map = Arc::clone(&system_map);
             let system_cell_mapping = Arc::clone(&system_cell_mapping);
             
             let results_tx = results_tx.clone();
+            let panicked_tx = panicked_tx.clone();
+            let cancelled_tx = cancelled_tx.clone();
 
             let thread_span = span!(Level::TRACE, "Thread", thread_id=current_thread);
 
-            threadpool.execute(move || {
-                async_runtime.block_on(async {
-                    let waker = Waker::from(Arc::new(DummyWaker));
-                    let mut context = Context::from_waker(&waker);
-                    let mut tasks = Vec::new();
-
-                    let mut current_graph_index = start_graph;
-                    while finished_graphs.load(Ordering::Acquire) > 0 {
-                        let current_graph = execution_graphs.get(current_graph_index).unwrap();
-
-                        let mut chain = 0;
-
-                        'graphs_walk: while {
-                            let current_graph = current_graph.read().unwrap();
-                            let finished = !current_graph.finished().load(Ordering::Acquire);
-                            let leaves_count = current_graph.leaves().count();
-                            finished && chain <= ( 2 * leaves_count) 
-                        } {
-                            let current_graph_read = current_graph.read().unwrap();
-                            let leaf_count = current_graph_read.leaves().count();
-
-                            if leaf_count > 0 {
-                                let nth_leaf = if let Some((id, status)) = current_graph_read.leaves().nth(chain % leaf_count) {
-                                    // Not `finished` invariant upheld by .leaves()
-                                    if status.load(Ordering::Acquire) != ExecutionGraph::<SystemId>::PENDING {
-                                        Some(id.clone())
-                                    } else {
-                                        None
+            executor.execute_on(current_thread, move |_| {
+                let _abort = ready_queue.abort_on_panic();
+                let _enter = thread_span.enter();
+
+                // Pending async systems, only polled again once their waker re-enqueues them
+                let mut tasks: HashMap<SystemId, (usize, Waker, PendingSystem<'_>)> = HashMap::new();
+
+                // `started` is false for systems which never ran, e.g. cancelled ones
+                let complete = |graph_index: usize, system_id: &SystemId, started: bool| {
+                    let mut current_graph = execution_graphs.get(graph_index).unwrap().write().unwrap();
+                    let ready = current_graph.mark_as_complete(system_id);
+
+                    let graph_finished = current_graph.leaves().next().is_none();
+                    if graph_finished {
+                        current_graph.finished().store(true, Ordering::Release);
+                    }
+                    drop(current_graph);
+
+                    // if false before then do event
+                    if graph_finished && !finished_graphs.complete(graph_index) {
+                        event!(Level::DEBUG, current_graph_index=graph_index, "Finished Current Graph");
+                    }
+
+                    ready_queue.extend(current_thread, ready.into_iter().map(|id| Task::Run(graph_index, id)));
+                    if started {
+                        ready_queue.complete();
+                    } else {
+                        ready_queue.skip();
+                    }
+                    // Completing releases accesses so deferred systems may be able to reserve now
+                    ready_queue.release_deferred(current_thread);
+                };
+
+                while !ready_queue.is_finished() {
+                    let Some(task) = ready_queue.pop(current_thread) else {
+                        ready_queue.park(current_thread, None);
+                        continue;
+                    };
+
+                    let (graph_index, system_id) = match task {
+                        Task::Run(graph_index, system_id) => (graph_index, system_id),
+                        Task::Resume(system_id) => {
+                            // Woken more than once, or after it has already finished
+                            let Some((graph_index, waker, mut fut)) = tasks.remove(&system_id) else {
+                                continue;
+                            };
+
+                            let poll = std::panic::catch_unwind(AssertUnwindSafe(|| fut.inner_mut().as_mut().poll(&mut Context::from_waker(&waker))))
+                                .unwrap_or_else(|payload| Poll::Ready(Some(Self::system_panicked(&memory, system_map.get(&system_id).unwrap(), &system_id, payload.as_ref(), &panicked_tx))));
+
+                            match poll {
+                                Poll::Pending => {
+                                    tasks.insert(system_id, (graph_index, waker, fut));
+                                },
+                                Poll::Ready(result) => {
+                                    if let Some(result) = result {
+                                        let _ = results_tx.send((system_id.clone(), result));
                                     }
-                                } else {
-                                    None
-                                };
-                                drop(current_graph_read);
 
-                                if let Some(system_id) = nth_leaf {
                                     let system_metadata = system_map.get(&system_id).unwrap();
-                                    
                                     let stored_system = memory.resolve::<Shared<StoredSystem>>(
                                         system_metadata.program_id().as_ref(), 
                                         Some(system_metadata.resource_id()), 
                                         None, 
                                         system_metadata.key().as_ref()
                                     ).unwrap().unwrap();
+
+                                    *stored_system.status().lock().unwrap() = SystemStatus::Executed;
+                                    complete(graph_index, &system_id, true);
                                     
-                                    match stored_system.status().try_lock() {
-                                        Ok(mut status) => {
-                                            match *status {
-                                                SystemStatus::Ready => {
-                                                    // Safety:
-                                                    // Only 1 reference because:
-                                                    // System is locked indirectly via status
-                                                    // unwrap wont panic because "Ready" branch only happens once
-                                                    let inner = unsafe {
-                                                        system_cell_mapping.get(&system_id).unwrap().get()
-                                                    };
-
-                                                    match inner.reserve_accesses(
-                                                        &memory, 
-                                                        system_metadata.program_id().as_ref(), 
-                                                        system_id.clone(), 
-                                                        system_metadata.key().as_ref()
-                                                    ) {
-                                                        Some(Ok(_)) => (),
-                                                        Some(Err(err)) => {
-                                                            event!(Level::TRACE, system_id=?system_id, error=?err, "Failed to reserve accesses");
-                                                            chain += 1;
-                                                            continue 'graphs_walk;
-                                                        }
-                                                        None => {
-                                                            event!(Level::TRACE, system_id=?system_id, "Failed to reserve accesses");
-                                                            chain += 1;
-                                                            continue 'graphs_walk;
-                                                        },
-                                                    } 
-                                                    
-                                                    *status = SystemStatus::Executing;
-                                                    chain = 0;
-                                                    
-                                                    match inner {
-                                                        System::Sync(sync_system) => {
-                                                            let system_span = span!(
-                                                                Level::TRACE, 
-                                                                "Sync",
-                                                                status = field::Empty,
-                                                                system_id = ?system_id, 
-                                                            );
-                                                            let _enter = system_span.enter();
-
-                                                            
-                                                            system_span.record("status", format!("{:?}", status));
-                                                            event!(
-                                                                Level::TRACE, 
-                                                                "Start"
-                                                            );
-
-                                                            let result = sync_system.run(
-                                                                &memory,
-                                                                system_metadata.program_id().as_ref(),
-                                                                Some(&system_id),
-                                                                system_metadata.key().as_ref()
-                                                            );
-
-                                                            if let Some(result) = result {
-                                                                let _ = results_tx.send((system_id.clone(), result));
-                                                            }
-
-                                                            current_graph.write().unwrap().mark_as_complete(&system_id);
-
-                                                            *status = SystemStatus::Executed;
-                                                            
-                                                            system_span.record("status", format!("{:?}", status));
-                                                            event!(
-                                                                Level::TRACE, 
-                                                                "End"
-                                                            );
-                                                        },
-                                                        System::Async(async_system) => {
-                                                            let system_span = span!(
-                                                                Level::TRACE, 
-                                                                "Async",
-                                                                status = field::Empty,
-                                                                system_id = ?system_id, 
-                                                            );
-                                                            let _enter = system_span.enter();
-
-                                                            
-                                                            system_span.record("status", format!("{:?}", status));
-                                                            event!(
-                                                                Level::TRACE, 
-                                                                "Start"
-                                                            );
-
-                                                            let mut task = async_system.run(
-                                                                Arc::clone(&memory),
-                                                                system_metadata.program_id().clone(),
-                                                                Some(system_id.clone()),
-                                                                system_metadata.key().clone()
-                                                            ).in_current_span();
-
-                                                            match task.inner_mut().as_mut().poll(&mut context) {
-                                                                Poll::Pending => {
-                                                                    current_graph.write().unwrap().mark_as_pending(&system_id);
-                                                                    *status = SystemStatus::Pending;
-
-                                                                    system_span.record("status", format!("{:?}", status));
-                                                                    event!(
-                                                                        Level::TRACE, 
-                                                                        "Pending"
-                                                                    );
-
-                                                                    tasks.push((
-                                                                        current_graph_index,
-                                                                        system_id,
-                                                                        task
-                                                                    ));
-                                                                },
-                                                                Poll::Ready(result) => {
-                                                                    if let Some(result) = result {
-                                                                        let _ = results_tx.send((system_id.clone(), result));
-                                                                    }
-
-                                                                    current_graph.write().unwrap().mark_as_complete(&system_id);
-                                                                    *status = SystemStatus::Executed;
-                                                                    
-                                                                    system_span.record("status", format!("{:?}", status));
-                                                                    event!(
-                                                                        Level::TRACE, 
-                                                                        "Ready"
-                                                                    );
-                                                                }
-                                                            }
-                                                        },
-                                                    }
-                                                    assert_ne!(*status, SystemStatus::Executing);
-                                                },
-                                                SystemStatus::Pending => chain += 1,
-                                                SystemStatus::Executed => { /* Somehow possible but is benign :) */ },
-                                                SystemStatus::Executing => { unreachable!("Somehow got a lock while another thread should be holding it (possible if another thread panics)") }
-                                            }
-                                        },
-                                        Err(_) => {
-                                            // assert!(matches!(err, std::sync::TryLockError::WouldBlock), "How poison?");
-                                            
-                                            chain += 1;
-                                            continue 'graphs_walk;
-                                        }
-                                    }
+                                    fut.span().record("status", format!("{:?}", SystemStatus::Executed));
+
+                                    event!(
+                                        parent: fut.span(),
+                                        Level::TRACE, 
+                                        "Ready",
+                                    );
                                 }
-                            } else {
-                                drop(current_graph_read);
+                            }
 
-                                current_graph.write().unwrap().finished().store(true, Ordering::Release);
+                            continue;
+                        },
+                    };
 
-                                // if false before then do event
-                                if !finished_graphs.complete(current_graph_index) {
-                                    event!(Level::DEBUG, current_graph_index=current_graph_index, "Finished Current Graph");
-                                }
+                    let system_metadata = system_map.get(&system_id).unwrap();
+
+                    if Self::cancelled(&memory, &system_id, system_metadata) {
+                        event!(Level::TRACE, system_id=?system_id, "Cancelled by Blocker");
+                        let _ = cancelled_tx.send(system_id.clone());
+                        complete(graph_index, &system_id, false);
+                        continue;
+                    }
+                    
+                    let stored_system = memory.resolve::<Shared<StoredSystem>>(
+                        system_metadata.program_id().as_ref(), 
+                        Some(system_metadata.resource_id()), 
+                        None, 
+                        system_metadata.key().as_ref()
+                    ).unwrap().unwrap();
+                    
+                    let mut status = stored_system.status().lock().unwrap();
+                    // Each system should be queued exactly once, when it becomes a leaf; the first queueing completes it
+                    if *status != SystemStatus::Ready {
+                        event!(Level::WARN, system_id=?system_id, status=?*status, "System Queued Twice");
+                        continue;
+                    }
+
+                    // Safety:
+                    // Only 1 reference because:
+                    // The system is only ever held by the worker which popped it from the queue
+                    let inner = unsafe {
+                        system_cell_mapping.get(&system_id).unwrap().get()
+                    };
+
+                    match inner.reserve_accesses(
+                        &memory, 
+                        system_metadata.program_id().as_ref(), 
+                        system_id.clone(), 
+                        system_metadata.key().as_ref()
+                    ) {
+                        Some(Ok(_)) => (),
+                        Some(Err(err)) => {
+                            event!(Level::TRACE, system_id=?system_id, error=?err, "Failed to reserve accesses");
+                            ready_queue.defer(Task::Run(graph_index, system_id));
+                            continue;
+                        }
+                        None => {
+                            event!(Level::TRACE, system_id=?system_id, "Failed to reserve accesses");
+                            ready_queue.defer(Task::Run(graph_index, system_id));
+                            continue;
+                        },
+                    } 
+                    
+                    ready_queue.start();
+                    *status = SystemStatus::Executing;
+                    
+                    match inner {
+                        System::Sync(sync_system) => {
+                            let system_span = span!(
+                                Level::TRACE, 
+                                "Sync",
+                                status = field::Empty,
+                                system_id = ?system_id, 
+                            );
+                            let _enter = system_span.enter();
+
+                            system_span.record("status", format!("{:?}", status));
+                            event!(
+                                Level::TRACE, 
+                                "Start"
+                            );
+
+                            let result = std::panic::catch_unwind(AssertUnwindSafe(|| sync_system.run(
+                                &memory,
+                                system_metadata.program_id().as_ref(),
+                                Some(&system_id),
+                                system_metadata.key().as_ref()
+                            ))).unwrap_or_else(|payload| Some(Self::system_panicked(&memory, system_metadata, &system_id, payload.as_ref(), &panicked_tx)));
+
+                            if let Some(result) = result {
+                                let _ = results_tx.send((system_id.clone(), result));
                             }
 
-                            let mut not_done = Vec::new();
-                            for (graph_number, system_id, mut fut) in tasks.drain(..) {
-                                match fut.inner_mut().as_mut().poll(&mut context) {
-                                    Poll::Pending => {
-                                        // event!(Level::TRACE, system_id=?system_id, "Async System Pending");
-
-                                        not_done.push((graph_number, system_id, fut));
-                                    },
-                                    Poll::Ready(result) => {
-                                        if let Some(result) = result {
-                                            let _ = results_tx.send((system_id.clone(), result));
-                                        }
-
-                                        let system_metadata = system_map.get(&system_id).unwrap();
-                                        let stored_system = memory.resolve::<Unique<StoredSystem>>(
-                                            system_metadata.program_id().as_ref(), 
-                                            Some(system_metadata.resource_id()), 
-                                            None, 
-                                            system_metadata.key().as_ref()
-                                        ).unwrap().unwrap();
-
-                                        *stored_system.status().lock().unwrap() = SystemStatus::Executed;
-                                        execution_graphs.get(graph_number).unwrap().write().unwrap().mark_as_complete(&system_id);
-                                        
-                                        fut.span().record("status", format!("{:?}", SystemStatus::Executed));
-
-                                        event!(
-                                            parent: fut.span(),
-                                            Level::TRACE, 
-                                            "Ready",
-                                        );
+                            *status = SystemStatus::Executed;
+                            drop(status);
+                            complete(graph_index, &system_id, true);
+                            
+                            system_span.record("status", format!("{:?}", SystemStatus::Executed));
+                            event!(
+                                Level::TRACE, 
+                                "End"
+                            );
+                        },
+                        System::Async(async_system) => {
+                            let system_span = span!(
+                                Level::TRACE, 
+                                "Async",
+                                status = field::Empty,
+                                system_id = ?system_id, 
+                            );
+                            let _enter = system_span.enter();
+
+                            system_span.record("status", format!("{:?}", status));
+                            event!(
+                                Level::TRACE, 
+                                "Start"
+                            );
+
+                            let waker = Waker::from(Arc::new(TaskWaker::new(Arc::clone(&ready_queue), current_thread, system_id.clone())));
+
+                            let polled = {
+                                let memory = Arc::clone(&memory);
+                                let program_id = system_metadata.program_id().clone();
+                                let source = Some(system_id.clone());
+                                let key = *system_metadata.key();
+                                let stored_system_metadata = system_metadata.clone();
+                                let waker = &waker;
+
+                                // `move` so the future can borrow the system for longer than the closure
+                                std::panic::catch_unwind(AssertUnwindSafe(move || {
+                                    let task = async_system.run(
+                                        Arc::clone(&memory),
+                                        program_id,
+                                        source.clone(),
+                                        key
+                                    );
+                                    let mut task = Self::with_timeout(memory, stored_system_metadata, source.unwrap(), task).in_current_span();
+
+                                    let poll = task.inner_mut().as_mut().poll(&mut Context::from_waker(waker));
+                                    (task, poll)
+                                }))
+                            };
+
+                            let (task, poll) = match polled {
+                                Ok((task, poll)) => (Some(task), poll),
+                                Err(payload) => (None, Poll::Ready(Some(Self::system_panicked(&memory, system_metadata, &system_id, payload.as_ref(), &panicked_tx)))),
+                            };
+
+                            match poll {
+                                Poll::Pending => {
+                                    execution_graphs.get(graph_index).unwrap().write().unwrap().mark_as_pending(&system_id);
+                                    *status = SystemStatus::Pending;
+
+                                    system_span.record("status", format!("{:?}", status));
+                                    event!(
+                                        Level::TRACE, 
+                                        "Pending"
+                                    );
+
+                                    tasks.insert(system_id, (
+                                        graph_index,
+                                        waker,
+                                        task.unwrap()
+                                    ));
+                                },
+                                Poll::Ready(result) => {
+                                    if let Some(result) = result {
+                                        let _ = results_tx.send((system_id.clone(), result));
                                     }
+
+                                    *status = SystemStatus::Executed;
+                                    drop(status);
+                                    complete(graph_index, &system_id, true);
+                                    
+                                    system_span.record("status", format!("{:?}", SystemStatus::Executed));
+                                    event!(
+                                        Level::TRACE, 
+                                        "Ready"
+                                    );
                                 }
                             }
-
-                            tasks.extend(not_done);
-                        }
-                    
-                        current_graph_index = (current_graph_index + 1 ) % graph_count;
+                        },
                     }
-
-                    drop(unwinder);
-                }.instrument(thread_span));
+                }
             });
         }
         
-        drop(results_tx);
-        
-        let span = span!(Level::DEBUG, "Thread Panic Checks");
-        span.in_scope(|| {
-            for _ in 0..threads {
-                let (panicked, thread_id) = unwinder_rx.recv().unwrap();
-                event!(Level::TRACE, panicked=panicked, thread_id=thread_id, "Thread Exit");
-                assert!(!panicked, "Panicked!")
-            }
-        });
+        let panics = executor.join();
 
-        threadpool.join();
+        let span = span!(Level::DEBUG, "Thread Panic Checks");
+        span.in_scope(|| Self::workers_panicked(&memory, &system_map, &panics, &results_tx, &panicked_tx));
+        drop(results_tx);
 
         event!(Level::DEBUG, "All Threads Finished");
 
+        let current_tick = Self::current_tick(&memory);
+        let now = Instant::now();
+
         let mut system_cells = Arc::try_unwrap(system_cell_mapping).unwrap();
         for (id, mut stored_system) in system_map.iter().filter_map(|(id, system_metadata)| {
             let stored_system = memory.resolve::<Unique<StoredSystem>>(
@@ -479,28 +743,58 @@ impl Processor {
 
             Some((id, stored_system))
         }) {
-            *stored_system.status().lock().unwrap() = SystemStatus::Ready;
+            let status = std::mem::replace(&mut *stored_system.status().lock().unwrap_or_else(PoisonError::into_inner), SystemStatus::Ready);
+            if status == SystemStatus::Executed {
+                stored_system.record_run(current_tick, now);
+            }
             stored_system.insert_system(system_cells.remove(id).unwrap().consume());
         }
 
         assert_eq!(system_cells.len(), 0);
 
+        drop(panicked_tx);
+        Self::record_panics(&memory, system_registry, panicked_rx.iter());
+
+        drop(cancelled_tx);
+        Self::remove_cancelled(&memory, cancelled_rx.iter());
+
         results_rx.iter().collect()
     }
 
+    /// Whether a cancelling blocker was inserted for the system since the tick started
+    fn cancelled(memory: &Memory, system_id: &SystemId, system_metadata: &StoredSystemMetadata) -> bool {
+        memory.resolve::<Shared<NextBlockers>>(None, None, None, None)
+            .and_then(|next_blockers| next_blockers.ok())
+            .is_some_and(|next_blockers| next_blockers.cancels(system_id, system_metadata.program_id().as_ref()))
+    }
+
+    /// Cancelled systems didn't run so shouldn't emit their derived event
+    fn remove_cancelled(memory: &Memory, cancelled: impl Iterator<Item = SystemId>) {
+        let Some(Ok(mut next_events)) = memory.resolve::<Unique<NextEvents>>(None, None, None, None) else {
+            return;
+        };
+
+        for system_id in cancelled {
+            next_events.remove(&EventId::from(system_id.into_id()));
+        }
+    }
+
     /// Only runs "read-only" systems so can optimise out the aliasing checks
     pub async fn execute_fast(
         memory: &Arc<Memory>,
         systems: Vec<SystemId>, 
         system_registry: &SystemRegistry,
-        threadpool: &threadpool::ThreadPool,
-        async_runtime: &Arc<tokio::runtime::Runtime>,
+        executor: &Executor,
     ) -> Vec<(SystemId, SystemResult)> {
-        let threads = (threadpool.max_count() - threadpool.active_count()).max(1);
+        let threads = executor.threads();
         event!(Level::DEBUG, thread_count=threads, "Thread Count");
 
-        let chunk_size = systems.len() / threads;
-        let systems = Arc::new(systems);
+        // No ordering between read-only systems so every system is ready immediately
+        let ready_queue = Arc::new(ReadyQueue::new(threads, systems.len()));
+        for (index, system_id) in systems.into_iter().enumerate() {
+            // Read-only systems aren't part of an execution graph
+            ready_queue.push(index % threads, Task::Run(0, system_id));
+        }
 
         let system_map = Arc::new(system_registry.read()
             .map(|(system_id, system_metadata)| (system_id.clone(), system_metadata.stored_system_metadata().clone()))
@@ -508,159 +802,232 @@ impl Processor {
         let system_cell_mapping = Arc::new(system_registry.into_system_cell_map(&memory));
 
         let (results_tx, results_rx) = std::sync::mpsc::channel();
-        let (unwinder_tx, unwinder_rx) = std::sync::mpsc::channel();
+        let (panicked_tx, panicked_rx) = std::sync::mpsc::channel();
 
         let span = span!(Level::DEBUG, "Execute Fast");
         let _enter = span.enter();
         let outer_span = span.clone();
 
         for current_thread in 0..threads {
-            let start_chunk = current_thread * chunk_size;
-            let current_thread_system_ids = start_chunk..((start_chunk + chunk_size).min(systems.len()));
-
-
             let memory = Arc::clone(&memory);
 
-            let async_runtime = Arc::clone(&async_runtime);
-
-            let systems = Arc::clone(&systems);   
-            let unwinder = Unwinder::new(unwinder_tx.clone(), current_thread);
+            let ready_queue = Arc::clone(&ready_queue);
 
             let system_map = Arc::clone(&system_map);
             let system_cell_mapping = Arc::clone(&system_cell_mapping);             
 
             let results_tx = results_tx.clone();
+            let panicked_tx = panicked_tx.clone();
             
             let outer_span = outer_span.clone();
 
-            threadpool.execute(move || {
-                async_runtime.block_on(async {
-                    let _enter = outer_span.enter();
+            executor.execute_on(current_thread, move |_| {
+                let _abort = ready_queue.abort_on_panic();
+
+                let _enter = outer_span.enter();
 
-                    let span = span!(Level::TRACE, "Thread", thread_id=current_thread);
-                    let _enter = span.enter();
+                let span = span!(Level::TRACE, "Thread", thread_id=current_thread);
+                let _enter = span.enter();
 
-                    let mut current_system_index = start_chunk;
-                    // ?
-                    while current_thread_system_ids.contains(&current_system_index) {
-                        let system_id = &systems.get(current_system_index).unwrap();
+                // Pending async systems, only polled again once their waker re-enqueues them
+                let mut tasks: HashMap<SystemId, (Waker, PendingSystem<'_>)> = HashMap::new();
 
-                        let inner = unsafe {
-                            system_cell_mapping.get(*system_id).unwrap().get()
-                        };
+                let finish = |system_id: &SystemId, system_metadata: &StoredSystemMetadata, result: Option<SystemResult>| {
+                    if let Some(result) = result {
+                        let _ = results_tx.send((system_id.clone(), result));
+                    }
+
+                    // Counted as a run when the system is restored
+                    if let Some(Ok(stored_system)) = memory.resolve::<Shared<StoredSystem>>(
+                        system_metadata.program_id().as_ref(), 
+                        Some(system_metadata.resource_id()), 
+                        None, 
+                        system_metadata.key().as_ref()
+                    ) {
+                        *stored_system.status().lock().unwrap() = SystemStatus::Executed;
+                    }
 
-                        let system_metadata = system_map.get(*system_id).unwrap();
+                    ready_queue.complete();
+                };
+
+                while !ready_queue.is_finished() {
+                    let Some(task) = ready_queue.pop(current_thread) else {
+                        ready_queue.park(current_thread, None);
+                        continue;
+                    };
+
+                    let system_id = match task {
+                        Task::Run(_, system_id) => system_id,
+                        Task::Resume(system_id) => {
+                            // Woken more than once, or after it has already finished
+                            let Some((waker, mut fut)) = tasks.remove(&system_id) else {
+                                continue;
+                            };
+
+                            let system_metadata = system_map.get(&system_id).unwrap();
+                            let poll = std::panic::catch_unwind(AssertUnwindSafe(|| fut.inner_mut().as_mut().poll(&mut Context::from_waker(&waker))))
+                                .unwrap_or_else(|payload| Poll::Ready(Some(Self::system_panicked(&memory, system_metadata, &system_id, payload.as_ref(), &panicked_tx))));
+
+                            match poll {
+                                Poll::Pending => {
+                                    tasks.insert(system_id, (waker, fut));
+                                },
+                                Poll::Ready(result) => {
+                                    finish(&system_id, system_metadata, result);
 
-                        match inner {
-                            System::Sync(sync_system) => {
-                                if !sync_system.check_read_only(Some(&system_id)) {
                                     event!(
+                                        parent: fut.span(),
                                         Level::TRACE, 
-                                        system_id = ?system_id,
-                                        "Sync System Not ReadOnly"
+                                        "System Finished"
                                     );
-                                    continue;
                                 }
+                            }
 
-                                let system_span = span!(
-                                    Level::TRACE, 
-                                    "Sync",
-                                    system_id = ?system_id, 
-                                );
-                                let _enter = system_span.enter();
+                            continue;
+                        },
+                    };
+
+                    // Safety:
+                    // Only 1 reference because:
+                    // The system is only ever held by the worker which popped it from the queue
+                    let inner = unsafe {
+                        system_cell_mapping.get(&system_id).unwrap().get()
+                    };
+
+                    let system_metadata = system_map.get(&system_id).unwrap();
+
+                    let read_only = match inner {
+                        System::Sync(sync_system) => sync_system.check_read_only(Some(&system_id)),
+                        System::Async(async_system) => async_system.check_read_only(Some(&system_id)),
+                    };
+
+                    if !read_only {
+                        event!(
+                            Level::TRACE, 
+                            system_id = ?system_id,
+                            "System Not ReadOnly"
+                        );
+                        ready_queue.skip();
+                        continue;
+                    }
 
-                                event!(
-                                    Level::TRACE, 
-                                    "System Running"
-                                );
+                    // Lets a panicked worker's systems be found, see `workers_panicked`
+                    if let Some(Ok(stored_system)) = memory.resolve::<Shared<StoredSystem>>(
+                        system_metadata.program_id().as_ref(), 
+                        Some(system_metadata.resource_id()), 
+                        None, 
+                        system_metadata.key().as_ref()
+                    ) {
+                        *stored_system.status().lock().unwrap() = SystemStatus::Executing;
+                    }
 
-                                let result = sync_system.run(
-                                    &memory, 
-                                    system_metadata.program_id().as_ref(), 
-                                    Some(&system_id), 
-                                    system_metadata.key().as_ref()
-                                );
-                                
-                                if let Some(result) = result {
-                                    let _ = results_tx.send(((*system_id).clone(), result));
-                                }
+                    ready_queue.start();
+
+                    match inner {
+                        System::Sync(sync_system) => {
+                            let system_span = span!(
+                                Level::TRACE, 
+                                "Sync",
+                                system_id = ?system_id, 
+                            );
+                            let _enter = system_span.enter();
+
+                            event!(
+                                Level::TRACE, 
+                                "System Running"
+                            );
+
+                            let result = std::panic::catch_unwind(AssertUnwindSafe(|| sync_system.run(
+                                &memory, 
+                                system_metadata.program_id().as_ref(), 
+                                Some(&system_id), 
+                                system_metadata.key().as_ref()
+                            ))).unwrap_or_else(|payload| Some(Self::system_panicked(&memory, system_metadata, &system_id, payload.as_ref(), &panicked_tx)));
+                            
+                            finish(&system_id, system_metadata, result);
+
+                            event!(
+                                Level::TRACE, 
+                                "System Finished"
+                            );
+                        },
+                        System::Async(async_system) => {
+                            let system_span = span!(
+                                Level::TRACE, 
+                                "Async",
+                                system_id = ?system_id, 
+                            );
+                            let _enter = system_span.enter();
+
+                            event!(
+                                Level::TRACE, 
+                                "System Running"
+                            );
+
+                            let waker = Waker::from(Arc::new(TaskWaker::new(Arc::clone(&ready_queue), current_thread, system_id.clone())));
+
+                            let polled = {
+                                let memory = Arc::clone(&memory);
+                                let program_id = system_metadata.program_id().clone();
+                                let source = Some(system_id.clone());
+                                let key = *system_metadata.key();
+                                let stored_system_metadata = system_metadata.clone();
+                                let waker = &waker;
+
+                                // `move` so the future can borrow the system for longer than the closure
+                                std::panic::catch_unwind(AssertUnwindSafe(move || {
+                                    let task = async_system.run(
+                                        Arc::clone(&memory),
+                                        program_id,
+                                        source.clone(),
+                                        key
+                                    );
+                                    let mut task = Self::with_timeout(memory, stored_system_metadata, source.unwrap(), task).in_current_span();
 
-                                event!(
-                                    Level::TRACE, 
-                                    "System Finished"
-                                );
-                            },
-                            System::Async(async_system) => {
-                                if !async_system.check_read_only(Some(&system_id)) {
+                                    let poll = task.inner_mut().as_mut().poll(&mut Context::from_waker(waker));
+                                    (task, poll)
+                                }))
+                            };
+
+                            let (task, poll) = match polled {
+                                Ok((task, poll)) => (Some(task), poll),
+                                Err(payload) => (None, Poll::Ready(Some(Self::system_panicked(&memory, system_metadata, &system_id, payload.as_ref(), &panicked_tx)))),
+                            };
+
+                            match poll {
+                                Poll::Pending => {
                                     event!(
                                         Level::TRACE, 
-                                        system_id = ?system_id,
-                                        "Async System Not ReadOnly"
+                                        "System Pending"
                                     );
-                                    continue;
-                                }
-
-                                let system_span = span!(
-                                    Level::TRACE, 
-                                    "Async",
-                                    system_id = ?system_id, 
-                                );
-                                let _enter = system_span.enter();
-
-                                event!(
-                                    Level::TRACE, 
-                                    "System Running"
-                                );
 
-                                // todo better async handling
-                                let result = async_system.run(
-                                    Arc::clone(&memory), 
-                                    system_metadata.program_id().clone(), 
-                                    Some((*system_id).clone()), 
-                                    system_metadata.key().clone()
-                                ).block_on();
+                                    tasks.insert(system_id, (waker, task.unwrap()));
+                                },
+                                Poll::Ready(result) => {
+                                    finish(&system_id, system_metadata, result);
 
-                                if let Some(result) = result {
-                                    let _ = results_tx.send(((*system_id).clone(), result));
+                                    event!(
+                                        Level::TRACE, 
+                                        "System Finished"
+                                    );
                                 }
-
-                                event!(
-                                    Level::TRACE, 
-                                    system_id = ?system_id,
-                                    "System Finished"
-                                );
-                            },
-                        }
-                    
-                        current_system_index += 1;
+                            }
+                        },
                     }
-
-                    drop(unwinder);
-                });                    
+                }
             });
         }
         
-        drop(results_tx);
+        let panics = executor.join();
 
         let span = span!(Level::DEBUG, "Thread Panic Checks");
-        span.in_scope(|| {
-            for _ in 0..threads {
-                let (panicked, thread_id) = unwinder_rx.recv().unwrap();
-                event!(Level::TRACE, panicked=panicked, thread_id=thread_id, "Thread Exit");
-                assert!(!panicked, "Panicked!")
-            }
-        });
-
-        threadpool.join();
+        span.in_scope(|| Self::workers_panicked(&memory, &system_map, &panics, &results_tx, &panicked_tx));
+        drop(results_tx);
         
         event!(Level::DEBUG, "All Threads Finished");
 
-        // let mut system_mapping = Arc::try_unwrap(system_mapping).unwrap();
-        // for (id, mut stored_system) in system_map.iter().map(|(id, (resource_id, program_id, key))| {
-        //         (id, memory.resolve::<Unique<StoredSystem>>(program_id.as_ref(), Some(resource_id), None, key.as_ref()).unwrap().unwrap())
-        // }) {
-        //     stored_system.insert_system(system_mapping.remove(id).unwrap().consume());
-        // }
+        let current_tick = Self::current_tick(&memory);
+        let now = Instant::now();
 
         let mut system_cells = Arc::try_unwrap(system_cell_mapping).unwrap();
         for (id, mut stored_system) in system_map.iter().filter_map(|(id, system_metadata)| {
@@ -673,26 +1040,37 @@ impl Processor {
 
             Some((id, stored_system))
         }) {
-            *stored_system.status().lock().unwrap() = SystemStatus::Ready;
+            let status = std::mem::replace(&mut *stored_system.status().lock().unwrap_or_else(PoisonError::into_inner), SystemStatus::Ready);
+            if status == SystemStatus::Executed {
+                stored_system.record_run(current_tick, now);
+            }
             stored_system.insert_system(system_cells.remove(id).unwrap().consume());
         }
 
         assert_eq!(system_cells.len(), 0);
 
+        drop(panicked_tx);
+        Self::record_panics(&memory, system_registry, panicked_rx.iter());
+
         results_rx.iter().collect()
     }
 
     pub async fn execute_non_blocking(
         memory: &Arc<Memory>,
         systems: HashMap<&SystemId, &SystemMetadata>,
-        async_runtime: &Arc<tokio::runtime::Runtime>,
-    ) -> (Vec<(SystemId, tokio::task::JoinHandle<(System, Option<SystemResult>)>)>, Vec<(SystemId, std::thread::JoinHandle<(System, Option<SystemResult>)>)>) {
+        executor: &Executor,
+    ) -> (Vec<(SystemId, JobHandle<(System, Option<SystemResult>)>)>, Vec<(SystemId, JobHandle<(System, Option<SystemResult>)>)>) {
         let mut new_async_join_handles = Vec::new();
         let mut new_sync_join_handles = Vec::new();
 
         let span = span!(Level::DEBUG, "Execute NonBlocking");
         let _enter = span.enter();
 
+        let current_tick = Self::current_tick(&memory);
+        let now = Instant::now();
+
+        let background_tasks = memory.resolve::<Shared<BackgroundTasks>>(None, None, None, None).and_then(Result::ok);
+
         for (system_id, system_metadata) in systems {
                 let program_id = system_metadata.stored_system_metadata().program_id();
                 let resource_id = system_metadata.stored_system_metadata().resource_id();
@@ -726,13 +1104,20 @@ impl Processor {
                 let program_id = program_id.clone();
                 let source = source.clone();
                 let key = system_metadata.stored_system_metadata().key().clone();
+                let stored_system_metadata = system_metadata.stored_system_metadata().clone();
+
+                // Background systems count as ran once spawned
+                system.record_run(current_tick, now);
+
+                let signal = background_tasks.as_ref()
+                    .map_or_else(TaskSignal::default, |background_tasks| background_tasks.start(system_id.clone(), current_tick, now));
 
                 if let Some(system) = system.take_system() {
                     match system {
                         System::Sync(mut sync_system) => {
                             let thread_span = span!(Level::TRACE, "Sync Thread", system_id=?system_id);
                             
-                            let join_handle = std::thread::spawn(move || {
+                            let join_handle = executor.spawn(move || {
                                 let _enter = thread_span.enter();
 
                                 event!(
@@ -740,7 +1125,13 @@ impl Processor {
                                     "Start"
                                 );
 
-                                let result = sync_system.run(&memory_clone, program_id.as_ref(), Some(&source), key.as_ref());
+                                let mut result = sync_system.run(&memory_clone, program_id.as_ref(), Some(&source), key.as_ref());
+
+                                // Cancellation is cooperative, whatever the system returned it has stopped early
+                                if signal.is_cancelled() {
+                                    memory_clone.unreserve(program_id.as_ref(), &source, key.as_ref());
+                                    result = Some(SystemResult::Cancelled);
+                                }
                                 
                                 event!(
                                     Level::TRACE, 
@@ -755,13 +1146,15 @@ impl Processor {
                         System::Async(mut async_system) => {
                             let thread_span = span!(Level::TRACE, "Async Thread", system_id=?system_id);
 
-                            let join_handle = async_runtime.spawn(async move {
+                            let join_handle = executor.spawn_future(async move {
                                 event!(
                                     Level::TRACE, 
                                     "Start"
                                 );
 
-                                let result = async_system.run(memory_clone, program_id, Some(source.clone()), key).await;
+                                let task = async_system.run(Arc::clone(&memory_clone), program_id, Some(source.clone()), key);
+                                let task = Self::with_timeout(Arc::clone(&memory_clone), stored_system_metadata.clone(), source.clone(), task);
+                                let result = Self::with_cancellation(memory_clone, stored_system_metadata, source, signal, task).await;
                                 
                                 event!(
                                     Level::TRACE, 
@@ -781,4 +1174,49 @@ impl Processor {
     
         (new_async_join_handles, new_sync_join_handles)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod processor_tests {
+    use std::collections::HashMap;
+
+    use crate::prelude::{Executor, Memory, ResourceId, Shared, StoredSystem, StoredSystemMetadata, System, SystemId, SystemResult, SystemStatus};
+
+    use super::Processor;
+
+    fn noop() -> Option<SystemResult> {
+        None
+    }
+
+    #[test]
+    fn panicked_workers_error_their_systems() {
+        let memory = Memory::new();
+        let mut system_map = HashMap::new();
+
+        for (name, status) in [("Running", SystemStatus::Executing), ("Done", SystemStatus::Executed)] {
+            let system_id = SystemId::from(name);
+            let resource_id = ResourceId::from_labelled_heap(system_id.clone().into_id());
+
+            let stored_system = StoredSystem::new(System::new_sync(noop));
+            *stored_system.status().lock().unwrap() = status;
+            assert!(matches!(memory.insert(None, Some(resource_id.clone()), None, stored_system), Some(Ok(_))));
+
+            system_map.insert(system_id, StoredSystemMetadata::new(resource_id, None, None));
+        }
+
+        let executor = Executor::new(1, None);
+        executor.execute(|_| panic!("Foo"));
+        let panics = executor.join();
+
+        let (results_tx, results_rx) = std::sync::mpsc::channel();
+        let (panicked_tx, panicked_rx) = std::sync::mpsc::channel();
+        Processor::workers_panicked(&memory, &system_map, &panics, &results_tx, &panicked_tx);
+        drop((results_tx, panicked_tx));
+
+        assert_eq!(panicked_rx.iter().collect::<Vec<_>>(), vec![SystemId::from("Running")]);
+        let results = results_rx.iter().collect::<Vec<_>>();
+        assert!(matches!(results.as_slice(), [(system_id, SystemResult::Error(_))] if *system_id == SystemId::from("Running")));
+
+        let stored_system = memory.resolve::<Shared<StoredSystem>>(None, Some(&ResourceId::from_labelled_heap(SystemId::from("Running").into_id())), None, None).unwrap().unwrap();
+        assert_eq!(*stored_system.status().lock().unwrap(), SystemStatus::Ready);
+    }
+}
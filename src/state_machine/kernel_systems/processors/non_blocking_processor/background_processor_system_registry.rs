@@ -1,18 +1,42 @@
+use std::collections::HashSet;
+
 use crate::prelude::{SystemId, SystemMetadata, SystemRegistry};
 
 #[derive(Default)]
-pub struct BackgroundProcessorSystemRegistry(SystemRegistry);
+pub struct BackgroundProcessorSystemRegistry {
+    registry: SystemRegistry,
+    /// Systems lost to a panic without a factory to recover them from
+    disabled: HashSet<SystemId>,
+}
 
 impl BackgroundProcessorSystemRegistry {
     pub fn get(&self, system_id: &SystemId) -> Option<&SystemMetadata> {
-        self.0.get(system_id)
+        self.registry.get(system_id)
     }
 
     pub fn ref_generic(&self) -> &SystemRegistry {
-        &self.0
+        &self.registry
     }
 
     pub fn ref_mut_generic(&mut self) -> &mut SystemRegistry {
-        &mut self.0
+        &mut self.registry
+    }
+
+    /// Disabled systems are never started, returns false if it already was
+    pub fn disable(&mut self, system_id: SystemId) -> bool {
+        self.disabled.insert(system_id)
+    }
+
+    /// Returns false if it wasn't disabled
+    pub fn enable(&mut self, system_id: &SystemId) -> bool {
+        self.disabled.remove(system_id)
+    }
+
+    pub fn is_disabled(&self, system_id: &SystemId) -> bool {
+        self.disabled.contains(system_id)
+    }
+
+    pub fn disabled(&self) -> impl Iterator<Item = &SystemId> {
+        self.disabled.iter()
     }
-}
\ No newline at end of file
+}
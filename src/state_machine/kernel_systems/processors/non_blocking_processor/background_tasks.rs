@@ -0,0 +1,176 @@
+use std::{collections::HashMap, sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}}, time::{Duration, Instant}};
+
+use crate::prelude::SystemId;
+
+/// Reported by a background system through its `TaskHandle`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Progress {
+    /// Clamped to `0.0..=1.0`
+    pub fraction: f32,
+    pub message: Option<String>,
+}
+
+impl Progress {
+    pub fn new(fraction: f32) -> Self {
+        Self {
+            fraction: fraction.clamp(0.0, 1.0),
+            message: None,
+        }
+    }
+
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+}
+
+/// Shared between `BackgroundTasks` and the running system
+#[derive(Debug, Clone, Default)]
+pub struct TaskSignal {
+    cancelled: Arc<AtomicBool>,
+    notify: Arc<tokio::sync::Notify>,
+    progress: Arc<Mutex<Option<Progress>>>,
+}
+
+impl TaskSignal {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+        self.notify.notify_waiters();
+    }
+
+    /// Sync systems should check this and return early
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Resolves once cancelled
+    pub async fn cancelled(&self) {
+        loop {
+            // Created before checking so a `cancel` in between isn't missed
+            let notified = self.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+
+            notified.await;
+        }
+    }
+
+    pub fn report(&self, progress: Progress) {
+        *self.progress.lock().unwrap() = Some(progress);
+    }
+
+    pub fn progress(&self) -> Option<Progress> {
+        self.progress.lock().unwrap().clone()
+    }
+}
+
+/// A system running in the background
+#[derive(Debug, Clone)]
+pub struct BackgroundTask {
+    pub system_id: SystemId,
+    pub started_tick: u64,
+    pub started_at: Instant,
+    signal: TaskSignal,
+}
+
+impl BackgroundTask {
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    pub fn progress(&self) -> Option<Progress> {
+        self.signal.progress()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.signal.is_cancelled()
+    }
+
+    pub fn signal(&self) -> &TaskSignal {
+        &self.signal
+    }
+}
+
+/// The systems started by `StartNonBlockingProcessor` that haven't been finished yet.
+/// Mutated behind `Shared` so running systems resolving their `TaskHandle` never block the processors
+#[derive(Debug, Default)]
+pub struct BackgroundTasks(Mutex<HashMap<SystemId, BackgroundTask>>);
+
+impl BackgroundTasks {
+    /// Replaces any task of the same system
+    pub fn start(&self, system_id: SystemId, started_tick: u64, started_at: Instant) -> TaskSignal {
+        let signal = TaskSignal::default();
+        self.0.lock().unwrap().insert(system_id.clone(), BackgroundTask {
+            system_id,
+            started_tick,
+            started_at,
+            signal: signal.clone(),
+        });
+
+        signal
+    }
+
+    pub fn finish(&self, system_id: &SystemId) -> Option<BackgroundTask> {
+        self.0.lock().unwrap().remove(system_id)
+    }
+
+    pub fn get(&self, system_id: &SystemId) -> Option<BackgroundTask> {
+        self.0.lock().unwrap().get(system_id).cloned()
+    }
+
+    /// Oldest first
+    pub fn running(&self) -> Vec<BackgroundTask> {
+        let mut running: Vec<_> = self.0.lock().unwrap().values().cloned().collect();
+        running.sort_by_key(|task| task.started_at);
+        running
+    }
+
+    /// Async systems are dropped at their next await, sync systems must check `TaskSignal::is_cancelled`.
+    /// Either way the run finishes with `SystemResult::Cancelled`. False if the system isn't running
+    pub fn cancel(&self, system_id: &SystemId) -> bool {
+        match self.0.lock().unwrap().get(system_id) {
+            Some(task) => {
+                task.signal.cancel();
+                true
+            },
+            None => false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod background_tasks_tests {
+    use std::time::Instant;
+
+    use crate::prelude::{BackgroundTasks, Progress, SystemId};
+
+    #[test]
+    fn lifecycle() {
+        let background_tasks = BackgroundTasks::default();
+        let foo = SystemId::from("Foo");
+
+        let signal = background_tasks.start(foo.clone(), 3, Instant::now());
+        signal.report(Progress::new(2.0).with_message("Done"));
+
+        let task = background_tasks.get(&foo).unwrap();
+        assert_eq!(task.started_tick, 3);
+        assert_eq!(task.progress(), Some(Progress { fraction: 1.0, message: Some("Done".to_string()) }));
+
+        assert!(!background_tasks.cancel(&SystemId::from("Bar")));
+        assert!(background_tasks.cancel(&foo));
+        assert!(signal.is_cancelled());
+        pollster::block_on(signal.cancelled());
+
+        assert!(background_tasks.finish(&foo).is_some());
+        assert!(background_tasks.is_empty());
+    }
+}
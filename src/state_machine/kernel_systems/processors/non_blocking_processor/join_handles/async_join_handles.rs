@@ -1,22 +1,21 @@
-use crate::prelude::{System, SystemId, SystemResult};
+use crate::prelude::{JobHandle, System, SystemId, SystemResult};
 
 #[derive(Default)]
-pub struct AsyncJoinHandles(Vec<(SystemId, tokio::task::JoinHandle<(System, Option<SystemResult>)>)>);
+pub struct AsyncJoinHandles(Vec<(SystemId, JobHandle<(System, Option<SystemResult>)>)>);
 
 impl AsyncJoinHandles {
-    pub fn push(&mut self, id: SystemId, join_handle: tokio::task::JoinHandle<(System, Option<SystemResult>)>) {
+    pub fn push(&mut self, id: SystemId, join_handle: JobHandle<(System, Option<SystemResult>)>) {
         self.0.push((id, join_handle));
     }
 
-    pub async fn get_finished(&mut self) -> Vec<(SystemId, Result<(System, Option<SystemResult>), tokio::task::JoinError>)> {
+    /// `Err` holds the panic message
+    pub fn get_finished(&mut self) -> Vec<(SystemId, Result<(System, Option<SystemResult>), String>)> {
         let mut not_finished = Vec::new();
         let mut finished = Vec::new();
         for (id, handle) in self.0.drain(..) {
-            if handle.is_finished() {
-                let result = handle.await;
-                finished.push((id, result));
-            } else {
-                not_finished.push((id, handle));
+            match handle.take() {
+                Some(result) => finished.push((id, result)),
+                None => not_finished.push((id, handle)),
             }
         }
 
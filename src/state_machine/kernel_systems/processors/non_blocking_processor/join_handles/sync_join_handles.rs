@@ -1,24 +1,21 @@
-use std::{any::Any, thread::JoinHandle};
-
-use crate::prelude::{System, SystemId, SystemResult};
+use crate::prelude::{JobHandle, System, SystemId, SystemResult};
 
 #[derive(Default)]
-pub struct SyncJoinHandles(Vec<(SystemId, JoinHandle<(System, Option<SystemResult>)>)>);
+pub struct SyncJoinHandles(Vec<(SystemId, JobHandle<(System, Option<SystemResult>)>)>);
 
 impl SyncJoinHandles {
-    pub fn push(&mut self, id: SystemId, join_handle: JoinHandle<(System, Option<SystemResult>)>) {
+    pub fn push(&mut self, id: SystemId, join_handle: JobHandle<(System, Option<SystemResult>)>) {
         self.0.push((id, join_handle));
     }
 
-    pub fn get_finished(&mut self) -> Vec<(SystemId, Result<(System, Option<SystemResult>), Box<dyn Any + Send>>)> {
+    /// `Err` holds the panic message
+    pub fn get_finished(&mut self) -> Vec<(SystemId, Result<(System, Option<SystemResult>), String>)> {
         let mut not_finished = Vec::new();
         let mut finished = Vec::new();
         for (id, handle) in self.0.drain(..) {
-            if handle.is_finished() {
-                let result = handle.join();
-                finished.push((id, result));
-            } else {
-                not_finished.push((id, handle));
+            match handle.take() {
+                Some(result) => finished.push((id, result)),
+                None => not_finished.push((id, handle)),
             }
         }
 
@@ -1,3 +1,4 @@
 pub mod background_processor_system_registry;
+pub mod background_tasks;
 pub mod join_handles;
 pub mod processors;
@@ -2,7 +2,7 @@ use std::{pin::Pin, sync::Arc};
 
 use tracing::{Level, event, span};
 
-use crate::prelude::{AsyncJoinHandles, BackgroundProcessorSystemRegistry, KernelSystem, Memory, NextBlockers, NextEvents, ProgramId, ProgramKey, ResourceId, Shared, StartNonBlockingProcessor, StateMachine, StoredSystem, SyncJoinHandles, SystemEventRegistry, SystemId, SystemMetadata, Unique};
+use crate::prelude::{AsyncJoinHandles, BackgroundProcessorSystemRegistry, BackgroundTask, BackgroundTasks, KernelSystem, Memory, NextBlockers, NextEvents, ProgramId, ProgramKey, ResourceId, Shared, StartNonBlockingProcessor, StateMachine, StoredSystem, SyncJoinHandles, System, SystemEventRegistry, SystemId, SystemMetadata, SystemResult, Unique};
 
 #[derive(Default)]
 pub struct FinishNonBlockingProcessor;
@@ -11,6 +11,80 @@ impl FinishNonBlockingProcessor {
     pub fn insert_system(state_machine: &StateMachine, system_id: SystemId, system_metadata: SystemMetadata, stored_system: StoredSystem) -> Option<Option<SystemMetadata>> {
         StartNonBlockingProcessor::insert_system(state_machine, system_id, system_metadata, stored_system)
     }
+
+    /// Puts the system back into its `StoredSystem`. A system that panicked (or whose task failed) is lost with its thread,
+    /// so it is rebuilt from its `SystemFactory`, or disabled if it has none. Either way its `PanicPolicy` applies,
+    /// its reservations are released and the panic becomes `SystemResult::Error`
+    fn restore(
+        memory: &Memory,
+        system_registry: &mut BackgroundProcessorSystemRegistry,
+        system_id: &SystemId,
+        finished: Result<(System, Option<SystemResult>), String>
+    ) -> Option<SystemResult> {
+        let system_metadata = system_registry.get(system_id).map(|system_metadata| (*system_metadata.panic_policy(), system_metadata.stored_system_metadata().clone()));
+        if system_metadata.is_none() {
+            event!(Level::WARN, "System Not Registered");
+        }
+
+        let stored_system = system_metadata.as_ref().and_then(|(_, stored_system_metadata)| memory.resolve::<Unique<StoredSystem>>(
+            stored_system_metadata.program_id().as_ref(),
+            Some(stored_system_metadata.resource_id()),
+            None,
+            stored_system_metadata.key().as_ref()
+        ).and_then(Result::ok));
+        if system_metadata.is_some() && stored_system.is_none() {
+            event!(Level::WARN, "StoredSystem Not Found");
+        }
+
+        let (system, result) = match finished {
+            Ok(finished) => finished,
+            Err(message) => {
+                event!(Level::ERROR, message=message, "System Panicked");
+
+                // Global reservations are released even if the system's program is unknown
+                let program_id = system_metadata.as_ref().and_then(|(_, stored_system_metadata)| stored_system_metadata.program_id().as_ref());
+                let key = system_metadata.as_ref().and_then(|(_, stored_system_metadata)| stored_system_metadata.key().as_ref());
+                memory.unreserve(program_id, system_id, key);
+
+                match (stored_system, system_metadata) {
+                    (Some(mut stored_system), Some((panic_policy, _))) => {
+                        if !stored_system.recover() {
+                            event!(Level::WARN, "System Lost, Disabling");
+                            system_registry.disable(system_id.clone());
+                        } else if stored_system.record_panic(&panic_policy) {
+                            event!(Level::WARN, panics=stored_system.panics(), "System Disabled");
+                        }
+                    },
+                    (None, Some(_)) => {
+                        event!(Level::WARN, "System Lost, Disabling");
+                        system_registry.disable(system_id.clone());
+                    },
+                    _ => {},
+                }
+
+                return Some(SystemResult::Error(anyhow::anyhow!("System {system_id:?} panicked: {message}")));
+            },
+        };
+
+        match stored_system {
+            Some(mut stored_system) => { stored_system.insert_system(system); },
+            None => event!(Level::WARN, "Dropping Finished System"),
+        }
+
+        result
+    }
+
+    /// The systems running in the background, oldest first
+    pub fn running(state_machine: &StateMachine) -> Vec<BackgroundTask> {
+        let background_tasks = state_machine.resolve::<Shared<BackgroundTasks>>(None, None, None, None).unwrap().unwrap();
+        background_tasks.running()
+    }
+
+    /// See `BackgroundTasks::cancel`
+    pub fn cancel(state_machine: &StateMachine, system_id: &SystemId) -> bool {
+        let background_tasks = state_machine.resolve::<Shared<BackgroundTasks>>(None, None, None, None).unwrap().unwrap();
+        background_tasks.cancel(system_id)
+    }
 }
 
 impl KernelSystem for FinishNonBlockingProcessor {
@@ -27,6 +101,9 @@ impl KernelSystem for FinishNonBlockingProcessor {
         
         event!(Level::DEBUG, "Inserting BackgroundProcessorSystemRegistry");
         assert!(memory.insert(None, None, None, BackgroundProcessorSystemRegistry::default()).unwrap().is_ok());
+
+        event!(Level::DEBUG, "Inserting BackgroundTasks");
+        assert!(memory.insert(None, None, None, BackgroundTasks::default()).unwrap().is_ok());
         
         event!(Level::DEBUG, "Checking NextEvents");
         if !matches!(memory.contains_resource(None, &ResourceId::from_raw_heap::<NextEvents>(), None), Some(true)) {
@@ -50,14 +127,16 @@ impl KernelSystem for FinishNonBlockingProcessor {
             let mut async_join_handles = memory.resolve::<Unique<AsyncJoinHandles>>(Some(&kernel_program_id), None, None, Some(&kernel_program_key)).unwrap().unwrap();
             let mut sync_join_handles = memory.resolve::<Unique<SyncJoinHandles>>(Some(&kernel_program_id), None, None, Some(&kernel_program_key)).unwrap().unwrap();
 
-            let system_registry = memory.resolve::<Shared<BackgroundProcessorSystemRegistry>>(None, None, None, None).unwrap().unwrap();
+            let mut system_registry = memory.resolve::<Unique<BackgroundProcessorSystemRegistry>>(None, None, None, None).unwrap().unwrap();
             
             let mut next_events = memory.resolve::<Unique<NextEvents>>(None, None, None, None).unwrap().unwrap();
             let mut next_blockers = memory.resolve::<Unique<NextBlockers>>(None, None, None, None).unwrap().unwrap();
 
             let system_event_registry = memory.resolve::<Shared<SystemEventRegistry>>(None, None, None, None).unwrap().unwrap();
+
+            let background_tasks = memory.resolve::<Shared<BackgroundTasks>>(None, None, None, None).unwrap().unwrap();
             
-            let async_finished = async_join_handles.get_finished().await;
+            let async_finished = async_join_handles.get_finished();
             
             let finished_span = span!(Level::DEBUG, "Finished Non Blocking");
             let _enter = finished_span;
@@ -73,16 +152,9 @@ impl KernelSystem for FinishNonBlockingProcessor {
 
                 event!(Level::TRACE, "System");
 
-                // todo dont do unwrap
-                let (finished, result) = finished.unwrap();
-                
-                let resource_id = system_registry.get(&system_id).unwrap().stored_system_metadata().resource_id();
-
-                let mut system = memory.resolve::<Unique<StoredSystem>>(None, Some(&resource_id), None, None).unwrap().unwrap();
-
-                system.insert_system(finished);
+                background_tasks.finish(&system_id);
 
-                if let Some(result) = result {
+                if let Some(result) = Self::restore(&memory, &mut system_registry, &system_id, finished) {
                     event!(Level::TRACE, result=?result, "System Returned Result");
                     result.act(
                         &system_id,
@@ -108,16 +180,9 @@ impl KernelSystem for FinishNonBlockingProcessor {
 
                 event!(Level::TRACE, "System");
 
-                // todo dont do unwrap
-                let (finished, result) = finished.unwrap();
-                
-                let resource_id = system_registry.get(&system_id).unwrap().stored_system_metadata().resource_id();
-                
-                let mut system = memory.resolve::<Unique<StoredSystem>>(None, Some(&resource_id), None, None).unwrap().unwrap();
-                
-                system.insert_system(finished);
+                background_tasks.finish(&system_id);
 
-                if let Some(result) = result {
+                if let Some(result) = Self::restore(&memory, &mut system_registry, &system_id, finished) {
                     event!(Level::TRACE, result=?result, "System Returned Result");
                     result.act(
                         &system_id,
@@ -130,4 +195,20 @@ impl KernelSystem for FinishNonBlockingProcessor {
             }
         })
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod finish_non_blocking_processor_tests {
+    use crate::prelude::{BackgroundProcessorSystemRegistry, Memory, SystemId, SystemResult};
+
+    use super::FinishNonBlockingProcessor;
+
+    #[test]
+    fn unregistered_panics_still_error() {
+        let memory = Memory::new();
+        let mut system_registry = BackgroundProcessorSystemRegistry::default();
+
+        let result = FinishNonBlockingProcessor::restore(&memory, &mut system_registry, &SystemId::from("Foo"), Err("boom".to_string()));
+        assert!(matches!(result, Some(SystemResult::Error(error)) if error.to_string().contains("boom")));
+        assert!(!system_registry.is_disabled(&SystemId::from("Foo")));
+    }
+}
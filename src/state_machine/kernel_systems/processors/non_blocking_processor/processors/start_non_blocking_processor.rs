@@ -2,13 +2,15 @@ use std::{pin::Pin, sync::Arc};
 
 use tracing::{Level, event, span};
 
-use crate::prelude::{AsyncJoinHandles, BackgroundProcessorSystemRegistry, KernelSystem, Memory, NextEvents, Processor, ProgramId, ProgramKey, ResourceId, Shared, StateMachine, StoredSystem, SyncJoinHandles, SystemId, SystemMetadata, Unique};
+use crate::prelude::{AsyncJoinHandles, BackgroundProcessorSystemRegistry, BackgroundTasks, Executor, KernelSystem, Memory, NextEvents, Processor, ProgramId, ProgramKey, ResourceId, Shared, StateMachine, StoredSystem, SyncJoinHandles, SystemId, SystemMetadata, Unique};
 
 pub struct StartNonBlockingProcessor;
 
 impl StartNonBlockingProcessor {
+    /// Re-enables the system if it was disabled
     pub fn insert_system(state_machine: &StateMachine, system_id: SystemId, system_metadata: SystemMetadata, stored_system: StoredSystem) -> Option<Option<SystemMetadata>> {
         let mut system_registry = state_machine.memory.resolve::<Unique<BackgroundProcessorSystemRegistry>>(None, None, None, None)?.ok()?;
+        system_registry.enable(&system_id);
         Processor::insert_system(state_machine, system_registry.ref_mut_generic(), system_id, system_metadata, stored_system)
     }
 }
@@ -19,9 +21,9 @@ impl KernelSystem for StartNonBlockingProcessor {
     }
 
     fn init(&mut self, memory: &Memory, kernel_program_id: &ProgramId, kernel_program_key: &ProgramKey) {
-        event!(Level::DEBUG, "Checking Arc<tokio::runtime::Runtime>");
-        if !matches!(memory.contains_resource(Some(kernel_program_id), &ResourceId::from_raw_heap::<Arc<tokio::runtime::Runtime>>(), Some(kernel_program_key)), Some(true)) {
-            event!(Level::WARN, "Arc<tokio::runtime::Runtime> Not Found")
+        event!(Level::DEBUG, "Checking Executor");
+        if !matches!(memory.contains_resource(Some(kernel_program_id), &ResourceId::from_raw_heap::<Executor>(), Some(kernel_program_key)), Some(true)) {
+            event!(Level::WARN, "Executor Not Found")
         }
         
         event!(Level::DEBUG, "Checking AsyncJoinHandles");
@@ -39,6 +41,11 @@ impl KernelSystem for StartNonBlockingProcessor {
             event!(Level::WARN, "BackgroundProcessorSystemRegistry Not Found")
         }
 
+        event!(Level::DEBUG, "Checking BackgroundTasks");
+        if !matches!(memory.contains_resource(None, &ResourceId::from_raw_heap::<BackgroundTasks>(), None), Some(true)) {
+            event!(Level::WARN, "BackgroundTasks Not Found")
+        }
+
         event!(Level::DEBUG, "Checking NextEvents");
         if !matches!(memory.contains_resource(None, &ResourceId::from_raw_heap::<NextEvents>(), None), Some(true)) {
             event!(Level::WARN, "NextEvents Not Found")
@@ -50,7 +57,8 @@ impl KernelSystem for StartNonBlockingProcessor {
         Box::pin(async move {
             let system_registry = memory.resolve::<Shared<BackgroundProcessorSystemRegistry>>(None, None, None, None).unwrap().unwrap();
             
-            let systems = Processor::get_systems(&memory, system_registry.ref_generic());
+            let mut systems = Processor::get_systems(&memory, system_registry.ref_generic());
+            systems.retain(|id, _| !system_registry.is_disabled(id));
 
             {
                 let span = span!(Level::TRACE, "System Derived Events");
@@ -59,13 +67,13 @@ impl KernelSystem for StartNonBlockingProcessor {
                 for &id in systems.keys() {
                     let event_id = id.clone().into_id();
                     event!(Level::TRACE, event=?event_id, "New Event");
-                    next_events.insert(event_id);
+                    next_events.emit(event_id, Some(id.clone()));
                 }
             }
 
             event!(Level::DEBUG, "Executing");
 
-            let runtime = memory.resolve::<Shared<Arc<tokio::runtime::Runtime>>>(
+            let executor = memory.resolve::<Shared<Executor>>(
                 Some(&kernel_program_id), 
                 None, 
                 None, 
@@ -78,9 +86,12 @@ impl KernelSystem for StartNonBlockingProcessor {
             ) = Processor::execute_non_blocking(
                 &memory,
                 systems,
-                &runtime
+                &executor
             ).await;
 
+            drop(system_registry);
+            Processor::remove_spent(&memory, memory.resolve::<Unique<BackgroundProcessorSystemRegistry>>(None, None, None, None).unwrap().unwrap().ref_mut_generic());
+
             let mut async_join_handles = memory.resolve::<Unique<AsyncJoinHandles>>(Some(&kernel_program_id), None, None, Some(&kernel_program_key)).unwrap().unwrap();
             let mut sync_join_handles = memory.resolve::<Unique<SyncJoinHandles>>(Some(&kernel_program_id), None, None, Some(&kernel_program_key)).unwrap().unwrap();
 
@@ -2,7 +2,7 @@ use std::{pin::Pin, sync::Arc};
 
 use tracing::{Level, event, span};
 
-use crate::prelude::{KernelSystem, Memory, NextBlockers, NextEvents, Processor, ProgramId, ProgramKey, ReadOnlySystemRegistry, ResourceId, Shared, StateMachine, StoredSystem, SystemEventRegistry, SystemId, SystemMetadata, Unique};
+use crate::prelude::{Executor, KernelSystem, Memory, NextBlockers, NextEvents, Processor, ProgramId, ProgramKey, ReadOnlySystemRegistry, ResourceId, Shared, StateMachine, StoredSystem, SystemEventRegistry, SystemId, SystemMetadata, Unique};
 
 pub struct ReadOnlyProcessor;
 
@@ -42,14 +42,9 @@ impl KernelSystem for ReadOnlyProcessor {
             event!(Level::WARN, "SystemEventRegistry Not Found")
         }
 
-        event!(Level::DEBUG, "Checking Arc<tokio::runtime::Runtime>");
-        if !matches!(memory.contains_resource(Some(kernel_program_id), &ResourceId::from_raw_heap::<Arc<tokio::runtime::Runtime>>(), Some(kernel_program_key)), Some(true)) {
-            event!(Level::WARN, "Arc<tokio::runtime::Runtime> Not Found")
-        }
-        
-        event!(Level::DEBUG, "Checking threadpool::ThreadPool");
-        if !matches!(memory.contains_resource(Some(kernel_program_id), &ResourceId::from_raw_heap::<threadpool::ThreadPool>(), Some(kernel_program_key)), Some(true)) {
-            event!(Level::WARN, "threadpool::ThreadPool Not Found")
+        event!(Level::DEBUG, "Checking Executor");
+        if !matches!(memory.contains_resource(Some(kernel_program_id), &ResourceId::from_raw_heap::<Executor>(), Some(kernel_program_key)), Some(true)) {
+            event!(Level::WARN, "Executor Not Found")
         }
     }
     
@@ -63,14 +58,7 @@ impl KernelSystem for ReadOnlyProcessor {
             
             event!(Level::DEBUG, executing_systems_count=systems.len(), "Executing Systems");
 
-            let runtime = memory.resolve::<Shared<Arc<tokio::runtime::Runtime>>>(
-                Some(&kernel_program_id), 
-                None, 
-                None, 
-                Some(&kernel_program_key)
-            ).unwrap().unwrap();
-
-            let threadpool = memory.resolve::<Shared<threadpool::ThreadPool>>(
+            let executor = memory.resolve::<Shared<Executor>>(
                 Some(&kernel_program_id), 
                 None, 
                 None, 
@@ -82,10 +70,12 @@ impl KernelSystem for ReadOnlyProcessor {
                 &memory, 
                 systems, 
                 system_registry.ref_generic(), 
-                &threadpool, 
-                &runtime
+                &executor
             ).await;
 
+            drop(system_registry);
+            Processor::remove_spent(&memory, memory.resolve::<Unique<ReadOnlySystemRegistry>>(None, None, None, None).unwrap().unwrap().ref_mut_generic());
+
             let mut next_events = memory.resolve::<Unique<NextEvents>>(None, None, None, None).unwrap().unwrap();
             let mut next_blockers = memory.resolve::<Unique<NextBlockers>>(None, None, None, None).unwrap().unwrap();
             
@@ -1,9 +1,10 @@
-use crate::prelude::{Cloned, Injection, Shared, SyncSystem, SystemId};
+use crate::prelude::{Cloned, EventReader, Injection, Shared, SyncSystem, SystemId};
 
 pub trait ReadOnlyInjection: Injection {}
 
 impl<T: 'static> ReadOnlyInjection for Shared<'_, T> {}
 impl<T: Clone + 'static> ReadOnlyInjection for Cloned<T> {}
+impl<T: 'static> ReadOnlyInjection for EventReader<'_, T> {}
 
 pub trait ReadOnlySystem {
     fn check_read_only(&self, source: Option<&SystemId>) -> bool;    
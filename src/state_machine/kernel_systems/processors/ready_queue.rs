@@ -0,0 +1,320 @@
+use std::{collections::VecDeque, sync::{Condvar, Mutex, atomic::{AtomicBool, AtomicUsize, Ordering}}, time::Duration};
+
+/// Work-stealing queue shared between the threads of a single execution.
+/// Each worker owns a local queue; when it runs dry it steals from the back of the others.
+/// Work that can't run yet (i.e. failed to reserve) is deferred until something finishes.
+/// Woken work is pinned to the worker which owns it and is never stolen.
+pub struct ReadyQueue<T> {
+    local_queues: Vec<Mutex<VecDeque<T>>>,
+    woken_queues: Vec<Mutex<VecDeque<T>>>,
+    deferred: Mutex<Vec<T>>,
+    // Work which has not been `complete`d yet (queued, deferred or running)
+    remaining: AtomicUsize,
+    // Work which is currently running (between `start` and `complete`)
+    in_flight: AtomicUsize,
+    aborted: AtomicBool,
+    parking: Mutex<()>,
+    wakeup: Condvar,
+}
+
+impl<T> ReadyQueue<T> {
+    /// How long an idle worker waits before retrying deferred work when nothing else is running
+    pub const DEFERRED_RETRY_INTERVAL: Duration = Duration::from_millis(1);
+
+    pub fn new(workers: usize, remaining: usize) -> Self {
+        Self {
+            local_queues: (0..workers.max(1)).map(|_| Mutex::new(VecDeque::new())).collect(),
+            woken_queues: (0..workers.max(1)).map(|_| Mutex::new(VecDeque::new())).collect(),
+            deferred: Mutex::new(Vec::new()),
+            remaining: AtomicUsize::new(remaining),
+            in_flight: AtomicUsize::new(0),
+            aborted: AtomicBool::new(false),
+            parking: Mutex::new(()),
+            wakeup: Condvar::new(),
+        }
+    }
+
+    pub fn workers(&self) -> usize {
+        self.local_queues.len()
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.remaining.load(Ordering::Acquire)
+    }
+
+    /// `true` when all work has completed or the queue was aborted
+    pub fn is_finished(&self) -> bool {
+        self.aborted.load(Ordering::Acquire) || self.remaining() == 0
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        self.aborted.load(Ordering::Acquire)
+    }
+
+    pub fn push(&self, worker: usize, item: T) {
+        self.local_queue(worker).lock().unwrap().push_back(item);
+        self.notify(false);
+    }
+
+    pub fn extend(&self, worker: usize, items: impl IntoIterator<Item = T>) {
+        let mut local_queue = self.local_queue(worker).lock().unwrap();
+        let before = local_queue.len();
+        local_queue.extend(items);
+        let pushed = local_queue.len() - before;
+        drop(local_queue);
+
+        match pushed {
+            0 => (),
+            1 => self.notify(false),
+            _ => self.notify(true),
+        }
+    }
+
+    /// Queues work which only `worker` may pop
+    pub fn wake(&self, worker: usize, item: T) {
+        self.woken_queues[worker % self.workers()].lock().unwrap().push_back(item);
+        // Only `worker` can take it so make sure it is the one woken
+        self.notify(true);
+    }
+
+    /// Pops woken work first, then from the front of `worker`'s queue, otherwise steals from the back of another worker's queue
+    pub fn pop(&self, worker: usize) -> Option<T> {
+        let worker = worker % self.workers();
+        if let Some(item) = self.woken_queues[worker].lock().unwrap().pop_front() {
+            return Some(item);
+        }
+
+        if let Some(item) = self.local_queues[worker].lock().unwrap().pop_front() {
+            return Some(item);
+        }
+
+        (1..self.workers())
+            .map(|offset| (worker + offset) % self.workers())
+            .find_map(|victim| self.local_queues[victim].lock().unwrap().pop_back())
+    }
+
+    /// Holds the item back until `release_deferred` is called
+    pub fn defer(&self, item: T) {
+        self.deferred.lock().unwrap().push(item);
+    }
+
+    /// Moves all deferred work onto `worker`'s queue, returning how many were released
+    pub fn release_deferred(&self, worker: usize) -> usize {
+        let deferred = std::mem::take(&mut *self.deferred.lock().unwrap());
+        let released = deferred.len();
+        self.extend(worker, deferred);
+        released
+    }
+
+    /// Marks a popped item as running
+    pub fn start(&self) {
+        self.in_flight.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Marks a started item as completed
+    pub fn complete(&self) {
+        self.in_flight.fetch_sub(1, Ordering::AcqRel);
+        if self.remaining.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.notify(true);
+        }
+    }
+
+    /// Marks an item which was never started as completed, e.g. it was cancelled
+    pub fn skip(&self) {
+        if self.remaining.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.notify(true);
+        }
+    }
+
+    /// Stops every worker at its next check, used if a worker panics
+    pub fn abort(&self) {
+        self.aborted.store(true, Ordering::Release);
+        self.notify(true);
+    }
+
+    /// Aborts the queue if dropped while panicking
+    pub fn abort_on_panic(&self) -> AbortOnPanic<'_, T> {
+        AbortOnPanic(self)
+    }
+
+    /// Blocks `worker` until there is work for it to pop, the queue finishes or `timeout` elapses
+    pub fn park(&self, worker: usize, timeout: Option<Duration>) {
+        let guard = self.parking.lock().unwrap();
+        if self.is_finished() || self.has_work(worker) {
+            return;
+        }
+
+        // Deferred work only becomes runnable when something completes;
+        // if nothing is running the blocker is outside this execution so retry periodically
+        let retry_deferred = self.in_flight.load(Ordering::Acquire) == 0 && !self.deferred.lock().unwrap().is_empty();
+        let timeout = match (timeout, retry_deferred) {
+            (Some(timeout), true) => Some(timeout.min(Self::DEFERRED_RETRY_INTERVAL)),
+            (None, true) => Some(Self::DEFERRED_RETRY_INTERVAL),
+            (timeout, false) => timeout,
+        };
+
+        let guard = match timeout {
+            Some(timeout) => self.wakeup.wait_timeout(guard, timeout).unwrap().0,
+            None => self.wakeup.wait(guard).unwrap(),
+        };
+        drop(guard);
+
+        if retry_deferred {
+            self.release_deferred(0);
+        }
+    }
+
+    fn has_work(&self, worker: usize) -> bool {
+        !self.woken_queues[worker % self.workers()].lock().unwrap().is_empty()
+            || self.local_queues.iter().any(|local_queue| !local_queue.lock().unwrap().is_empty())
+    }
+
+    fn local_queue(&self, worker: usize) -> &Mutex<VecDeque<T>> {
+        &self.local_queues[worker % self.workers()]
+    }
+
+    fn notify(&self, all: bool) {
+        // Taking the lock orders this after any `park` currently checking for work
+        let _guard = self.parking.lock().unwrap();
+        if all {
+            self.wakeup.notify_all();
+        } else {
+            self.wakeup.notify_one();
+        }
+    }
+}
+
+pub struct AbortOnPanic<'a, T>(&'a ReadyQueue<T>);
+
+impl<T> Drop for AbortOnPanic<'_, T> {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            self.0.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod ready_queue_tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[test]
+    fn pops_local_before_stealing() {
+        let queue = ReadyQueue::new(2, 3);
+        queue.extend(0, [1, 2]);
+        queue.push(1, 3);
+
+        assert_eq!(queue.pop(0), Some(1));
+        assert_eq!(queue.pop(0), Some(2));
+        assert_eq!(queue.pop(0), Some(3));
+        assert_eq!(queue.pop(0), None);
+    }
+
+    #[test]
+    fn steals_from_back() {
+        let queue = ReadyQueue::new(2, 3);
+        queue.extend(0, [1, 2, 3]);
+
+        assert_eq!(queue.pop(1), Some(3));
+        assert_eq!(queue.pop(0), Some(1));
+    }
+
+    #[test]
+    fn woken_not_stolen() {
+        let queue = ReadyQueue::new(2, 2);
+        queue.wake(0, 1);
+        queue.push(0, 2);
+
+        assert_eq!(queue.pop(1), Some(2));
+        assert_eq!(queue.pop(1), None);
+        assert_eq!(queue.pop(0), Some(1));
+    }
+
+    #[test]
+    fn deferred_released() {
+        let queue = ReadyQueue::new(1, 1);
+        queue.defer(1);
+        assert_eq!(queue.pop(0), None);
+
+        assert_eq!(queue.release_deferred(0), 1);
+        assert_eq!(queue.pop(0), Some(1));
+    }
+
+    #[test]
+    fn finishes_after_all_complete() {
+        let queue = ReadyQueue::new(1, 2);
+        queue.extend(0, [1, 2]);
+
+        for _ in 0..2 {
+            assert!(!queue.is_finished());
+            queue.pop(0).unwrap();
+            queue.start();
+            queue.complete();
+        }
+
+        assert!(queue.is_finished());
+    }
+
+    #[test]
+    fn skipped_work_still_retries_deferred() {
+        let queue = ReadyQueue::new(1, 2);
+        queue.defer(1);
+        queue.skip();
+        assert!(!queue.is_finished());
+
+        // Nothing is running so the deferred work is retried rather than waiting for a completion
+        let start = std::time::Instant::now();
+        queue.park(0, Some(Duration::from_secs(5)));
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_eq!(queue.pop(0), Some(1));
+
+        queue.start();
+        queue.complete();
+        assert!(queue.is_finished());
+    }
+
+    #[test]
+    fn parked_workers_wake() {
+        let queue = Arc::new(ReadyQueue::new(2, 1));
+
+        let worker = {
+            let queue = Arc::clone(&queue);
+            std::thread::spawn(move || {
+                let item = loop {
+                    if let Some(item) = queue.pop(1) {
+                        break item;
+                    }
+                    queue.park(1, None);
+                };
+
+                queue.start();
+                queue.complete();
+                item
+            })
+        };
+
+        queue.push(0, 7);
+        assert_eq!(worker.join().unwrap(), 7);
+        assert!(queue.is_finished());
+    }
+
+    #[test]
+    fn abort_wakes_parked() {
+        let queue = Arc::new(ReadyQueue::<u8>::new(1, 1));
+
+        let worker = {
+            let queue = Arc::clone(&queue);
+            std::thread::spawn(move || {
+                while !queue.is_finished() {
+                    queue.park(0, None);
+                }
+            })
+        };
+
+        queue.abort();
+        worker.join().unwrap();
+        assert!(queue.is_aborted());
+    }
+}
@@ -1,25 +1,46 @@
-use std::sync::Mutex;
+use std::{sync::{Arc, Mutex}, time::Instant};
 
-use crate::prelude::{ProgramKey, Memory, ProgramId, ReservationError, System, SystemId, SystemStatus};
+use crate::prelude::{PanicPolicy, ProgramKey, Memory, ProgramId, ReservationError, RunState, System, SystemId, SystemStatus};
 
 #[derive(Debug)]
 pub enum StoredSystemError {
     MissingSystem
 }
 
+/// Rebuilds a system lost to a panic in the background
+pub type SystemFactory = Arc<dyn Fn() -> System + Send + Sync>;
+
 pub struct StoredSystem {
     system: Option<System>,
-    status: Mutex<SystemStatus>
+    factory: Option<SystemFactory>,
+    status: Mutex<SystemStatus>,
+    panics: u32,
+    disabled: bool,
+    run_state: RunState,
 }
 
 impl StoredSystem {
     pub fn new(system: System) -> Self {
         Self {
             system: Some(system),
-            status: Mutex::new(SystemStatus::Ready)
+            factory: None,
+            status: Mutex::new(SystemStatus::Ready),
+            panics: 0,
+            disabled: false,
+            run_state: RunState::default(),
         }
     }
 
+    /// Builds the system, and rebuilds it if it is lost
+    pub fn from_factory(factory: impl Fn() -> System + Send + Sync + 'static) -> Self {
+        Self::new(factory()).with_factory(factory)
+    }
+
+    pub fn with_factory(mut self, factory: impl Fn() -> System + Send + Sync + 'static) -> Self {
+        self.factory = Some(Arc::new(factory));
+        self
+    }
+
     pub fn ok_resources(&self, memory: &Memory, program_id: Option<&ProgramId>, source: Option<&SystemId>, key: Option<&ProgramKey>) -> Result<Option<bool>, StoredSystemError> {
         match self.system.as_ref() {
             Some(system) => Ok(system.ok_resources(memory, program_id, source, key)),
@@ -56,5 +77,52 @@ impl StoredSystem {
     pub fn insert_system(&mut self, system: System) -> Option<System> {
         self.system.replace(system)
     }
+
+    /// Rebuilds a missing system from the factory, false if there is no system nor factory
+    pub fn recover(&mut self) -> bool {
+        if self.system.is_none() {
+            self.system = self.factory.as_ref().map(|factory| factory());
+        }
+
+        self.system.is_some()
+    }
+
+    /// How many times the system has panicked
+    pub fn panics(&self) -> u32 {
+        self.panics
+    }
+
+    /// Returns true if the policy disabled the system
+    pub fn record_panic(&mut self, panic_policy: &PanicPolicy) -> bool {
+        self.panics += 1;
+        if panic_policy.disables(self.panics) {
+            self.disabled = true;
+        }
+
+        self.disabled
+    }
+
+    /// Disabled systems are never selected to run
+    pub fn is_disabled(&self) -> bool {
+        self.disabled
+    }
+
+    /// Re-enables the system and resets its panic count
+    pub fn enable(&mut self) {
+        self.disabled = false;
+        self.panics = 0;
+    }
+
+    pub fn disable(&mut self) {
+        self.disabled = true;
+    }
+
+    pub fn run_state(&self) -> &RunState {
+        &self.run_state
+    }
+
+    pub fn record_run(&mut self, tick: u64, now: Instant) {
+        self.run_state.record(tick, now);
+    }
 }
 
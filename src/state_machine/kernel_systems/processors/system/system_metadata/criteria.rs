@@ -1,24 +1,331 @@
-use std::{collections::HashSet, fmt::Debug};
+use std::{collections::HashSet, fmt::{Debug, Display}, ops::{BitAnd, BitOr, Not}, str::FromStr};
 
-use crate::prelude::EventId;
+use crate::prelude::{BlockerId, CriteriaParseError, CriteriaParser, CurrentBlockers, EventId, IntoResourceCriteria, Memory, ResourceCriteria, ProgramId, ProgramKey, StoredResourceCriteria, SystemId};
 
+/// What a system needs to be true (of the current tick) to run
+pub enum Criteria {
+    /// True when the event is in the current events
+    Event(EventId),
+    /// True when the blocker is in the current blockers
+    Blocked(BlockerId),
+    /// True when every criteria is, so empty is always true
+    All(Vec<Criteria>),
+    /// True when any criteria is, so empty is always false
+    Any(Vec<Criteria>),
+    Not(Box<Criteria>),
+    /// An opaque closure over the current events
+    Custom(Box<dyn Fn(&HashSet<&EventId>) -> bool + Send + Sync>),
+    /// A predicate over read-only injections
+    Resources(StoredResourceCriteria),
+}
 
-pub struct Criteria(Box<dyn Fn(&HashSet<&EventId>) -> bool + Send + Sync>);
+/// Everything criteria may be evaluated against
+pub struct CriteriaContext<'a> {
+    events: &'a HashSet<&'a EventId>,
+    blockers: Option<&'a CurrentBlockers>,
+    memory: Option<&'a Memory>,
+    program_id: Option<&'a ProgramId>,
+    source: Option<&'a SystemId>,
+    key: Option<&'a ProgramKey>,
+}
 
-impl Debug for Criteria {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "System Criteria")
+impl<'a> CriteriaContext<'a> {
+    /// Without blockers `Blocked` is false, without memory `Resources` is false
+    pub fn new(events: &'a HashSet<&'a EventId>) -> Self {
+        Self {
+            events,
+            blockers: None,
+            memory: None,
+            program_id: None,
+            source: None,
+            key: None,
+        }
+    }
+
+    pub fn with_blockers(mut self, blockers: &'a CurrentBlockers) -> Self {
+        self.blockers = Some(blockers);
+        self
+    }
+
+    pub fn with_memory(mut self, memory: &'a Memory, program_id: Option<&'a ProgramId>, source: Option<&'a SystemId>, key: Option<&'a ProgramKey>) -> Self {
+        self.memory = Some(memory);
+        self.program_id = program_id;
+        self.source = source;
+        self.key = key;
+        self
+    }
+
+    pub fn events(&self) -> &HashSet<&'a EventId> {
+        self.events
+    }
+}
+
+impl Default for Criteria {
+    fn default() -> Self {
+        Self::always()
     }
 }
 
 impl Criteria {
     pub fn new(criteria: impl Fn(&HashSet<&EventId>) -> bool + Send + Sync + 'static) -> Self {
-        Self(
-            Box::new(criteria)
-        )
+        Self::Custom(Box::new(criteria))
+    }
+
+    pub fn always() -> Self {
+        Self::All(Vec::new())
+    }
+
+    pub fn never() -> Self {
+        Self::Any(Vec::new())
+    }
+
+    pub fn event(event_id: impl Into<EventId>) -> Self {
+        Self::Event(event_id.into())
+    }
+
+    pub fn blocked(blocker_id: impl Into<BlockerId>) -> Self {
+        Self::Blocked(blocker_id.into())
+    }
+
+    pub fn all(criteria: impl IntoIterator<Item = Criteria>) -> Self {
+        Self::All(criteria.into_iter().collect())
+    }
+
+    pub fn any(criteria: impl IntoIterator<Item = Criteria>) -> Self {
+        Self::Any(criteria.into_iter().collect())
+    }
+
+    /// Criteria only gated on resources, e.g. `Criteria::resources(|health: Shared<Health>| health.0 < 0)`
+    pub fn resources<C, R, I>(criteria: C) -> Self where C: IntoResourceCriteria<I, Criteria = R>, R: ResourceCriteria + 'static {
+        Self::Resources(Box::new(criteria.into_criteria()))
+    }
+
+    /// Also requires the resource predicate, injections must be read-only
+    pub fn with_resources<C, R, I>(self, criteria: C) -> Self where C: IntoResourceCriteria<I, Criteria = R>, R: ResourceCriteria + 'static {
+        self.and(Self::resources(criteria))
+    }
+
+    /// Parses e.g. `"Start & !Paused | Force"`, see [`CriteriaParser`]
+    pub fn parse(source: &str) -> Result<Self, CriteriaParseError> {
+        CriteriaParser::new(source).parse()
+    }
+
+    pub fn and(self, other: Criteria) -> Self {
+        match self {
+            Self::All(mut criteria) => {
+                criteria.push(other);
+                Self::All(criteria)
+            },
+            criteria => Self::All(vec![criteria, other])
+        }
+    }
+
+    pub fn or(self, other: Criteria) -> Self {
+        match self {
+            Self::Any(mut criteria) => {
+                criteria.push(other);
+                Self::Any(criteria)
+            },
+            criteria => Self::Any(vec![criteria, other])
+        }
+    }
+
+    pub fn has_resources(&self) -> bool {
+        match self {
+            Self::Resources(_) => true,
+            Self::All(criteria) | Self::Any(criteria) => criteria.iter().any(Self::has_resources),
+            Self::Not(criteria) => criteria.has_resources(),
+            _ => false
+        }
+    }
+
+    /// The events which can trigger the criteria, i.e. not under a `Not`
+    pub fn events(&self) -> Vec<&EventId> {
+        let mut events = Vec::new();
+        self.collect_events(false, &mut events);
+        events
     }
 
+    fn collect_events<'a>(&'a self, negated: bool, events: &mut Vec<&'a EventId>) {
+        match self {
+            Self::Event(event_id) if !negated => events.push(event_id),
+            Self::All(criteria) | Self::Any(criteria) => criteria.iter().for_each(|criteria| criteria.collect_events(negated, events)),
+            Self::Not(criteria) => criteria.collect_events(!negated, events),
+            _ => ()
+        }
+    }
+
+    /// Only against the events, so `Blocked` & `Resources` are false
     pub fn test(&self, events: &HashSet<&EventId>) -> bool {
-        self.0(events)
+        self.evaluate(&CriteriaContext::new(events))
+    }
+
+    pub fn evaluate(&self, context: &CriteriaContext) -> bool {
+        match self {
+            Self::Event(event_id) => context.events.contains(event_id),
+            Self::Blocked(blocker_id) => context.blockers.is_some_and(|blockers| blockers.blocks(blocker_id)),
+            Self::All(criteria) => criteria.iter().all(|criteria| criteria.evaluate(context)),
+            Self::Any(criteria) => criteria.iter().any(|criteria| criteria.evaluate(context)),
+            Self::Not(criteria) => !criteria.evaluate(context),
+            Self::Custom(criteria) => criteria(context.events),
+            Self::Resources(resources) => {
+                let Some(memory) = context.memory else {
+                    return false;
+                };
+
+                let (program_id, source, key) = (context.program_id, context.source, context.key);
+
+                resources.ok_resources(memory, program_id, source, key).is_some_and(|ok| ok)
+                    && resources.ok_accesses(memory, program_id, source, key).is_some_and(|ok| ok)
+                    && resources.test(memory, program_id, source, key).is_some_and(|ok| ok)
+            }
+        }
     }
-}
\ No newline at end of file
+}
+
+impl FromStr for Criteria {
+    type Err = CriteriaParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl BitAnd for Criteria {
+    type Output = Criteria;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.and(rhs)
+    }
+}
+
+impl BitOr for Criteria {
+    type Output = Criteria;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.or(rhs)
+    }
+}
+
+impl Not for Criteria {
+    type Output = Criteria;
+
+    fn not(self) -> Self::Output {
+        Self::Not(Box::new(self))
+    }
+}
+
+impl Display for Criteria {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Always parenthesise nested combinators so the output parses back the same
+        fn nested(f: &mut std::fmt::Formatter<'_>, criteria: &Criteria) -> std::fmt::Result {
+            match criteria {
+                Criteria::All(inner) | Criteria::Any(inner) if inner.len() > 1 => write!(f, "({criteria})"),
+                criteria => write!(f, "{criteria}")
+            }
+        }
+
+        fn join(f: &mut std::fmt::Formatter<'_>, criteria: &[Criteria], separator: &str) -> std::fmt::Result {
+            for (i, criteria) in criteria.iter().enumerate() {
+                if i > 0 {
+                    write!(f, " {separator} ")?;
+                }
+                nested(f, criteria)?;
+            }
+            Ok(())
+        }
+
+        match self {
+            Self::Event(event_id) => write_name(f, &event_id.get_id().to_string()),
+            Self::Blocked(blocker_id) => {
+                write!(f, "blocked(")?;
+                write_name(f, &blocker_id.to_string())?;
+                write!(f, ")")
+            },
+            Self::All(criteria) if criteria.is_empty() => write!(f, "true"),
+            Self::Any(criteria) if criteria.is_empty() => write!(f, "false"),
+            Self::All(criteria) => join(f, criteria, "&"),
+            Self::Any(criteria) => join(f, criteria, "|"),
+            Self::Not(criteria) => {
+                write!(f, "!")?;
+                match criteria.as_ref() {
+                    Self::All(inner) | Self::Any(inner) if inner.len() > 1 => write!(f, "({criteria})"),
+                    criteria => write!(f, "{criteria}")
+                }
+            },
+            Self::Custom(_) => write!(f, "<custom>"),
+            Self::Resources(_) => write!(f, "<resources>"),
+        }
+    }
+}
+
+impl Debug for Criteria {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Criteria({self})")
+    }
+}
+
+/// Bare names are written as-is, anything the parser would split on is quoted
+fn write_name(f: &mut std::fmt::Formatter<'_>, name: &str) -> std::fmt::Result {
+    if CriteriaParser::is_bare_name(name) {
+        write!(f, "{name}")
+    } else {
+        write!(f, "\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+#[cfg(test)]
+mod criteria_tests {
+    use crate::prelude::Blocker;
+
+    use super::*;
+
+    fn events<'a>(events: &'a [EventId]) -> HashSet<&'a EventId> {
+        events.iter().collect()
+    }
+
+    #[test]
+    fn combinators() {
+        let criteria = Criteria::event("Start") & !Criteria::event("Paused") | Criteria::event("Force");
+
+        assert!(criteria.test(&events(&[EventId::from("Start")])));
+        assert!(!criteria.test(&events(&[EventId::from("Start"), EventId::from("Paused")])));
+        assert!(criteria.test(&events(&[EventId::from("Paused"), EventId::from("Force")])));
+        assert!(!criteria.test(&events(&[])));
+
+        assert!(Criteria::always().test(&events(&[])));
+        assert!(!Criteria::never().test(&events(&[])));
+    }
+
+    #[test]
+    fn triggering_events() {
+        let criteria = Criteria::parse("(Start & !Paused) | !!Force | blocked(Foo)").unwrap();
+        assert_eq!(criteria.events(), vec![&EventId::from("Start"), &EventId::from("Force")]);
+    }
+
+    #[test]
+    fn blocked() {
+        let mut blockers = CurrentBlockers::default();
+        blockers.insert(BlockerId::from("Foo"), Blocker::system("Foo"), None, 0);
+
+        let no_events = events(&[]);
+        let context = CriteriaContext::new(&no_events).with_blockers(&blockers);
+
+        assert!(Criteria::blocked("Foo").evaluate(&context));
+        assert!(!Criteria::blocked("Bar").evaluate(&context));
+        // No blockers in the context
+        assert!(!Criteria::blocked("Foo").test(&no_events));
+    }
+
+    #[test]
+    fn displays() {
+        let criteria = Criteria::event("Start") & !Criteria::event("Paused") | Criteria::event("Force");
+        assert_eq!(criteria.to_string(), "(Start & !Paused) | Force");
+        assert_eq!(format!("{criteria:?}"), "Criteria((Start & !Paused) | Force)");
+
+        let criteria = !(Criteria::event("Foo Bar") | Criteria::blocked("Baz"));
+        assert_eq!(criteria.to_string(), "!(\"Foo Bar\" | blocked(Baz))");
+
+        assert_eq!(Criteria::new(|_| true).to_string(), "<custom>");
+    }
+}
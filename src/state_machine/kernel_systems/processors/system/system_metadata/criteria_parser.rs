@@ -0,0 +1,232 @@
+use std::{iter::Peekable, str::CharIndices};
+
+use crate::prelude::Criteria;
+
+#[derive(Debug, PartialEq)]
+pub enum CriteriaParseError {
+    UnexpectedEnd,
+    /// (position, found)
+    UnexpectedChar(usize, char),
+    /// Position of the opening quote
+    UnterminatedQuote(usize),
+    /// Position where a name was expected
+    ExpectedName(usize),
+}
+
+/// Parses criteria expressions, loosest binding first:
+/// - `a | b` any
+/// - `a & b` all
+/// - `!a` not
+/// - `(a)`, `blocked(a)`, `true`, `false`
+/// - names are bare (`Foo-TimedOut`) or quoted (`"Foo Bar"`, with `\"` & `\\` escapes)
+pub struct CriteriaParser<'a> {
+    source: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> CriteriaParser<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            chars: source.char_indices().peekable(),
+        }
+    }
+
+    pub fn is_bare_char(c: char) -> bool {
+        !c.is_whitespace() && !matches!(c, '&' | '|' | '!' | '(' | ')' | '"' | '\\')
+    }
+
+    /// Whether `name` would be read back as a single event name without quotes
+    pub fn is_bare_name(name: &str) -> bool {
+        !name.is_empty() && name.chars().all(Self::is_bare_char) && !matches!(name, "true" | "false" | "blocked")
+    }
+
+    pub fn parse(mut self) -> Result<Criteria, CriteriaParseError> {
+        let criteria = self.parse_any()?;
+
+        self.skip_whitespace();
+        match self.chars.next() {
+            None => Ok(criteria),
+            Some((position, c)) => Err(CriteriaParseError::UnexpectedChar(position, c)),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+
+    fn peek(&mut self) -> Option<(usize, char)> {
+        self.skip_whitespace();
+        self.chars.peek().copied()
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), CriteriaParseError> {
+        match self.peek() {
+            Some((_, c)) if c == expected => {
+                self.chars.next();
+                Ok(())
+            },
+            Some((position, c)) => Err(CriteriaParseError::UnexpectedChar(position, c)),
+            None => Err(CriteriaParseError::UnexpectedEnd),
+        }
+    }
+
+    fn parse_any(&mut self) -> Result<Criteria, CriteriaParseError> {
+        let mut criteria = vec![self.parse_all()?];
+
+        while let Some((_, '|')) = self.peek() {
+            self.chars.next();
+            criteria.push(self.parse_all()?);
+        }
+
+        Ok(if criteria.len() == 1 { criteria.pop().unwrap() } else { Criteria::Any(criteria) })
+    }
+
+    fn parse_all(&mut self) -> Result<Criteria, CriteriaParseError> {
+        let mut criteria = vec![self.parse_not()?];
+
+        while let Some((_, '&')) = self.peek() {
+            self.chars.next();
+            criteria.push(self.parse_not()?);
+        }
+
+        Ok(if criteria.len() == 1 { criteria.pop().unwrap() } else { Criteria::All(criteria) })
+    }
+
+    fn parse_not(&mut self) -> Result<Criteria, CriteriaParseError> {
+        if let Some((_, '!')) = self.peek() {
+            self.chars.next();
+            return Ok(!self.parse_not()?);
+        }
+
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Criteria, CriteriaParseError> {
+        match self.peek() {
+            None => Err(CriteriaParseError::UnexpectedEnd),
+            Some((_, '(')) => {
+                self.chars.next();
+                let criteria = self.parse_any()?;
+                self.expect(')')?;
+                Ok(criteria)
+            },
+            Some((_, '"')) => Ok(Criteria::event(self.parse_quoted()?)),
+            Some((_, c)) if Self::is_bare_char(c) => {
+                let name = self.parse_bare();
+                match name {
+                    "true" => Ok(Criteria::always()),
+                    "false" => Ok(Criteria::never()),
+                    "blocked" => {
+                        self.expect('(')?;
+                        let name = self.parse_name()?;
+                        self.expect(')')?;
+                        Ok(Criteria::blocked(name))
+                    },
+                    name => Ok(Criteria::event(name)),
+                }
+            },
+            Some((position, c)) => Err(CriteriaParseError::UnexpectedChar(position, c)),
+        }
+    }
+
+    fn parse_name(&mut self) -> Result<String, CriteriaParseError> {
+        match self.peek() {
+            None => Err(CriteriaParseError::UnexpectedEnd),
+            Some((_, '"')) => self.parse_quoted(),
+            Some((_, c)) if Self::is_bare_char(c) => Ok(self.parse_bare().to_string()),
+            Some((position, _)) => Err(CriteriaParseError::ExpectedName(position)),
+        }
+    }
+
+    fn parse_bare(&mut self) -> &'a str {
+        let start = self.chars.peek().map_or(self.source.len(), |&(position, _)| position);
+        let mut end = start;
+
+        while let Some((position, c)) = self.chars.next_if(|&(_, c)| Self::is_bare_char(c)) {
+            end = position + c.len_utf8();
+        }
+
+        &self.source[start..end]
+    }
+
+    fn parse_quoted(&mut self) -> Result<String, CriteriaParseError> {
+        let (start, _) = self.chars.next().ok_or(CriteriaParseError::UnexpectedEnd)?;
+        let mut name = String::new();
+
+        loop {
+            match self.chars.next() {
+                None => return Err(CriteriaParseError::UnterminatedQuote(start)),
+                Some((_, '"')) => return Ok(name),
+                Some((_, '\\')) => match self.chars.next() {
+                    None => return Err(CriteriaParseError::UnterminatedQuote(start)),
+                    Some((_, c)) => name.push(c),
+                },
+                Some((_, c)) => name.push(c),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod criteria_parser_tests {
+    use std::collections::HashSet;
+
+    use crate::prelude::{Blocker, CurrentBlockers, BlockerId, CriteriaContext, EventId};
+
+    use super::*;
+
+    fn test(criteria: &Criteria, events: &[&str]) -> bool {
+        let events = events.iter().map(|&event| EventId::from(event)).collect::<Vec<_>>();
+        criteria.test(&events.iter().collect::<HashSet<_>>())
+    }
+
+    #[test]
+    fn precedence() {
+        let criteria = Criteria::parse("Start & !Paused | Force").unwrap();
+        assert_eq!(criteria.to_string(), "(Start & !Paused) | Force");
+
+        assert!(test(&criteria, &["Start"]));
+        assert!(!test(&criteria, &["Start", "Paused"]));
+        assert!(test(&criteria, &["Paused", "Force"]));
+
+        let criteria = Criteria::parse("Start & !(Paused | Stopped)").unwrap();
+        assert!(test(&criteria, &["Start"]));
+        assert!(!test(&criteria, &["Start", "Stopped"]));
+
+        assert!(test(&Criteria::parse("!!Foo").unwrap(), &["Foo"]));
+        assert!(test(&Criteria::parse("true").unwrap(), &[]));
+        assert!(!test(&Criteria::parse("false | \"true\"").unwrap(), &[]));
+    }
+
+    #[test]
+    fn names() {
+        let criteria = Criteria::parse("\"Foo Bar\" & Baz-TimedOut & \"say \\\"hi\\\"\"").unwrap();
+        assert!(test(&criteria, &["Foo Bar", "Baz-TimedOut", "say \"hi\""]));
+
+        let mut blockers = CurrentBlockers::default();
+        blockers.insert(BlockerId::from("Foo Bar"), Blocker::system("Foo Bar"), None, 0);
+
+        let criteria = Criteria::parse("blocked(\"Foo Bar\") & !blocked(Baz)").unwrap();
+        let events = HashSet::new();
+        assert!(criteria.evaluate(&CriteriaContext::new(&events).with_blockers(&blockers)));
+    }
+
+    #[test]
+    fn round_trips() {
+        for source in ["(Start & !Paused) | Force", "!(\"Foo Bar\" | blocked(Baz))", "A & (B | C) & !D", "true", "\"false\""] {
+            assert_eq!(Criteria::parse(source).unwrap().to_string(), source);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Criteria::parse("").unwrap_err(), CriteriaParseError::UnexpectedEnd);
+        assert_eq!(Criteria::parse("Foo &").unwrap_err(), CriteriaParseError::UnexpectedEnd);
+        assert_eq!(Criteria::parse("(Foo").unwrap_err(), CriteriaParseError::UnexpectedEnd);
+        assert_eq!(Criteria::parse("Foo)").unwrap_err(), CriteriaParseError::UnexpectedChar(3, ')'));
+        assert_eq!(Criteria::parse("Foo & \"Bar").unwrap_err(), CriteriaParseError::UnterminatedQuote(6));
+        assert_eq!(Criteria::parse("blocked(|)").unwrap_err(), CriteriaParseError::ExpectedName(8));
+        assert_eq!("Foo Bar".parse::<Criteria>().unwrap_err(), CriteriaParseError::UnexpectedChar(4, 'B'));
+    }
+}
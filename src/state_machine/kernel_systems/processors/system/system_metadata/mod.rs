@@ -1,15 +1,21 @@
-use std::collections::{HashMap, HashSet};
+use std::{collections::{HashMap, HashSet}, time::Duration};
 
-use crate::{prelude::{Criteria, EventId, Memory, SchedulerOrdering, StoredSystem, SystemCell, SystemId, Unique}, state_machine::kernel_systems::processors::system::system_metadata::stored_system_metadata::StoredSystemMetadata};
+use crate::{prelude::{Criteria, CriteriaContext, EventId, Memory, PanicPolicy, RunConditions, SchedulerOrdering, StoredSystem, SystemCell, SystemId, Unique}, state_machine::kernel_systems::processors::system::system_metadata::stored_system_metadata::StoredSystemMetadata};
 
 pub mod criteria;
+pub mod criteria_parser;
+pub mod resource_criteria;
 pub mod stored_system_metadata;
+pub mod panic_policy;
+pub mod run_conditions;
 
 #[derive(Debug)]
 pub struct SystemMetadata {
     stored_system_metadata: StoredSystemMetadata,
     criteria: Criteria,
     ordering: SchedulerOrdering,
+    panic_policy: PanicPolicy,
+    run_conditions: RunConditions,
 }
 
 impl SystemMetadata {
@@ -21,7 +27,9 @@ impl SystemMetadata {
         Self {
             stored_system_metadata,
             criteria,
-            ordering
+            ordering,
+            panic_policy: PanicPolicy::default(),
+            run_conditions: RunConditions::default()
         }
     }
 
@@ -29,6 +37,14 @@ impl SystemMetadata {
         self.criteria.test(events)
     }
 
+    pub fn evaluate(&self, context: &CriteriaContext) -> bool {
+        self.criteria.evaluate(context)
+    }
+
+    pub fn criteria(&self) -> &Criteria {
+        &self.criteria
+    }
+
     pub fn stored_system_metadata(&self) -> &StoredSystemMetadata {
         &self.stored_system_metadata
     }
@@ -44,6 +60,31 @@ impl SystemMetadata {
     pub fn replace_criteria(&mut self, criteria: Criteria) {
         self.criteria = criteria;
     }
+
+    pub fn timeout(&self) -> &Option<Duration> {
+        self.stored_system_metadata.timeout()
+    }
+
+    /// Async systems running longer than `timeout` are cancelled and return `SystemResult::TimedOut`
+    pub fn replace_timeout(&mut self, timeout: Option<Duration>) {
+        self.stored_system_metadata.replace_timeout(timeout);
+    }
+
+    pub fn panic_policy(&self) -> &PanicPolicy {
+        &self.panic_policy
+    }
+
+    pub fn replace_panic_policy(&mut self, panic_policy: PanicPolicy) {
+        self.panic_policy = panic_policy;
+    }
+
+    pub fn run_conditions(&self) -> &RunConditions {
+        &self.run_conditions
+    }
+
+    pub fn replace_run_conditions(&mut self, run_conditions: RunConditions) {
+        self.run_conditions = run_conditions;
+    }
 }
 
 #[derive(Debug, Default)]
@@ -62,6 +103,14 @@ impl SystemRegistry {
         self.0.get(id)
     }
 
+    pub fn get_mut(&mut self, id: &SystemId) -> Option<&mut SystemMetadata> {
+        self.0.get_mut(id)
+    }
+
+    pub fn remove(&mut self, id: &SystemId) -> Option<SystemMetadata> {
+        self.0.remove(id)
+    }
+
     pub fn into_system_cell_map(&self, memory: &Memory) -> HashMap<SystemId, SystemCell> {
         self.read()
             .map(|(system_id, system_metadata)| (system_id.clone(), system_metadata.stored_system_metadata().clone()))
@@ -0,0 +1,23 @@
+/// What happens to a system after it panics.
+/// The panic is always converted into `SystemResult::Error` and its reservations released
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PanicPolicy {
+    /// Keep the system enabled, it runs again when next triggered
+    #[default]
+    Retry,
+    /// Disable the system after its first panic
+    Disable,
+    /// Disable the system once it has panicked this many times
+    Quarantine(u32),
+}
+
+impl PanicPolicy {
+    /// If a system which has panicked `panics` times should be disabled
+    pub fn disables(&self, panics: u32) -> bool {
+        match self {
+            PanicPolicy::Retry => false,
+            PanicPolicy::Disable => true,
+            PanicPolicy::Quarantine(limit) => panics >= *limit,
+        }
+    }
+}
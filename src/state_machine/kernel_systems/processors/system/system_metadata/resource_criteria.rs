@@ -0,0 +1,118 @@
+use std::marker::PhantomData;
+
+use crate::prelude::{Injection, Memory, ProgramId, ProgramKey, ReadOnlyInjection, SystemId};
+
+pub type StoredResourceCriteria = Box<dyn ResourceCriteria>;
+
+/// A predicate over read-only injections, evaluated before a system is scheduled
+pub trait ResourceCriteria: Send + Sync {
+    fn ok_resources(&self, memory: &Memory, program_id: Option<&ProgramId>, source: Option<&SystemId>, key: Option<&ProgramKey>) -> Option<bool>;
+    fn ok_accesses(&self, memory: &Memory, program_id: Option<&ProgramId>, source: Option<&SystemId>, key: Option<&ProgramKey>) -> Option<bool>;
+
+    /// None if any injection fails to resolve
+    fn test(&self, memory: &Memory, program_id: Option<&ProgramId>, source: Option<&SystemId>, key: Option<&ProgramKey>) -> Option<bool>;
+}
+
+pub struct FunctionCriteria<Input, F> {
+    f: F,
+    marker: PhantomData<fn() -> Input>
+}
+
+pub trait IntoResourceCriteria<Input> {
+    type Criteria: ResourceCriteria;
+
+    fn into_criteria(self) -> Self::Criteria;
+}
+
+macro_rules! impl_resource_criteria {
+    (
+        $($params:ident),*
+    ) => {
+        #[allow(unused_variables)]
+        #[allow(non_snake_case)]
+        impl<F, $($params: ReadOnlyInjection),*> ResourceCriteria for FunctionCriteria<($($params,)*), F>
+            where F: Send + Sync,
+            for <'a, 'b> &'a F:
+                Fn($($params),*) -> bool +
+                Fn($(<$params as Injection>::Item<'b>),*) -> bool
+        {
+            fn ok_resources(
+                &self,
+                memory: &Memory,
+                program_id: Option<&ProgramId>,
+                source: Option<&SystemId>,
+                key: Option<&ProgramKey>
+            ) -> Option<bool> {
+                Some(true $(&& memory.ok_resources::<$params>(program_id, source, None, key)?)*)
+            }
+
+            fn ok_accesses(
+                &self,
+                memory: &Memory,
+                program_id: Option<&ProgramId>,
+                source: Option<&SystemId>,
+                key: Option<&ProgramKey>
+            ) -> Option<bool> {
+                Some(true $(&& memory.ok_accesses::<$params>(program_id, source, None, key)?)*)
+            }
+
+            fn test(
+                &self,
+                memory: &Memory,
+                program_id: Option<&ProgramId>,
+                source: Option<&SystemId>,
+                key: Option<&ProgramKey>
+            ) -> Option<bool> {
+                // One parameter per resolved injection
+                #[allow(clippy::too_many_arguments)]
+                fn call_inner<$($params),*>(
+                    f: impl Fn($($params),*) -> bool,
+                    $($params: $params),*
+                ) -> bool {
+                    f($($params),*)
+                }
+
+                $(
+                    let $params = memory.resolve::<$params>(
+                        program_id,
+                        None,
+                        source,
+                        key
+                    )?.ok()?;
+                )*
+
+                Some(call_inner(&self.f, $($params),*))
+            }
+        }
+
+        impl<F, $($params: ReadOnlyInjection),*> IntoResourceCriteria<($($params,)*)> for F
+            where
+                F: Send + Sync,
+                for<'a, 'b> &'a F:
+                    Fn($($params),*) -> bool +
+                    Fn($(<$params as Injection>::Item<'b>),*) -> bool
+        {
+            type Criteria = FunctionCriteria<($($params,)*), Self>;
+
+            fn into_criteria(self) -> Self::Criteria {
+                FunctionCriteria {
+                    f: self,
+                    marker: Default::default(),
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_all_resource_criteria {
+    () => {
+        impl_resource_criteria!();
+    };
+
+    ($first:ident $(, $rest:ident)*) => {
+        impl_resource_criteria!($first $(, $rest)*);
+        impl_all_resource_criteria!($($rest),*);
+    };
+}
+
+impl_all_resource_criteria!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
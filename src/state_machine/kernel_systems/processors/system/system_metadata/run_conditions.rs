@@ -0,0 +1,171 @@
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cooldown {
+    /// Skips this many ticks after running
+    Ticks(u64),
+    /// Skips until this much time has passed since running
+    Duration(Duration),
+}
+
+/// Scheduling limits checked alongside `Criteria`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunConditions {
+    every_ticks: Option<u64>,
+    max_runs: Option<u64>,
+    cooldown: Option<Cooldown>,
+    once: bool,
+}
+
+impl RunConditions {
+    /// Only runs on ticks which are a multiple of `ticks`
+    pub fn with_every_ticks(mut self, ticks: u64) -> Self {
+        self.every_ticks = Some(ticks.max(1));
+        self
+    }
+
+    pub fn with_max_runs(mut self, runs: u64) -> Self {
+        self.max_runs = Some(runs);
+        self
+    }
+
+    pub fn with_cooldown(mut self, cooldown: Cooldown) -> Self {
+        self.cooldown = Some(cooldown);
+        self
+    }
+
+    /// Runs once then is unregistered
+    pub fn once(mut self) -> Self {
+        self.once = true;
+        self
+    }
+
+    pub fn every_ticks(&self) -> Option<u64> {
+        self.every_ticks
+    }
+
+    pub fn max_runs(&self) -> Option<u64> {
+        self.max_runs
+    }
+
+    pub fn cooldown(&self) -> Option<Cooldown> {
+        self.cooldown
+    }
+
+    pub fn is_once(&self) -> bool {
+        self.once
+    }
+
+    pub fn allows(&self, run_state: &RunState, current_tick: u64, now: Instant) -> bool {
+        if self.is_spent(run_state) {
+            return false;
+        }
+
+        if let Some(every_ticks) = self.every_ticks && !current_tick.is_multiple_of(every_ticks) {
+            return false;
+        }
+
+        match self.cooldown {
+            Some(Cooldown::Ticks(ticks)) => run_state.last_tick.is_none_or(|last_tick| current_tick > last_tick + ticks),
+            Some(Cooldown::Duration(duration)) => run_state.last_instant.is_none_or(|last_instant| now.duration_since(last_instant) >= duration),
+            None => true
+        }
+    }
+
+    /// True if the system can never run again
+    pub fn is_spent(&self, run_state: &RunState) -> bool {
+        (self.once && run_state.runs > 0) || self.max_runs.is_some_and(|max_runs| run_state.runs >= max_runs)
+    }
+}
+
+/// Per system counters for `RunConditions`, persisted in the `StoredSystem`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunState {
+    runs: u64,
+    last_tick: Option<u64>,
+    last_instant: Option<Instant>,
+}
+
+impl RunState {
+    pub fn runs(&self) -> u64 {
+        self.runs
+    }
+
+    pub fn last_tick(&self) -> Option<u64> {
+        self.last_tick
+    }
+
+    pub fn last_instant(&self) -> Option<Instant> {
+        self.last_instant
+    }
+
+    pub fn record(&mut self, tick: u64, now: Instant) {
+        self.runs += 1;
+        self.last_tick = Some(tick);
+        self.last_instant = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod run_conditions_tests {
+    use super::*;
+
+    #[test]
+    fn every_ticks() {
+        let run_conditions = RunConditions::default().with_every_ticks(3);
+        let run_state = RunState::default();
+        let now = Instant::now();
+
+        let allowed = (0..7).filter(|&tick| run_conditions.allows(&run_state, tick, now)).collect::<Vec<_>>();
+        assert_eq!(allowed, vec![0, 3, 6]);
+    }
+
+    #[test]
+    fn max_runs() {
+        let run_conditions = RunConditions::default().with_max_runs(2);
+        let mut run_state = RunState::default();
+        let now = Instant::now();
+
+        assert!(run_conditions.allows(&run_state, 0, now));
+        run_state.record(0, now);
+        assert!(run_conditions.allows(&run_state, 1, now));
+        run_state.record(1, now);
+        assert!(!run_conditions.allows(&run_state, 2, now));
+        assert!(run_conditions.is_spent(&run_state));
+    }
+
+    #[test]
+    fn cooldown_ticks() {
+        let run_conditions = RunConditions::default().with_cooldown(Cooldown::Ticks(2));
+        let mut run_state = RunState::default();
+        let now = Instant::now();
+
+        run_state.record(5, now);
+        assert!(!run_conditions.allows(&run_state, 6, now));
+        assert!(!run_conditions.allows(&run_state, 7, now));
+        assert!(run_conditions.allows(&run_state, 8, now));
+    }
+
+    #[test]
+    fn cooldown_duration() {
+        let run_conditions = RunConditions::default().with_cooldown(Cooldown::Duration(Duration::from_secs(1)));
+        let mut run_state = RunState::default();
+        let now = Instant::now();
+
+        run_state.record(0, now);
+        assert!(!run_conditions.allows(&run_state, 1, now + Duration::from_millis(500)));
+        assert!(run_conditions.allows(&run_state, 2, now + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn once() {
+        let run_conditions = RunConditions::default().once();
+        let mut run_state = RunState::default();
+        let now = Instant::now();
+
+        assert!(!run_conditions.is_spent(&run_state));
+        run_state.record(0, now);
+        assert!(run_conditions.is_spent(&run_state));
+        assert!(!run_conditions.allows(&run_state, 1, now));
+    }
+}
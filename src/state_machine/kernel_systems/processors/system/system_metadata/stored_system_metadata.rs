@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use crate::prelude::{ProgramId, ProgramKey, ResourceId};
 
 // Since criteria cant be cloned
@@ -6,12 +8,13 @@ pub struct StoredSystemMetadata {
     resource_id: ResourceId,
     program_id: Option<ProgramId>,
     key: Option<ProgramKey>,
+    timeout: Option<Duration>,
 }
 
 impl StoredSystemMetadata {
     pub fn new(resource_id: ResourceId, program_id: Option<ProgramId>, key: Option<ProgramKey>) -> Self {
         Self {
-            resource_id, program_id, key
+            resource_id, program_id, key, timeout: None
         }
     }
 
@@ -26,4 +29,13 @@ impl StoredSystemMetadata {
     pub fn key(&self) -> &Option<ProgramKey> {
         &self.key
     }
+
+    /// How long an async system may run before it is cancelled
+    pub fn timeout(&self) -> &Option<Duration> {
+        &self.timeout
+    }
+
+    pub fn replace_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
 }
@@ -1,21 +1,31 @@
+use std::time::Duration;
+
 use tracing::{Level, event};
 
-use crate::prelude::{BlockerId, EventId, NextBlockers, NextEvents, SystemEventRegistry, SystemId};
+use crate::prelude::{Blocker, BlockerId, EventId, NextBlockers, NextEvents, SystemEventRegistry, SystemId};
 
 
 #[derive(Debug)]
 pub enum SystemEvent {
     NoEvent,
     WithEvent(EventId),
-    WithBlocker(BlockerId)
+    /// Blocks the system named by the blocker for 1 tick
+    WithBlocker(BlockerId),
+    Block(BlockerId, Blocker),
+    Unblock(BlockerId),
 }
 
 impl SystemEvent {
     pub fn act(self, system_id: &SystemId, next_events: &mut NextEvents, next_blockers: &mut NextBlockers) {
         match self {
             SystemEvent::NoEvent => { next_events.remove(&EventId::from(system_id.clone().into_id())); },
-            SystemEvent::WithEvent(event) => { next_events.insert(event); },
-            SystemEvent::WithBlocker(blocker) => { next_blockers.insert(blocker); },
+            SystemEvent::WithEvent(event) => { next_events.emit(event, Some(system_id.clone())); },
+            SystemEvent::WithBlocker(blocker) => {
+                let blocks = SystemId::from(blocker.clone().into_id());
+                next_blockers.insert_blocker(blocker, Blocker::system(blocks), Some(system_id.clone()));
+            },
+            SystemEvent::Block(blocker_id, blocker) => { next_blockers.insert_blocker(blocker_id, blocker, Some(system_id.clone())); },
+            SystemEvent::Unblock(blocker_id) => { next_blockers.unblock(blocker_id); },
         }
     }
 }
@@ -26,6 +36,10 @@ pub enum SystemResult {
     Event(SystemEvent),
 
     Error(anyhow::Error),
+    /// The system was cancelled after running for longer than its timeout
+    TimedOut(Duration),
+    /// The background system was cancelled, see `BackgroundTasks::cancel`
+    Cancelled,
     #[deprecated(note = "Use SystemResult::Events")]
     // true => inserts events via a registry
     // false => removes the current system as an event (SystemEvent::NoEvent)
@@ -50,12 +64,25 @@ impl SystemResult {
                     system_event.act(&system_id, &mut next_events, &mut next_blockers);
                 }
             },
-            SystemResult::Error(error) => event!(parent: parent_span, Level::ERROR, system_result_error=%error),
+            SystemResult::Error(error) => {
+                event!(parent: parent_span, Level::ERROR, system_result_error=%error);
+                next_events.emit(EventId::errored(system_id), Some(system_id.clone()));
+            },
+            SystemResult::TimedOut(timeout) => {
+                event!(parent: parent_span, Level::WARN, timeout=?timeout, "System Timed Out");
+                next_events.emit(EventId::timed_out(system_id), Some(system_id.clone()));
+            },
+            SystemResult::Cancelled => {
+                event!(parent: parent_span, Level::DEBUG, "System Cancelled");
+                next_events.emit(EventId::cancelled(system_id), Some(system_id.clone()));
+            },
             #[allow(deprecated)]
             SystemResult::Conditional(bool) => {
                 if bool {
                     if let Some(events) = system_event_registry.get(&system_id) {
-                        next_events.extend(events.clone().into_iter());
+                        for event in events {
+                            next_events.emit(event.clone(), Some(system_id.clone()));
+                        }
                     } else {
                         event!(parent: parent_span, Level::WARN, "No Events in SystemEventRegistry");
                     }
@@ -1,7 +1,35 @@
-use std::task::Wake;
+use std::{sync::Arc, task::Wake};
 
-pub struct DummyWaker;
+use crate::prelude::{ReadyQueue, SystemId};
 
-impl Wake for DummyWaker {
-    fn wake(self: std::sync::Arc<Self>) {}
-}
\ No newline at end of file
+/// Work for a processor thread
+#[derive(Debug, Clone, PartialEq)]
+pub enum Task {
+    /// Start the system, `graph_index` is the execution graph it belongs to
+    Run(usize, SystemId),
+    /// Re-poll the pending async system held by the thread
+    Resume(SystemId),
+}
+
+/// Re-enqueues a pending async system onto the thread which owns its future
+pub struct TaskWaker {
+    ready_queue: Arc<ReadyQueue<Task>>,
+    worker: usize,
+    system_id: SystemId,
+}
+
+impl TaskWaker {
+    pub fn new(ready_queue: Arc<ReadyQueue<Task>>, worker: usize, system_id: SystemId) -> Self {
+        Self { ready_queue, worker, system_id }
+    }
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.ready_queue.wake(self.worker, Task::Resume(self.system_id.clone()));
+    }
+}
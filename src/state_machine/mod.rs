@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use tracing::{Level, event, field, span};
 
-use crate::prelude::{Injection, InsertError, KernelSystemRegistry, Memory, MemoryDomain, ProgramId, ProgramKey, ResolveError, Resource, ResourceId, Shared, StoredKernelSystem, SystemId, TickAccumulator, Unique};
+use crate::prelude::{EventId, EventMapperManager, Injection, InsertError, KernelSystemRegistry, Memory, MappingError, MemoryDomain, ProgramId, ProgramKey, RemoveError, ResolveError, Resource, ResourceId, Shared, StoredKernelSystem, SystemId, TickAccumulator, Unique};
 
 pub mod kernel_systems;
 pub mod kernel_registry;
@@ -71,10 +71,24 @@ impl StateMachine {
         self.memory.insert(program_id, resource_id, key, resource)
     }
 
+    pub fn remove(&self, program_id: Option<&ProgramId>, resource_id: &ResourceId, key: Option<&ProgramKey>) -> Option<Result<Option<Resource>, RemoveError>> {
+        self.memory.remove(program_id, resource_id, key)
+    }
+
     pub fn insert_program(&self, program_id: ProgramId, memory_domain: Arc<MemoryDomain>, key: Option<ProgramKey>) -> bool {
         self.memory.insert_program(program_id, memory_domain, key)
     }
 
+    /// Aliases `a` & `b`: when either is current, both are. See `EventMapperManager::insert_is`
+    pub fn insert_is(&self, a: impl Into<EventId>, b: impl Into<EventId>) -> Option<Result<bool, MappingError>> {
+        EventMapperManager::insert_is(&self.memory, a.into(), b.into())
+    }
+
+    /// When `from` is current, `to` is emitted into the next tick. See `EventMapperManager::insert_becomes`
+    pub fn insert_becomes(&self, from: impl Into<EventId>, to: impl Into<EventId>) -> Option<Result<bool, MappingError>> {
+        EventMapperManager::insert_becomes(&self.memory, from.into(), to.into())
+    }
+
     // could make it async but then Processor run time weird stuff so idk
     pub fn tick(&self) {
         let span = span!(Level::INFO, "Tick", current_tick=field::Empty);
@@ -1,4 +1,6 @@
 mod processors;
+mod managers;
+mod systems;
 
 use tracing_subscriber::fmt;
 use std::sync::Once;
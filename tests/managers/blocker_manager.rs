@@ -0,0 +1,149 @@
+use aion_reactor::prelude::{BlockedEvents, Blocker, BlockerId, BlockerManager, BlockerMode, Criteria, CurrentBlockers, CurrentEvents, EventId, KernelBuilder, NextBlockers, NextEvents, SchedulerOrdering, Shared, StateMachine, System, SystemId, SystemResult, Unique};
+
+use crate::{init_tracing, systems::TestSystem};
+
+fn counter(mut count: Unique<usize>) -> Option<SystemResult> {
+    **count += 1;
+    None
+}
+
+fn observer(mut count: Unique<i32>) -> Option<SystemResult> {
+    **count += 1;
+    None
+}
+
+fn pause_counter(mut next_blockers: Unique<NextBlockers>) -> Option<SystemResult> {
+    next_blockers.insert_blocker(BlockerId::from("Pause"), Blocker::system("Counter").cancelling(), None);
+    None
+}
+
+fn count(state_machine: &StateMachine) -> usize {
+    **state_machine.resolve::<Unique<usize>>(None, None, None, None).unwrap().unwrap()
+}
+
+fn observed(state_machine: &StateMachine) -> i32 {
+    **state_machine.resolve::<Unique<i32>>(None, None, None, None).unwrap().unwrap()
+}
+
+fn init_with(criteria: Criteria) -> StateMachine {
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    state_machine.insert(None, None, None, 0usize);
+    state_machine.insert(None, None, None, 0i32);
+
+    TestSystem::new("Counter", System::new_sync(counter)).replace_criteria(criteria).build_blocking(&state_machine);
+    state_machine
+}
+
+fn init() -> StateMachine {
+    init_with(Criteria::always())
+}
+
+fn send(state_machine: &StateMachine, event: &str) {
+    state_machine.resolve::<Unique<CurrentEvents>>(None, None, None, None).unwrap().unwrap().insert(EventId::from(event));
+}
+
+#[test]
+fn blocks_for_ticks() {
+    init_tracing();
+
+    let state_machine = init();
+    assert!(BlockerManager::insert_blocker(&state_machine, "Pause", Blocker::system("Counter").for_ticks(2).with_reason("Paused")).unwrap().is_none());
+
+    state_machine.tick();
+    {
+        let current_blockers = state_machine.resolve::<Shared<CurrentBlockers>>(None, None, None, None).unwrap().unwrap();
+        let system_id = SystemId::from("Counter");
+        let (blocker_id, active_blocker) = current_blockers.blockers_of(&system_id, None).next().unwrap();
+        assert_eq!(blocker_id, &BlockerId::from("Pause"));
+        assert_eq!(active_blocker.blocker().reason(), Some("Paused"));
+    }
+
+    state_machine.tick();
+    assert_eq!(count(&state_machine), 0);
+
+    state_machine.tick();
+    assert_eq!(count(&state_machine), 1);
+}
+
+#[test]
+fn blocks_until_event_or_unblocked() {
+    init_tracing();
+
+    let state_machine = init();
+    BlockerManager::insert_blocker(&state_machine, "Wait", Blocker::systems(["Counter"]).until_event("Resume"));
+    BlockerManager::insert_blocker(&state_machine, "Hold", Blocker::system("Counter").until_unblocked());
+
+    for _ in 0..3 {
+        state_machine.tick();
+    }
+    assert_eq!(count(&state_machine), 0);
+
+    send(&state_machine, "Resume");
+    state_machine.tick();
+    assert_eq!(count(&state_machine), 0);
+    assert_eq!(state_machine.resolve::<Shared<CurrentBlockers>>(None, None, None, None).unwrap().unwrap().len(), 1);
+
+    assert_eq!(BlockerManager::unblock(&state_machine, "Hold"), Some(true));
+    state_machine.tick();
+    assert_eq!(count(&state_machine), 1);
+}
+
+#[test]
+fn consume_and_mask_triggering_events() {
+    init_tracing();
+
+    for (mode, still_current) in [(BlockerMode::Skip, true), (BlockerMode::Mask, true), (BlockerMode::Consume, false)] {
+        let state_machine = init_with(Criteria::event("Go"));
+        TestSystem::new("Observer", System::new_sync(observer)).replace_criteria(Criteria::event("Go")).build_blocking(&state_machine);
+
+        BlockerManager::insert_blocker(&state_machine, "Pause", Blocker::system("Counter").with_mode(mode));
+        // Current next tick, alongside the blocker
+        state_machine.resolve::<Unique<NextEvents>>(None, None, None, None).unwrap().unwrap().insert(EventId::from("Go"));
+
+        state_machine.tick();
+
+        assert_eq!(count(&state_machine), 0);
+        assert_eq!(observed(&state_machine), (mode == BlockerMode::Skip) as i32, "{mode:?}");
+        assert_eq!(state_machine.resolve::<Shared<CurrentEvents>>(None, None, None, None).unwrap().unwrap().contains(&EventId::from("Go")), still_current, "{mode:?}");
+    }
+}
+
+fn reader() -> Option<SystemResult> {
+    None
+}
+
+#[test]
+fn masks_across_processors() {
+    init_tracing();
+
+    for (mode, still_current) in [(BlockerMode::Mask, true), (BlockerMode::Consume, false)] {
+        let state_machine = init_with(Criteria::never());
+        TestSystem::new("Observer", System::new_sync(observer)).replace_criteria(Criteria::event("Go")).build_blocking(&state_machine);
+
+        // Read only systems run after blocking ones, their blocker still hides the event from "Observer"
+        TestSystem::new("Reader", System::new_sync(reader)).replace_criteria(Criteria::event("Go")).build_read_only(&state_machine);
+
+        BlockerManager::insert_blocker(&state_machine, "Pause", Blocker::system("Reader").with_mode(mode));
+        state_machine.resolve::<Unique<NextEvents>>(None, None, None, None).unwrap().unwrap().insert(EventId::from("Go"));
+
+        state_machine.tick();
+
+        assert_eq!(observed(&state_machine), 0, "{mode:?}");
+        assert!(state_machine.resolve::<Shared<BlockedEvents>>(None, None, None, None).unwrap().unwrap().hides(&EventId::from("Go")));
+        assert_eq!(state_machine.resolve::<Shared<CurrentEvents>>(None, None, None, None).unwrap().unwrap().contains(&EventId::from("Go")), still_current, "{mode:?}");
+    }
+}
+
+#[test]
+fn cancels_scheduled_systems() {
+    init_tracing();
+
+    let state_machine = init();
+    TestSystem::new("Pauser", System::new_sync(pause_counter)).insert_ordering(SchedulerOrdering::default().insert_before(SystemId::from("Counter"))).build_blocking(&state_machine);
+
+    state_machine.tick();
+    assert_eq!(count(&state_machine), 0);
+    assert!(!state_machine.resolve::<Shared<CurrentEvents>>(None, None, None, None).unwrap().unwrap().contains(&EventId::from("Counter")));
+}
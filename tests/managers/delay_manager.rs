@@ -0,0 +1,70 @@
+use aion_reactor::prelude::{CurrentEvents, DelayBuffer, DelayManager, EventId, KernelBuilder, RegisteredDelay, Shared, StateMachine, Unique};
+
+use crate::init_tracing;
+
+fn is_current(state_machine: &StateMachine, event: &str) -> bool {
+    state_machine.resolve::<Shared<CurrentEvents>>(None, None, None, None).unwrap().unwrap().contains(&EventId::from(event))
+}
+
+fn send(state_machine: &StateMachine, event: &str) {
+    state_machine.resolve::<Unique<CurrentEvents>>(None, None, None, None).unwrap().unwrap().insert(EventId::from(event));
+}
+
+fn pending(state_machine: &StateMachine) -> usize {
+    state_machine.resolve::<Shared<DelayBuffer>>(None, None, None, None).unwrap().unwrap().len()
+}
+
+#[test]
+fn regular() {
+    init_tracing();
+
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    assert_eq!(DelayManager::insert_delay(&state_machine, RegisteredDelay::new("Start", "End").with_delayed_by("During")), Some(0));
+
+    state_machine.tick();
+    send(&state_machine, "Start");
+    send(&state_machine, "During");
+
+    for _ in 0..3 {
+        state_machine.tick();
+        assert!(!is_current(&state_machine, "End"));
+        assert_eq!(pending(&state_machine), 1);
+        send(&state_machine, "During");
+    }
+
+    // During stopped
+    state_machine.tick();
+    state_machine.tick();
+    assert!(is_current(&state_machine, "End"));
+    assert_eq!(pending(&state_machine), 0);
+
+    state_machine.tick();
+    assert!(!is_current(&state_machine, "End"));
+}
+
+#[test]
+fn max_ticks() {
+    init_tracing();
+
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    DelayManager::insert_delay(&state_machine, RegisteredDelay::new("Start", "End").with_delayed_by("During").with_max_ticks(2));
+
+    state_machine.tick();
+    send(&state_machine, "Start");
+    send(&state_machine, "During");
+
+    let mut ended = None;
+    for tick in 0..5 {
+        state_machine.tick();
+        if is_current(&state_machine, "End") {
+            ended.get_or_insert(tick);
+        }
+        send(&state_machine, "During");
+    }
+
+    assert_eq!(ended, Some(2));
+}
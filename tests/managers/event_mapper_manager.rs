@@ -0,0 +1,67 @@
+use aion_reactor::prelude::{Criteria, CurrentEvents, EventId, KernelBuilder, MappingError, Shared, StateMachine, System, SystemEvent, SystemResult};
+
+use crate::{init_tracing, systems::TestSystem};
+
+fn start() -> Option<SystemResult> {
+    Some(SystemResult::Event(SystemEvent::WithEvent(EventId::from("Start"))))
+}
+
+fn current(state_machine: &StateMachine) -> Vec<String> {
+    let current_events = state_machine.resolve::<Shared<CurrentEvents>>(None, None, None, None).unwrap().unwrap();
+    let mut events = current_events.read().map(|event| event.get_id().to_string()).filter(|event| event.len() == 1 || event == "Start").collect::<Vec<_>>();
+    events.sort();
+    events
+}
+
+#[test]
+fn maps_is_then_becomes() {
+    init_tracing();
+
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    // Start is A, A becomes B, B becomes C
+    assert_eq!(state_machine.insert_is("Start", "A"), Some(Ok(true)));
+    assert_eq!(state_machine.insert_becomes("A", "B"), Some(Ok(true)));
+    assert_eq!(state_machine.insert_becomes("B", "C"), Some(Ok(true)));
+    assert_eq!(state_machine.insert_becomes("B", "C"), Some(Ok(false)));
+
+    TestSystem::new("Starter", System::new_sync(start))
+        .replace_criteria(Criteria::parse("!Start & !A & !B & !C").unwrap())
+        .build_blocking(&state_machine);
+
+    state_machine.tick();
+    state_machine.tick();
+    assert_eq!(current(&state_machine), vec!["A", "Start"]);
+
+    state_machine.tick();
+    assert_eq!(current(&state_machine), vec!["B"]);
+
+    state_machine.tick();
+    assert_eq!(current(&state_machine), vec!["C"]);
+}
+
+#[test]
+fn rejects_cycles() {
+    init_tracing();
+
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    assert_eq!(state_machine.insert_becomes("A", "A"), Some(Err(MappingError::SelfMapping(EventId::from("A")))));
+
+    assert_eq!(state_machine.insert_becomes("A", "B"), Some(Ok(true)));
+    assert_eq!(state_machine.insert_becomes("B", "C"), Some(Ok(true)));
+    assert_eq!(
+        state_machine.insert_becomes("C", "A"),
+        Some(Err(MappingError::Cycle(vec![EventId::from("C"), EventId::from("A"), EventId::from("B"), EventId::from("C")])))
+    );
+
+    // C is A would make A become itself
+    assert!(matches!(state_machine.insert_is("C", "A"), Some(Err(MappingError::Cycle(_)))));
+
+    // A -> B -> C -> E, and E is D, so D cant become A
+    assert_eq!(state_machine.insert_is("D", "E"), Some(Ok(true)));
+    assert_eq!(state_machine.insert_becomes("C", "E"), Some(Ok(true)));
+    assert!(matches!(state_machine.insert_becomes("D", "A"), Some(Err(MappingError::Cycle(_)))));
+}
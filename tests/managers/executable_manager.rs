@@ -0,0 +1,587 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use aion_reactor::prelude::{Criteria, CurrentEvents, ErrorPolicy, EventId, Executable, ExecutableBuffer, ExecutableFailure, ExecutableInput, ExecutableInputs, ExecutableManager, ExecutableMessage, ExecutableOutput, ExecutableOutputs, ExecutableQueue, ExecutableRegistry, ExecutableWork, ExecutableEntities, EntityId, ExecutableStatus, KernelBuilder, NextEvents, Pipeline, PipelineError, PipelineParseError, ResourceId, Shared, StateMachine, System, SystemEvent, SystemResult, Unique, World};
+
+use crate::{init_tracing, systems::TestSystem};
+
+fn is_current(state_machine: &StateMachine, event: &str) -> bool {
+    state_machine.resolve::<Shared<CurrentEvents>>(None, None, None, None).unwrap().unwrap().contains(&EventId::from(event))
+}
+
+fn current(state_machine: &StateMachine) -> Vec<&'static str> {
+    ["A", "B", "C", "D"].into_iter().filter(|event| is_current(state_machine, event)).collect()
+}
+
+fn init() -> StateMachine {
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    // Complete the tick after they are triggered
+    for name in ["A", "B", "C", "D"] {
+        assert_eq!(ExecutableManager::insert_executable(&state_machine, name, EventId::from(name)), format!("{name}-Executable"));
+    }
+
+    state_machine
+}
+
+fn message() -> ExecutableMessage {
+    ExecutableMessage::ResourceId(ResourceId::from_raw_heap::<i32>())
+}
+
+#[test]
+fn fan_out_and_join() {
+    init_tracing();
+
+    let state_machine = init();
+    ExecutableManager::queue_executable(&state_machine, "A-Executable>(B-Executable|C-Executable>C-Executable)>D-Executable".to_string(), message()).unwrap();
+
+    state_machine.tick();
+    assert_eq!(current(&state_machine), vec!["A"]);
+
+    state_machine.tick();
+    assert_eq!(current(&state_machine), vec!["B", "C"]);
+
+    // D waits for the longer branch
+    state_machine.tick();
+    assert_eq!(current(&state_machine), vec!["C"]);
+
+    state_machine.tick();
+    assert_eq!(current(&state_machine), vec!["D"]);
+    {
+        let buffer = state_machine.resolve::<Shared<ExecutableBuffer>>(None, None, None, None).unwrap().unwrap();
+        let joined = buffer.read().next().unwrap();
+        assert_eq!(joined.label.get_label(), "D-Executable");
+        assert!(matches!(&joined.source, ExecutableMessage::Joined(messages) if messages.len() == 2));
+    }
+
+    state_machine.tick();
+    assert!(current(&state_machine).is_empty());
+    assert_eq!(state_machine.resolve::<Shared<ExecutableQueue>>(None, None, None, None).unwrap().unwrap().len(), 0);
+    assert!(state_machine.resolve::<Shared<ExecutableBuffer>>(None, None, None, None).unwrap().unwrap().is_empty());
+}
+
+#[test]
+fn branches() {
+    init_tracing();
+
+    let state_machine = init();
+    for _ in 0..2 {
+        ExecutableManager::queue_executable(&state_machine, "A-Executable>[Go: B-Executable | _: C-Executable]".to_string(), message()).unwrap();
+    }
+
+    state_machine.tick();
+    ExecutableManager::queue_executable(&state_machine, "[$entity: A-Executable | $resource: D-Executable]".to_string(), message()).unwrap();
+    state_machine.resolve::<Unique<CurrentEvents>>(None, None, None, None).unwrap().unwrap().insert(EventId::from("Go"));
+
+    state_machine.tick();
+    assert_eq!(current(&state_machine), vec!["B", "D"]);
+
+    state_machine.tick();
+    assert!(current(&state_machine).is_empty());
+}
+
+#[test]
+fn validates_when_queued() {
+    init_tracing();
+
+    let state_machine = init();
+
+    assert_eq!(
+        ExecutableManager::queue_executable(&state_machine, "A-Executable>(B-Executable|Nope)".to_string(), message()),
+        Err(PipelineError::NotFound("Nope".to_string()))
+    );
+    assert_eq!(
+        ExecutableManager::queue_executable(&state_machine, "A-Executable>(B-Executable".to_string(), message()),
+        Err(PipelineError::Parse(PipelineParseError::UnexpectedEnd))
+    );
+    assert_eq!(state_machine.resolve::<Shared<ExecutableQueue>>(None, None, None, None).unwrap().unwrap().len(), 0);
+}
+
+#[test]
+fn configurable_syntax() {
+    init_tracing();
+
+    let state_machine = init();
+    ExecutableManager::configure_registry(&state_machine, ExecutableRegistry::builder().with_delimiter(',').with_skip("wait"));
+    ExecutableManager::queue_executable(&state_machine, "A-Executable, wait, B-Executable".to_string(), message()).unwrap();
+
+    state_machine.tick();
+    assert_eq!(current(&state_machine), vec!["A"]);
+
+    state_machine.tick();
+    assert!(current(&state_machine).is_empty());
+
+    state_machine.tick();
+    assert_eq!(current(&state_machine), vec!["B"]);
+}
+
+#[test]
+fn queues_composed_pipelines() {
+    init_tracing();
+
+    let state_machine = init();
+    let pipeline = Pipeline::stage("A-Executable")
+        .then(Pipeline::fan_out([Pipeline::stage("B-Executable"), Pipeline::stage("C-Executable")]))
+        .then(Pipeline::stage("D-Executable"));
+
+    let label = ExecutableManager::render_pipeline(&state_machine, &pipeline);
+    assert_eq!(label, "A-Executable>(B-Executable|C-Executable)>D-Executable");
+
+    // Policies apply by the rendered label
+    ExecutableManager::set_error_policy(&state_machine, label, ErrorPolicy::Skip);
+    let run_id = ExecutableManager::queue_pipeline(&state_machine, pipeline, message()).unwrap();
+    assert_eq!(state_machine.resolve::<Shared<ExecutableQueue>>(None, None, None, None).unwrap().unwrap().get(run_id).unwrap().policy(), &ErrorPolicy::Skip);
+
+    state_machine.tick();
+    assert_eq!(current(&state_machine), vec!["A"]);
+
+    state_machine.tick();
+    assert_eq!(current(&state_machine), vec!["B", "C"]);
+
+    state_machine.tick();
+    assert_eq!(current(&state_machine), vec!["D"]);
+
+    assert_eq!(
+        ExecutableManager::queue_pipeline(&state_machine, Pipeline::stage("A-Executable").then(Pipeline::stage("Nope")), message()),
+        Err(PipelineError::NotFound("Nope".to_string()))
+    );
+    assert_eq!(
+        ExecutableManager::queue_pipeline(&state_machine, Pipeline::sequence([]), message()),
+        Err(PipelineError::Empty)
+    );
+}
+
+fn completes(work: ExecutableWork) -> Option<SystemResult> {
+    Some(SystemResult::Events(work.iter().map(|buffered_executable| SystemEvent::WithEvent(buffered_executable.completed())).collect()))
+}
+
+#[test]
+fn waits_for_completion() {
+    init_tracing();
+
+    let state_machine = init();
+    ExecutableManager::register_executable(&state_machine, Executable::new("Slow-A".to_string(), EventId::from("A")).with_system("A System"));
+    ExecutableManager::register_executable(&state_machine, Executable::new("Slow-B".to_string(), EventId::from("B")));
+    TestSystem::new("A System", System::new_sync(completes)).replace_criteria(Criteria::event("A")).build_blocking(&state_machine);
+
+    let run_id = ExecutableManager::queue_executable(&state_machine, "Slow-A>Slow-B".to_string(), message()).unwrap();
+    assert_eq!(ExecutableManager::status(&state_machine, run_id), Some(ExecutableStatus::Queued));
+
+    state_machine.tick();
+    assert_eq!(current(&state_machine), vec!["A"]);
+    assert_eq!(ExecutableManager::status(&state_machine, run_id), Some(ExecutableStatus::Running(1)));
+
+    // "A System" completed A
+    state_machine.tick();
+    assert_eq!(current(&state_machine), vec!["B"]);
+    assert_eq!(ExecutableManager::status(&state_machine, run_id), Some(ExecutableStatus::Running(2)));
+
+    // Nothing completes B
+    for _ in 0..3 {
+        state_machine.tick();
+        assert!(current(&state_machine).is_empty());
+    }
+    assert_eq!(ExecutableManager::running(&state_machine), vec![run_id]);
+
+    state_machine.resolve::<Unique<NextEvents>>(None, None, None, None).unwrap().unwrap().insert(EventId::completed("Slow-B", run_id));
+    state_machine.tick();
+    assert_eq!(ExecutableManager::status(&state_machine, run_id), Some(ExecutableStatus::Done));
+    assert!(ExecutableManager::running(&state_machine).is_empty());
+    assert!(state_machine.resolve::<Shared<ExecutableBuffer>>(None, None, None, None).unwrap().unwrap().is_empty());
+
+    assert_eq!(ExecutableManager::take_finished(&state_machine).remove(&run_id), Some(ExecutableStatus::Done));
+    assert_eq!(ExecutableManager::status(&state_machine, run_id), None);
+}
+
+#[test]
+fn completes_each_run_once() {
+    init_tracing();
+
+    let state_machine = init();
+    ExecutableManager::register_executable(&state_machine, Executable::new("Slow".to_string(), EventId::from("S")));
+
+    let first = ExecutableManager::queue_executable(&state_machine, "Slow>Slow".to_string(), message()).unwrap();
+    let second = ExecutableManager::queue_executable(&state_machine, "Slow>Slow".to_string(), message()).unwrap();
+    let complete = |run_id| state_machine.resolve::<Unique<NextEvents>>(None, None, None, None).unwrap().unwrap().insert(EventId::completed("Slow", run_id));
+
+    state_machine.tick();
+    complete(first);
+
+    // Only the completed run advances
+    state_machine.tick();
+    assert_eq!(ExecutableManager::status(&state_machine, first), Some(ExecutableStatus::Running(2)));
+    assert_eq!(ExecutableManager::status(&state_machine, second), Some(ExecutableStatus::Running(1)));
+
+    // The completion is now current, it doesn't complete the second "Slow"
+    state_machine.tick();
+    assert_eq!(ExecutableManager::status(&state_machine, first), Some(ExecutableStatus::Running(2)));
+
+    complete(first);
+    state_machine.tick();
+    assert_eq!(ExecutableManager::status(&state_machine, first), Some(ExecutableStatus::Done));
+    assert_eq!(ExecutableManager::status(&state_machine, second), Some(ExecutableStatus::Running(1)));
+}
+
+#[test]
+fn fails_on_timeout_or_failed_event() {
+    init_tracing();
+
+    let state_machine = init();
+    ExecutableManager::register_executable(&state_machine, Executable::new("Timed".to_string(), EventId::from("A")).with_timeout(2));
+    ExecutableManager::register_executable(&state_machine, Executable::new("Failing".to_string(), EventId::from("B")));
+
+    let timed = ExecutableManager::queue_executable(&state_machine, "Timed>C-Executable".to_string(), message()).unwrap();
+    let failing = ExecutableManager::queue_executable(&state_machine, "(Failing|D-Executable)>C-Executable".to_string(), message()).unwrap();
+
+    state_machine.tick();
+    state_machine.resolve::<Unique<NextEvents>>(None, None, None, None).unwrap().unwrap().insert(EventId::failed("Failing", failing));
+
+    state_machine.tick();
+    assert_eq!(ExecutableManager::status(&state_machine, failing), Some(ExecutableStatus::Failed(ExecutableFailure::Failed("Failing".to_string()))));
+    assert_eq!(ExecutableManager::status(&state_machine, timed), Some(ExecutableStatus::Running(1)));
+
+    state_machine.tick();
+    assert_eq!(ExecutableManager::status(&state_machine, timed), Some(ExecutableStatus::Failed(ExecutableFailure::TimedOut("Timed".to_string()))));
+    assert!(!is_current(&state_machine, "C"));
+    assert!(state_machine.resolve::<Shared<ExecutableBuffer>>(None, None, None, None).unwrap().unwrap().is_empty());
+}
+
+fn double(input: ExecutableInput<i32>, mut output: ExecutableOutput<i32>) -> Option<SystemResult> {
+    for (stage, value) in input.iter() {
+        output.write(stage.run_id, *value * 2);
+    }
+    None
+}
+
+fn increment(input: ExecutableInput<i32>, mut output: ExecutableOutput<i32>) -> Option<SystemResult> {
+    for (stage, value) in input.iter() {
+        output.write(stage.run_id, *value + 1);
+    }
+    None
+}
+
+#[test]
+fn rewrites_resources_between_stages() {
+    init_tracing();
+
+    let state_machine = init();
+    ExecutableManager::register_executable(&state_machine, Executable::new("Double".to_string(), EventId::from("Double")).immediate().with_system("Double System"));
+    ExecutableManager::register_executable(&state_machine, Executable::new("Increment".to_string(), EventId::from("Increment")).immediate().with_system("Increment System"));
+    TestSystem::new("Double System", System::new_sync(double)).replace_criteria(Criteria::event("Double")).build_blocking(&state_machine);
+    TestSystem::new("Increment System", System::new_sync(increment)).replace_criteria(Criteria::event("Increment")).build_blocking(&state_machine);
+
+    let input = ResourceId::from_labelled_heap("Input");
+    assert!(state_machine.insert(None, Some(input.clone()), None, 5_i32).unwrap().is_ok());
+
+    let run_id = ExecutableManager::queue_executable(&state_machine, "Double>Increment".to_string(), ExecutableMessage::ResourceId(input.clone())).unwrap();
+    let doubled = ResourceId::from_labelled_heap(format!("{run_id}-0-Double"));
+
+    state_machine.tick();
+    assert!(state_machine.resolve::<Shared<ExecutableOutputs>>(None, None, None, None).unwrap().unwrap().contains(&doubled));
+    // Lent while "Double" reads it
+    assert!(state_machine.resolve::<Shared<i32>>(None, Some(&input), None, None).unwrap().is_err());
+
+    // Written, then lent to "Increment"
+    state_machine.tick();
+    assert_eq!(state_machine.resolve::<Shared<ExecutableInputs>>(None, None, None, None).unwrap().unwrap().get::<i32>(&doubled), Some(&10));
+
+    state_machine.tick();
+    assert_eq!(ExecutableManager::status(&state_machine, run_id), Some(ExecutableStatus::Done));
+
+    let Some(ExecutableMessage::ResourceId(output)) = ExecutableManager::output(&state_machine, run_id) else {
+        panic!("Expected a resource output");
+    };
+    assert_eq!(**state_machine.resolve::<Shared<i32>>(None, Some(&output), None, None).unwrap().unwrap(), 11);
+
+    // The intermediate resource is removed, the input is untouched
+    assert!(state_machine.resolve::<Shared<i32>>(None, Some(&doubled), None, None).unwrap().is_err());
+    assert_eq!(**state_machine.resolve::<Shared<i32>>(None, Some(&input), None, None).unwrap().unwrap(), 5);
+}
+
+#[test]
+fn runs_reaching_a_system_the_same_tick() {
+    init_tracing();
+
+    let state_machine = init();
+    ExecutableManager::register_executable(&state_machine, Executable::new("Double".to_string(), EventId::from("Double")).immediate().with_system("Double System"));
+    TestSystem::new("Double System", System::new_sync(double)).replace_criteria(Criteria::event("Double")).build_blocking(&state_machine);
+
+    let runs: Vec<_> = [1_i32, 2].into_iter().map(|value| {
+        let input = ResourceId::from_labelled_heap(format!("Input-{value}"));
+        assert!(state_machine.insert(None, Some(input.clone()), None, value).unwrap().is_ok());
+        ExecutableManager::queue_executable(&state_machine, "Double".to_string(), ExecutableMessage::ResourceId(input)).unwrap()
+    }).collect();
+
+    state_machine.tick();
+    state_machine.tick();
+    for (run_id, expected) in runs.into_iter().zip([2, 4]) {
+        assert_eq!(ExecutableManager::status(&state_machine, run_id), Some(ExecutableStatus::Done));
+        let Some(ExecutableMessage::ResourceId(output)) = ExecutableManager::output(&state_machine, run_id) else {
+            panic!("Expected a resource output");
+        };
+        assert_eq!(**state_machine.resolve::<Shared<i32>>(None, Some(&output), None, None).unwrap().unwrap(), expected);
+    }
+}
+
+fn double_all(mut work: ExecutableWork) -> Option<SystemResult> {
+    let stages: Vec<_> = work.iter().cloned().collect();
+    for buffered_executable in &stages {
+        let input = work.read_cloned::<i32>(buffered_executable).unwrap();
+        assert!(work.write(buffered_executable, input * 2));
+    }
+    None
+}
+
+#[test]
+fn concurrent_runs_of_the_same_pipeline() {
+    init_tracing();
+
+    let state_machine = init();
+    ExecutableManager::register_executable(&state_machine, Executable::new("Double".to_string(), EventId::from("Double")).immediate().with_system("Double System"));
+    TestSystem::new("Double System", System::new_sync(double_all)).replace_criteria(Criteria::event("Double")).build_blocking(&state_machine);
+
+    let runs: Vec<_> = [1_i32, 2].into_iter().map(|value| {
+        let input = ResourceId::from_labelled_heap(format!("Input-{value}"));
+        assert!(state_machine.insert(None, Some(input.clone()), None, value).unwrap().is_ok());
+        ExecutableManager::queue_executable(&state_machine, "Double".to_string(), ExecutableMessage::ResourceId(input)).unwrap()
+    }).collect();
+    assert_ne!(runs[0], runs[1]);
+
+    state_machine.tick();
+    {
+        let buffer = state_machine.resolve::<Shared<ExecutableBuffer>>(None, None, None, None).unwrap().unwrap();
+        assert_eq!(buffer.run(runs[0]).len(), 1);
+        assert_eq!(buffer.run(runs[1]).len(), 1);
+    }
+
+    state_machine.tick();
+    for (run_id, expected) in runs.into_iter().zip([2, 4]) {
+        let Some(ExecutableMessage::ResourceId(output)) = ExecutableManager::output(&state_machine, run_id) else {
+            panic!("Expected a resource output");
+        };
+        assert_eq!(**state_machine.resolve::<Shared<i32>>(None, Some(&output), None, None).unwrap().unwrap(), expected);
+    }
+}
+
+fn errors(_work: ExecutableWork) -> Option<SystemResult> {
+    Some(SystemResult::Error(anyhow::anyhow!("stage errored")))
+}
+
+/// "Erroring" fails through its system's `SystemResult::Error`
+fn init_erroring(policy: ErrorPolicy) -> StateMachine {
+    let state_machine = init();
+    ExecutableManager::register_executable(&state_machine, Executable::new("Erroring".to_string(), EventId::from("E")).immediate().with_system("Erroring System"));
+    TestSystem::new("Erroring System", System::new_sync(errors)).replace_criteria(Criteria::event("E")).build_blocking(&state_machine);
+    ExecutableManager::set_error_policy(&state_machine, "Erroring>B-Executable".to_string(), policy);
+    state_machine
+}
+
+#[test]
+fn aborts_on_system_error() {
+    init_tracing();
+
+    let state_machine = init_erroring(ErrorPolicy::Abort);
+    let run_id = ExecutableManager::queue_executable(&state_machine, "Erroring>B-Executable".to_string(), message()).unwrap();
+
+    state_machine.tick();
+    state_machine.tick();
+    assert_eq!(ExecutableManager::status(&state_machine, run_id), Some(ExecutableStatus::Failed(ExecutableFailure::Errored("Erroring".to_string()))));
+    assert!(!is_current(&state_machine, "B"));
+    assert!(state_machine.resolve::<Shared<CurrentEvents>>(None, None, None, None).unwrap().unwrap().contains(&EventId::executable_failed()));
+
+    let failed = ExecutableManager::take_failed(&state_machine);
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].run_id, run_id);
+    assert_eq!(failed[0].label, "Erroring>B-Executable");
+    assert!(ExecutableManager::take_failed(&state_machine).is_empty());
+}
+
+#[test]
+fn skips_failed_stages() {
+    init_tracing();
+
+    let state_machine = init_erroring(ErrorPolicy::Skip);
+    let run_id = ExecutableManager::queue_executable(&state_machine, "Erroring>B-Executable".to_string(), message()).unwrap();
+
+    state_machine.tick();
+    state_machine.tick();
+    assert_eq!(current(&state_machine), vec!["B"]);
+    {
+        // B is given the input of the skipped stage
+        let buffer = state_machine.resolve::<Shared<ExecutableBuffer>>(None, None, None, None).unwrap().unwrap();
+        let buffered_executable = buffer.run(run_id).first().unwrap();
+        assert!(matches!(&buffered_executable.source, ExecutableMessage::ResourceId(resource_id) if *resource_id == ResourceId::from_raw_heap::<i32>()));
+    }
+
+    state_machine.tick();
+    assert_eq!(ExecutableManager::status(&state_machine, run_id), Some(ExecutableStatus::Done));
+    assert!(ExecutableManager::take_failed(&state_machine).is_empty());
+}
+
+#[test]
+fn dead_letters_failed_runs() {
+    init_tracing();
+
+    let state_machine = init_erroring(ErrorPolicy::dead_letter("D-Executable"));
+    let run_id = ExecutableManager::queue_executable(&state_machine, "Erroring>B-Executable".to_string(), message()).unwrap();
+
+    state_machine.tick();
+    state_machine.tick();
+    assert_eq!(current(&state_machine), vec!["D"]);
+    assert_eq!(ExecutableManager::status(&state_machine, run_id), Some(ExecutableStatus::Running(2)));
+
+    state_machine.tick();
+    assert_eq!(ExecutableManager::status(&state_machine, run_id), Some(ExecutableStatus::Failed(ExecutableFailure::Errored("Erroring".to_string()))));
+    assert_eq!(ExecutableManager::take_failed(&state_machine).len(), 1);
+
+    // The dead-letter stage must be registered
+    ExecutableManager::set_error_policy(&state_machine, "B-Executable".to_string(), ErrorPolicy::dead_letter("Missing"));
+    assert_eq!(ExecutableManager::queue_executable(&state_machine, "B-Executable".to_string(), message()), Err(PipelineError::NotFound("Missing".to_string())));
+}
+
+/// Errors on odd inputs, never completes
+fn picky(work: ExecutableWork) -> Option<SystemResult> {
+    if work.iter().any(|buffered_executable| work.read_cloned::<i32>(buffered_executable).unwrap() % 2 == 1) {
+        return errors(work);
+    }
+    None
+}
+
+#[test]
+fn fails_only_the_errored_run() {
+    init_tracing();
+
+    let state_machine = init();
+    ExecutableManager::register_executable(&state_machine, Executable::new("Picky".to_string(), EventId::from("P")).with_system("Picky System"));
+    TestSystem::new("Picky System", System::new_sync(picky)).replace_criteria(Criteria::event("P")).build_blocking(&state_machine);
+
+    let queue = |value: i32| {
+        let input = ResourceId::from_labelled_heap(format!("Input-{value}"));
+        assert!(state_machine.insert(None, Some(input.clone()), None, value).unwrap().is_ok());
+        ExecutableManager::queue_executable(&state_machine, "Picky>B-Executable".to_string(), ExecutableMessage::ResourceId(input)).unwrap()
+    };
+
+    let waiting = queue(2);
+    state_machine.tick();
+
+    // Errors on the later run while the first still waits
+    let erroring = queue(1);
+    state_machine.tick();
+    state_machine.tick();
+    assert_eq!(ExecutableManager::status(&state_machine, erroring), Some(ExecutableStatus::Failed(ExecutableFailure::Errored("Picky".to_string()))));
+    assert_eq!(ExecutableManager::status(&state_machine, waiting), Some(ExecutableStatus::Running(1)));
+
+    state_machine.resolve::<Unique<NextEvents>>(None, None, None, None).unwrap().unwrap().insert(EventId::completed("Picky", waiting));
+    state_machine.tick();
+    assert_eq!(current(&state_machine), vec!["B"]);
+    assert_eq!(ExecutableManager::status(&state_machine, waiting), Some(ExecutableStatus::Running(2)));
+}
+
+static FLAKY_RUNS: AtomicU32 = AtomicU32::new(0);
+
+/// Errors twice then completes
+fn flaky(work: ExecutableWork) -> Option<SystemResult> {
+    if FLAKY_RUNS.fetch_add(1, Ordering::SeqCst) < 2 {
+        return errors(work);
+    }
+    completes(work)
+}
+
+#[test]
+fn retries_with_backoff() {
+    init_tracing();
+
+    let state_machine = init();
+    ExecutableManager::register_executable(&state_machine, Executable::new("Flaky".to_string(), EventId::from("F")).with_system("Flaky System"));
+    TestSystem::new("Flaky System", System::new_sync(flaky)).replace_criteria(Criteria::event("F")).build_blocking(&state_machine);
+    ExecutableManager::set_error_policy(&state_machine, "Flaky".to_string(), ErrorPolicy::retry(2, 1));
+
+    let run_id = ExecutableManager::queue_executable(&state_machine, "Flaky".to_string(), message()).unwrap();
+
+    for expected in [true, false, true, false, true] {
+        state_machine.tick();
+        assert_eq!(is_current(&state_machine, "F"), expected);
+    }
+    assert_eq!(FLAKY_RUNS.load(Ordering::SeqCst), 3);
+
+    state_machine.tick();
+    assert_eq!(ExecutableManager::status(&state_machine, run_id), Some(ExecutableStatus::Done));
+}
+
+struct Count(i32);
+struct Visited;
+
+fn increment_entities(entities: ExecutableEntities, mut world: Unique<World>) -> Option<SystemResult> {
+    let world = world.get_mut_hecs().unwrap();
+    for entity_id in entities.iter() {
+        let entity = *entity_id.get_hecs().unwrap();
+        world.get::<&mut Count>(entity).unwrap().0 += 1;
+        world.insert_one(entity, Visited).unwrap();
+    }
+    None
+}
+
+#[test]
+fn entities_carry_components_between_stages() {
+    init_tracing();
+
+    let state_machine = init();
+    ExecutableManager::set_retention(&state_machine, 2);
+    assert!(state_machine.insert(None, None, None, World::default()).unwrap().is_ok());
+    ExecutableManager::register_executable(&state_machine, Executable::new("Increment".to_string(), EventId::from("Increment")).immediate().with_system("Increment System"));
+    TestSystem::new("Increment System", System::new_sync(increment_entities)).replace_criteria(Criteria::event("Increment")).build_blocking(&state_machine);
+
+    let entities: Vec<_> = [1, 10].into_iter().map(|count| {
+        let mut world = state_machine.resolve::<Unique<World>>(None, None, None, None).unwrap().unwrap();
+        EntityId::new_hecs(world.get_mut_hecs().unwrap().spawn((Count(count),)))
+    }).collect();
+
+    let runs: Vec<_> = entities.iter().map(|entity_id| {
+        ExecutableManager::queue_executable(&state_machine, "Increment>Increment".to_string(), ExecutableMessage::ECS(entity_id.clone())).unwrap()
+    }).collect();
+
+    for _ in 0..3 {
+        state_machine.tick();
+    }
+
+    {
+        let world = state_machine.resolve::<Shared<World>>(None, None, None, None).unwrap().unwrap();
+        let world = world.get_hecs().unwrap();
+        for (entity_id, expected) in entities.iter().zip([3, 12]) {
+            let entity = *entity_id.get_hecs().unwrap();
+            assert_eq!(world.get::<&Count>(entity).unwrap().0, expected);
+            assert!(world.get::<&Visited>(entity).is_ok());
+        }
+        // No entity is reserved per stage
+        assert_eq!(world.len(), 2);
+    }
+
+    // Taking the statuses leaves the entities until the retention ends
+    let finished = ExecutableManager::take_finished(&state_machine);
+    assert!(runs.iter().all(|run_id| finished.get(run_id) == Some(&ExecutableStatus::Done)));
+
+    state_machine.tick();
+    assert_eq!(state_machine.resolve::<Shared<World>>(None, None, None, None).unwrap().unwrap().get_hecs().unwrap().len(), 2);
+
+    state_machine.tick();
+    assert_eq!(state_machine.resolve::<Shared<World>>(None, None, None, None).unwrap().unwrap().get_hecs().unwrap().len(), 0);
+}
+
+#[test]
+fn drops_untaken_runs_after_retention() {
+    init_tracing();
+
+    let state_machine = init();
+    ExecutableManager::set_retention(&state_machine, 1);
+    let run_id = ExecutableManager::queue_executable(&state_machine, "A-Executable".to_string(), message()).unwrap();
+
+    state_machine.tick();
+    state_machine.tick();
+    assert_eq!(ExecutableManager::status(&state_machine, run_id), Some(ExecutableStatus::Done));
+    assert!(ExecutableManager::output(&state_machine, run_id).is_some());
+
+    state_machine.tick();
+    assert_eq!(ExecutableManager::status(&state_machine, run_id), None);
+    assert!(ExecutableManager::output(&state_machine, run_id).is_none());
+    assert!(ExecutableManager::take_finished(&state_machine).is_empty());
+}
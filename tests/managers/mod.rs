@@ -0,0 +1,6 @@
+mod blocker_manager;
+mod delay_manager;
+mod event_mapper_manager;
+mod executable_manager;
+mod time_event_manager;
+mod while_manager;
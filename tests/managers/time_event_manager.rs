@@ -0,0 +1,84 @@
+use aion_reactor::prelude::{CurrentEvents, EventId, KernelBuilder, MockClock, Schedule, ScheduleId, Shared, StateMachine, TimeEventManager, TimeEventRegistry, TimedEvent, Unique};
+
+use std::{sync::Arc, time::Duration};
+
+use crate::init_tracing;
+
+fn is_current(state_machine: &StateMachine, event: &str) -> bool {
+    state_machine.resolve::<Shared<CurrentEvents>>(None, None, None, None).unwrap().unwrap().contains(&EventId::from(event))
+}
+
+#[test]
+fn emits_on_ticks() {
+    init_tracing();
+
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    assert!(TimeEventManager::insert_timed_event(&state_machine, "Beat", TimedEvent::new(Schedule::EveryTicks(2), vec![EventId::from("Beat")])).unwrap().is_none());
+    assert!(TimeEventManager::insert_timed_event(&state_machine, "Once", TimedEvent::new(Schedule::AtTick(3), vec![EventId::from("Once")])).unwrap().is_none());
+
+    let mut beats = Vec::new();
+    let mut onces = Vec::new();
+    for tick in 0..7 {
+        state_machine.tick();
+        if is_current(&state_machine, "Beat") {
+            beats.push(tick);
+        }
+        if is_current(&state_machine, "Once") {
+            onces.push(tick);
+        }
+    }
+
+    assert_eq!(beats, vec![2, 4, 6]);
+    assert_eq!(onces, vec![3]);
+
+    let registry = state_machine.resolve::<Shared<TimeEventRegistry>>(None, None, None, None).unwrap().unwrap();
+    assert!(registry.get(&ScheduleId::from("Once")).is_none());
+    assert_eq!(registry.get(&ScheduleId::from("Beat")).unwrap().fired(), 3);
+}
+
+#[test]
+fn emits_on_mock_clock() {
+    init_tracing();
+
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    let clock = Arc::new(MockClock::default());
+    {
+        let mut registry = state_machine.resolve::<Unique<TimeEventRegistry>>(None, None, None, None).unwrap().unwrap();
+        registry.replace_clock(clock.clone());
+        registry.insert(
+            "Timer",
+            TimedEvent::new(Schedule::Every(Duration::from_secs(3)), vec![EventId::from("Timer")])
+                .with_pause_on("Pause")
+                .with_cancel_on("Cancel")
+        );
+    }
+
+    state_machine.tick();
+    clock.advance(Duration::from_secs(2));
+    state_machine.tick();
+    assert!(!is_current(&state_machine, "Timer"));
+
+    clock.advance(Duration::from_secs(1));
+    state_machine.tick();
+    assert!(is_current(&state_machine, "Timer"));
+
+    // Paused by an event current last tick
+    state_machine.resolve::<Unique<CurrentEvents>>(None, None, None, None).unwrap().unwrap().insert(EventId::from("Pause"));
+    clock.advance(Duration::from_secs(3));
+    state_machine.tick();
+    assert!(!is_current(&state_machine, "Timer"));
+    assert!(state_machine.resolve::<Shared<TimeEventRegistry>>(None, None, None, None).unwrap().unwrap().get(&ScheduleId::from("Timer")).unwrap().is_paused());
+
+    state_machine.resolve::<Unique<TimeEventRegistry>>(None, None, None, None).unwrap().unwrap().resume(&ScheduleId::from("Timer"));
+    clock.advance(Duration::from_secs(3));
+    state_machine.tick();
+    assert!(is_current(&state_machine, "Timer"));
+
+    state_machine.resolve::<Unique<CurrentEvents>>(None, None, None, None).unwrap().unwrap().insert(EventId::from("Cancel"));
+    state_machine.tick();
+    assert!(state_machine.resolve::<Shared<TimeEventRegistry>>(None, None, None, None).unwrap().unwrap().is_empty());
+}
@@ -0,0 +1,67 @@
+use aion_reactor::prelude::{CurrentEvents, EventId, KernelBuilder, LoopId, Shared, StateMachine, Unique, WhileEvent, WhileManager, WhileRegistry, WhileState};
+
+use crate::init_tracing;
+
+fn is_current(state_machine: &StateMachine, event: &str) -> bool {
+    state_machine.resolve::<Shared<CurrentEvents>>(None, None, None, None).unwrap().unwrap().contains(&EventId::from(event))
+}
+
+fn send(state_machine: &StateMachine, event: &str) {
+    state_machine.resolve::<Unique<CurrentEvents>>(None, None, None, None).unwrap().unwrap().insert(EventId::from(event));
+}
+
+#[test]
+fn kettle() {
+    init_tracing();
+
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    assert!(WhileManager::insert_loop(&state_machine, "Kettle", WhileEvent::new("On", "Boiling").with_finally(["Boiled"])).unwrap().is_none());
+
+    state_machine.tick();
+    send(&state_machine, "On");
+    send(&state_machine, "Boiling");
+
+    state_machine.tick();
+    assert!(!is_current(&state_machine, "Boiled"));
+    {
+        let registry = state_machine.resolve::<Shared<WhileRegistry>>(None, None, None, None).unwrap().unwrap();
+        assert_eq!(registry.state(&LoopId::from("Kettle")), Some(WhileState::Active));
+    }
+
+    send(&state_machine, "Boiling");
+    state_machine.tick();
+    assert!(!is_current(&state_machine, "Boiled"));
+
+    // Stopped boiling
+    state_machine.tick();
+    assert!(is_current(&state_machine, "Boiled"));
+
+    state_machine.tick();
+    assert!(!is_current(&state_machine, "Boiled"));
+    let registry = state_machine.resolve::<Shared<WhileRegistry>>(None, None, None, None).unwrap().unwrap();
+    assert_eq!(registry.state(&LoopId::from("Kettle")), Some(WhileState::Idle));
+}
+
+#[test]
+fn tap() {
+    init_tracing();
+
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    WhileManager::insert_loop(&state_machine, "Tap", WhileEvent::until("On", "Off").with_do(["Water"]));
+
+    state_machine.tick();
+    send(&state_machine, "On");
+
+    for _ in 0..3 {
+        state_machine.tick();
+        assert!(is_current(&state_machine, "Water"));
+    }
+
+    send(&state_machine, "Off");
+    state_machine.tick();
+    assert!(!is_current(&state_machine, "Water"));
+}
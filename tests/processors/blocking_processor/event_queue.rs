@@ -0,0 +1,38 @@
+use aion_reactor::prelude::{Criteria, CurrentEvents, EventId, EventManager, EventMode, KernelBuilder, Shared, StateMachine, System, SystemEvent, SystemId, SystemResult, Unique};
+
+use crate::{init_tracing, systems::TestSystem};
+
+fn audit() -> Option<SystemResult> {
+    Some(SystemResult::Event(SystemEvent::WithEvent(EventId::from("Audit"))))
+}
+
+fn counts(current_events: Shared<CurrentEvents>, mut count: Unique<usize>) -> Option<SystemResult> {
+    **count = current_events.count(&EventId::from("Audit"));
+    None
+}
+
+#[test]
+fn queued_events_keep_count() {
+    init_tracing();
+
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    assert_eq!(EventManager::set_event_mode(&state_machine, "Audit", EventMode::Queue), Some(EventMode::Set));
+
+    state_machine.insert(None, None, None, 0usize);
+
+    TestSystem::new("A", System::new_sync(audit)).build_blocking(&state_machine);
+    TestSystem::new("B", System::new_sync(audit)).build_blocking(&state_machine);
+    // `contains` checks still see queued events
+    TestSystem::new("Counts", System::new_sync(counts)).replace_criteria(Criteria::event("Audit")).build_blocking(&state_machine);
+
+    state_machine.tick();
+    state_machine.tick();
+    assert_eq!(**state_machine.resolve::<Unique<usize>>(None, None, None, None).unwrap().unwrap(), 2);
+
+    let current_events = state_machine.resolve::<Shared<CurrentEvents>>(None, None, None, None).unwrap().unwrap();
+    let mut emitters = current_events.emitters(&EventId::from("Audit")).map(|emitter| emitter.cloned().unwrap()).collect::<Vec<_>>();
+    emitters.sort_by_key(|emitter| emitter.to_string());
+    assert_eq!(emitters, vec![SystemId::from("A"), SystemId::from("B")]);
+}
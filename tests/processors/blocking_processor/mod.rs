@@ -1,3 +1,9 @@
 mod runs_one;
 mod fixed_ordering;
-mod async_works;
\ No newline at end of file
+mod async_works;
+mod panic_isolation;
+mod timeout;
+mod run_conditions;
+mod resource_criteria;
+mod typed_events;
+mod event_queue;
@@ -0,0 +1,53 @@
+use aion_reactor::prelude::{KernelBuilder, PanicPolicy, ResourceId, Shared, StateMachine, StoredSystem, System, SystemId, SystemResult, Unique};
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{init_tracing, systems::TestSystem};
+
+static PANICS: AtomicUsize = AtomicUsize::new(0);
+static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+fn panics(mut number: Unique<i32>) -> Option<SystemResult> {
+    **number += 1;
+    PANICS.fetch_add(1, Ordering::SeqCst);
+    panic!("Foo");
+}
+
+fn runs() -> Option<SystemResult> {
+    RUNS.fetch_add(1, Ordering::SeqCst);
+    None
+}
+
+#[test]
+fn quarantines_after_panics() {
+    init_tracing();
+
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    state_machine.insert(None, None, None, 1);
+
+    TestSystem::new("Foo", System::new_sync(panics)).replace_panic_policy(PanicPolicy::Quarantine(2)).build_blocking(&state_machine);
+    TestSystem::new("Bar", System::new_sync(runs)).build_blocking(&state_machine);
+
+    state_machine.tick();
+    assert_eq!(PANICS.load(Ordering::SeqCst), 1);
+    assert_eq!(RUNS.load(Ordering::SeqCst), 1);
+
+    state_machine.tick();
+    assert_eq!(PANICS.load(Ordering::SeqCst), 2);
+    assert_eq!(RUNS.load(Ordering::SeqCst), 2);
+
+    // Quarantined
+    state_machine.tick();
+    assert_eq!(PANICS.load(Ordering::SeqCst), 2);
+    assert_eq!(RUNS.load(Ordering::SeqCst), 3);
+
+    // Accesses were released when it unwound
+    assert_eq!(**state_machine.resolve::<Unique<i32>>(None, None, None, None).unwrap().unwrap(), 3);
+
+    let stored_system = state_machine.resolve::<Shared<StoredSystem>>(None, Some(&ResourceId::from_labelled_heap(SystemId::from("Foo").into_id())), None, None).unwrap().unwrap();
+    assert!(stored_system.has_system());
+    assert!(stored_system.is_disabled());
+    assert_eq!(stored_system.panics(), 2);
+}
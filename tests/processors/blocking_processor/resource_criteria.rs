@@ -0,0 +1,60 @@
+use aion_reactor::prelude::{Criteria, KernelBuilder, Shared, StateMachine, System, SystemResult, Unique};
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{init_tracing, systems::TestSystem};
+
+static DEAD: AtomicUsize = AtomicUsize::new(0);
+
+fn damage(mut health: Unique<i32>) -> Option<SystemResult> {
+    **health -= 1;
+    None
+}
+
+fn dead() -> Option<SystemResult> {
+    DEAD.fetch_add(1, Ordering::SeqCst);
+    None
+}
+
+#[test]
+fn gates_on_resources() {
+    init_tracing();
+
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    state_machine.insert(None, None, None, 1);
+
+    TestSystem::new("Damage", System::new_sync(damage)).replace_criteria(Criteria::resources(|health: Shared<i32>| **health >= 0)).build_blocking(&state_machine);
+    TestSystem::new("Dead", System::new_sync(dead)).replace_criteria(Criteria::resources(|health: Shared<i32>| **health < 0)).build_blocking(&state_machine);
+
+    // 1 -> 0 -> -1
+    state_machine.tick();
+    state_machine.tick();
+    assert_eq!(DEAD.load(Ordering::SeqCst), 0);
+
+    // Gated on the same tick the resource changed, no observer event needed
+    state_machine.tick();
+    state_machine.tick();
+    assert_eq!(DEAD.load(Ordering::SeqCst), 2);
+    assert_eq!(**state_machine.resolve::<Unique<i32>>(None, None, None, None).unwrap().unwrap(), -1);
+}
+
+#[test]
+fn missing_resources_fail_criteria() {
+    init_tracing();
+
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    fn runs() -> Option<SystemResult> {
+        RUNS.fetch_add(1, Ordering::SeqCst);
+        None
+    }
+
+    TestSystem::new("Runs", System::new_sync(runs)).replace_criteria(Criteria::new(|_| true).with_resources(|_: Shared<u64>| true)).build_blocking(&state_machine);
+
+    state_machine.tick();
+    assert_eq!(RUNS.load(Ordering::SeqCst), 0);
+}
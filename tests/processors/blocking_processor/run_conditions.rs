@@ -0,0 +1,54 @@
+use aion_reactor::prelude::{Cooldown, KernelBuilder, ProcessorSystemRegistry, ResourceId, RunConditions, Shared, StateMachine, StoredSystem, System, SystemId, SystemResult};
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{init_tracing, systems::TestSystem};
+
+static EVERY: AtomicUsize = AtomicUsize::new(0);
+static ONCE: AtomicUsize = AtomicUsize::new(0);
+static LIMITED: AtomicUsize = AtomicUsize::new(0);
+
+fn every() -> Option<SystemResult> {
+    EVERY.fetch_add(1, Ordering::SeqCst);
+    None
+}
+
+fn once() -> Option<SystemResult> {
+    ONCE.fetch_add(1, Ordering::SeqCst);
+    None
+}
+
+fn limited() -> Option<SystemResult> {
+    LIMITED.fetch_add(1, Ordering::SeqCst);
+    None
+}
+
+#[test]
+fn limits_runs() {
+    init_tracing();
+
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    TestSystem::new("Every", System::new_sync(every)).replace_run_conditions(RunConditions::default().with_every_ticks(2)).build_blocking(&state_machine);
+    TestSystem::new("Once", System::new_sync(once)).replace_run_conditions(RunConditions::default().once()).build_blocking(&state_machine);
+    TestSystem::new("Limited", System::new_sync(limited)).replace_run_conditions(RunConditions::default().with_max_runs(2).with_cooldown(Cooldown::Ticks(1))).build_blocking(&state_machine);
+
+    for _ in 0..6 {
+        state_machine.tick();
+    }
+
+    // ticks 0, 2 & 4
+    assert_eq!(EVERY.load(Ordering::SeqCst), 3);
+    assert_eq!(ONCE.load(Ordering::SeqCst), 1);
+    // ticks 0 & 2
+    assert_eq!(LIMITED.load(Ordering::SeqCst), 2);
+
+    let system_registry = state_machine.resolve::<Shared<ProcessorSystemRegistry>>(None, None, None, None).unwrap().unwrap();
+    assert!(system_registry.0.get(&SystemId::from("Once")).is_none());
+    assert!(system_registry.0.get(&SystemId::from("Limited")).is_some());
+    
+    let stored_system = state_machine.resolve::<Shared<StoredSystem>>(None, Some(&ResourceId::from_labelled_heap(SystemId::from("Limited").into_id())), None, None).unwrap().unwrap();
+    assert_eq!(stored_system.run_state().runs(), 2);
+    assert_eq!(stored_system.run_state().last_tick(), Some(2));
+}
@@ -0,0 +1,48 @@
+use aion_reactor::prelude::{Criteria, EventId, KernelBuilder, ResourceId, StateMachine, StoredSystem, System, SystemId, SystemResult, Unique};
+
+use std::{sync::atomic::{AtomicUsize, Ordering}, time::{Duration, Instant}};
+
+use crate::{init_tracing, systems::TestSystem};
+
+static STARTED: AtomicUsize = AtomicUsize::new(0);
+static FINISHED: AtomicUsize = AtomicUsize::new(0);
+static REACTED: AtomicUsize = AtomicUsize::new(0);
+
+async fn never_finishes() -> Option<SystemResult> {
+    STARTED.fetch_add(1, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_secs(10)).await;
+    FINISHED.fetch_add(1, Ordering::SeqCst);
+    None
+}
+
+fn reacts() -> Option<SystemResult> {
+    REACTED.fetch_add(1, Ordering::SeqCst);
+    None
+}
+
+#[test]
+fn cancels_after_timeout() {
+    init_tracing();
+
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    let timed_out = EventId::timed_out(&SystemId::from("Foo"));
+    TestSystem::new("Foo", System::new_async(never_finishes)).replace_timeout(Duration::from_millis(50)).build_blocking(&state_machine);
+    TestSystem::new("Bar", System::new_sync(reacts)).replace_criteria(Criteria::new(move |events| events.contains(&timed_out))).build_blocking(&state_machine);
+
+    let start = Instant::now();
+    state_machine.tick();
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(REACTED.load(Ordering::SeqCst), 0);
+
+    // Cancelled part way through
+    assert_eq!(STARTED.load(Ordering::SeqCst), 1);
+    assert_eq!(FINISHED.load(Ordering::SeqCst), 0);
+
+    state_machine.tick();
+    assert_eq!(REACTED.load(Ordering::SeqCst), 1);
+
+    let stored_system = state_machine.resolve::<Unique<StoredSystem>>(None, Some(&ResourceId::from_labelled_heap(SystemId::from("Foo").into_id())), None, None).unwrap().unwrap();
+    assert!(stored_system.has_system());
+}
@@ -0,0 +1,53 @@
+use aion_reactor::prelude::{Criteria, EventId, EventManager, EventReader, EventWriter, KernelBuilder, StateMachine, System, SystemResult, Unique};
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{init_tracing, systems::TestSystem};
+
+static WRITES: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, PartialEq, Clone)]
+struct Damage(i32);
+
+fn writes(mut damage: EventWriter<Damage>) -> Option<SystemResult> {
+    WRITES.fetch_add(1, Ordering::SeqCst);
+    damage.send(Damage(1));
+    damage.send(Damage(2));
+    None
+}
+
+fn reads(damage: EventReader<Damage>, mut total: Unique<i32>) -> Option<SystemResult> {
+    **total += damage.read().map(|damage| damage.0).sum::<i32>();
+    None
+}
+
+#[test]
+fn payloads_arrive_next_tick() {
+    init_tracing();
+
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    assert_eq!(EventManager::register_event::<Damage>(&state_machine), Some(true));
+    assert_eq!(EventManager::register_event::<Damage>(&state_machine), Some(false));
+
+    state_machine.insert(None, None, None, 0);
+
+    TestSystem::new("Writes", System::new_sync(writes)).replace_criteria(Criteria::new(|events| events.is_empty())).build_blocking(&state_machine);
+    // Id-only criteria see the event by its type name
+    TestSystem::new("Reads", System::new_sync(reads)).replace_criteria(Criteria::event(EventId::of::<Damage>())).build_blocking(&state_machine);
+
+    // Writes
+    state_machine.tick();
+    assert_eq!(**state_machine.resolve::<Unique<i32>>(None, None, None, None).unwrap().unwrap(), 0);
+    assert_eq!(WRITES.load(Ordering::SeqCst), 1);
+
+    // Reads 1 + 2
+    state_machine.tick();
+    assert_eq!(**state_machine.resolve::<Unique<i32>>(None, None, None, None).unwrap().unwrap(), 3);
+
+    // The reader's system event is current, so `Writes` doesn't run again and there is nothing to read
+    state_machine.tick();
+    assert_eq!(**state_machine.resolve::<Unique<i32>>(None, None, None, None).unwrap().unwrap(), 3);
+    assert_eq!(WRITES.load(Ordering::SeqCst), 1);
+}
@@ -1,5 +1,6 @@
 mod blocking_processor;
 mod non_blocking_processor;
+mod read_only_processor;
 
 // metadata:
 // -- ordering (fixed_ordering, &)
@@ -0,0 +1,77 @@
+use aion_reactor::prelude::{Criteria, EventId, FinishNonBlockingProcessor, KernelBuilder, NextEvents, Progress, ResourceId, StateMachine, StoredSystem, System, SystemId, SystemResult, TaskHandle, Unique};
+
+use std::{sync::atomic::{AtomicUsize, Ordering}, time::Duration};
+
+use crate::{init_tracing, systems::TestSystem};
+
+static FINISHED: AtomicUsize = AtomicUsize::new(0);
+static REACTED: AtomicUsize = AtomicUsize::new(0);
+
+async fn sleeps() -> Option<SystemResult> {
+    tokio::time::sleep(Duration::from_secs(10)).await;
+    FINISHED.fetch_add(1, Ordering::SeqCst);
+    None
+}
+
+fn spins(task: TaskHandle) -> Option<SystemResult> {
+    let mut steps = 0;
+    while !task.is_cancelled() {
+        steps += 1;
+        task.report(Progress::new(0.5).with_message(format!("{steps} steps")));
+        std::thread::sleep(Duration::from_millis(5));
+    }
+
+    None
+}
+
+fn reacts() -> Option<SystemResult> {
+    REACTED.fetch_add(1, Ordering::SeqCst);
+    None
+}
+
+#[test]
+fn cancels_running_systems() {
+    init_tracing();
+
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    let (foo, bar) = (SystemId::from("Foo"), SystemId::from("Bar"));
+    TestSystem::new("Foo", System::new_async(sleeps)).replace_criteria(Criteria::event("Go")).build_non_blocking(&state_machine);
+    TestSystem::new("Bar", System::new_sync(spins)).replace_criteria(Criteria::event("Go")).build_non_blocking(&state_machine);
+
+    let (foo_cancelled, bar_cancelled) = (EventId::cancelled(&foo), EventId::cancelled(&bar));
+    TestSystem::new("Baz", System::new_sync(reacts))
+        .replace_criteria(Criteria::new(move |events| events.contains(&foo_cancelled) && events.contains(&bar_cancelled)))
+        .build_blocking(&state_machine);
+
+    state_machine.resolve::<Unique<NextEvents>>(None, None, None, None).unwrap().unwrap().emit("Go", None);
+    state_machine.tick();
+    std::thread::sleep(Duration::from_millis(100));
+
+    let running = FinishNonBlockingProcessor::running(&state_machine);
+    assert_eq!(running.len(), 2);
+    assert!(running.iter().all(|task| task.started_tick == running[0].started_tick && !task.is_cancelled()));
+
+    let spinning = running.iter().find(|task| task.system_id == bar).unwrap();
+    assert!(spinning.elapsed() >= Duration::from_millis(100));
+    assert_eq!(spinning.progress().unwrap().fraction, 0.5);
+
+    assert!(!FinishNonBlockingProcessor::cancel(&state_machine, &SystemId::from("Baz")));
+    assert!(FinishNonBlockingProcessor::cancel(&state_machine, &foo));
+    assert!(FinishNonBlockingProcessor::cancel(&state_machine, &bar));
+    std::thread::sleep(Duration::from_millis(100));
+
+    // Finished & their cancelled events emitted
+    state_machine.tick();
+    assert!(FinishNonBlockingProcessor::running(&state_machine).is_empty());
+    assert_eq!(FINISHED.load(Ordering::SeqCst), 0);
+
+    state_machine.tick();
+    assert_eq!(REACTED.load(Ordering::SeqCst), 1);
+
+    for name in ["Foo", "Bar"] {
+        let stored_system = state_machine.resolve::<Unique<StoredSystem>>(None, Some(&ResourceId::from_labelled_heap(SystemId::from(name).into_id())), None, None).unwrap().unwrap();
+        assert!(stored_system.has_system());
+    }
+}
@@ -1,2 +1,4 @@
 mod runs_one;
-mod async_works;
\ No newline at end of file
+mod async_works;
+mod cancellation;
+mod panic_recovery;
\ No newline at end of file
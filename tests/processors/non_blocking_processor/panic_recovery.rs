@@ -0,0 +1,78 @@
+use aion_reactor::prelude::{BackgroundProcessorSystemRegistry, CurrentEvents, EventId, KernelBuilder, ResourceId, Shared, StateMachine, StoredSystem, System, SystemId, SystemResult, Unique};
+
+use std::{sync::atomic::{AtomicUsize, Ordering}, time::Duration};
+
+use crate::{init_tracing, systems::TestSystem};
+
+static SYNC_PANICS: AtomicUsize = AtomicUsize::new(0);
+static ASYNC_PANICS: AtomicUsize = AtomicUsize::new(0);
+
+fn panics(mut number: Unique<i32>) -> Option<SystemResult> {
+    **number += 1;
+    SYNC_PANICS.fetch_add(1, Ordering::SeqCst);
+    panic!("Foo");
+}
+
+async fn panics_async() -> Option<SystemResult> {
+    ASYNC_PANICS.fetch_add(1, Ordering::SeqCst);
+    panic!("Bar");
+}
+
+fn resource_id(name: &str) -> ResourceId {
+    ResourceId::from_labelled_heap(SystemId::from(name).into_id())
+}
+
+fn has_system(state_machine: &StateMachine, name: &str) -> bool {
+    state_machine.resolve::<Shared<StoredSystem>>(None, Some(&resource_id(name)), None, None).unwrap().unwrap().has_system()
+}
+
+fn tick(state_machine: &StateMachine) {
+    state_machine.tick();
+    std::thread::sleep(Duration::from_millis(100));
+}
+
+#[test]
+fn recovers_or_disables_panicked_systems() {
+    init_tracing();
+
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    state_machine.insert(None, None, None, 1);
+
+    TestSystem::from_stored("Foo", StoredSystem::from_factory(|| System::new_sync(panics))).build_non_blocking(&state_machine);
+    TestSystem::from_stored("Bar", StoredSystem::new(System::new_async(panics_async))).build_non_blocking(&state_machine);
+
+    tick(&state_machine);
+    assert_eq!(SYNC_PANICS.load(Ordering::SeqCst), 1);
+    assert_eq!(ASYNC_PANICS.load(Ordering::SeqCst), 1);
+
+    // Both are finished, only the one with a factory is restarted
+    tick(&state_machine);
+    assert_eq!(SYNC_PANICS.load(Ordering::SeqCst), 2);
+    assert_eq!(ASYNC_PANICS.load(Ordering::SeqCst), 1);
+    {
+        let current_events = state_machine.resolve::<Shared<CurrentEvents>>(None, None, None, None).unwrap().unwrap();
+        assert!(current_events.contains(&EventId::errored(&SystemId::from("Foo"))));
+        assert!(current_events.contains(&EventId::errored(&SystemId::from("Bar"))));
+    }
+
+    let bar = SystemId::from("Bar");
+    assert!(!has_system(&state_machine, "Bar"));
+    assert!(state_machine.resolve::<Shared<BackgroundProcessorSystemRegistry>>(None, None, None, None).unwrap().unwrap().is_disabled(&bar));
+
+    // Rebuilt every time it panics
+    tick(&state_machine);
+    assert_eq!(SYNC_PANICS.load(Ordering::SeqCst), 3);
+    assert_eq!(ASYNC_PANICS.load(Ordering::SeqCst), 1);
+
+    // Reservations were released
+    assert_eq!(**state_machine.resolve::<Unique<i32>>(None, None, None, None).unwrap().unwrap(), 4);
+
+    // Re-inserting enables it
+    TestSystem::from_stored("Bar", StoredSystem::new(System::new_async(panics_async))).build_non_blocking(&state_machine);
+    assert!(!state_machine.resolve::<Shared<BackgroundProcessorSystemRegistry>>(None, None, None, None).unwrap().unwrap().is_disabled(&bar));
+
+    tick(&state_machine);
+    assert_eq!(ASYNC_PANICS.load(Ordering::SeqCst), 2);
+}
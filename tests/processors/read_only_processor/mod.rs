@@ -0,0 +1 @@
+mod runs_all;
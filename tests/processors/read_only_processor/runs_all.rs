@@ -0,0 +1,57 @@
+use aion_reactor::prelude::{KernelBuilder, StateMachine, System, SystemResult};
+
+use std::{sync::atomic::{AtomicUsize, Ordering}, time::{Duration, Instant}};
+
+use crate::{init_tracing, systems::TestSystem};
+
+static OUTPUT: AtomicUsize = AtomicUsize::new(0);
+
+fn no_input() -> Option<SystemResult> {
+    OUTPUT.fetch_add(1, Ordering::SeqCst);
+    None
+}
+
+#[test]
+fn runs_uneven_chunks() {
+    init_tracing();
+
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(2).init(&state_machine);
+
+    // 5 systems over 2 threads previously dropped the remainder
+    for name in ["A", "B", "C", "D", "E"] {
+        TestSystem::new(name, System::new_sync(no_input)).build_read_only(&state_machine);
+    }
+
+    state_machine.tick();
+    assert_eq!(OUTPUT.load(Ordering::SeqCst), 5);
+
+    state_machine.tick();
+    assert_eq!(OUTPUT.load(Ordering::SeqCst), 10);
+}
+
+static SLEPT: AtomicUsize = AtomicUsize::new(0);
+
+async fn sleeps() -> Option<SystemResult> {
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    SLEPT.fetch_add(1, Ordering::SeqCst);
+    None
+}
+
+#[test]
+fn polls_async_systems_on_the_workers() {
+    init_tracing();
+
+    let state_machine = StateMachine::new();
+    KernelBuilder::full(1).init(&state_machine);
+
+    for name in ["A", "B", "C"] {
+        TestSystem::new(name, System::new_async(sleeps)).build_read_only(&state_machine);
+    }
+
+    // A single worker interleaves the sleeps rather than blocking on each in turn
+    let started = Instant::now();
+    state_machine.tick();
+    assert_eq!(SLEPT.load(Ordering::SeqCst), 3);
+    assert!(started.elapsed() < Duration::from_millis(250));
+}
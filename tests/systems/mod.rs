@@ -0,0 +1,68 @@
+use aion_reactor::prelude::{BlockingProcessor, Criteria, FinishNonBlockingProcessor, PanicPolicy, ReadOnlyProcessor, ResourceId, RunConditions, SchedulerOrdering, StateMachine, StoredSystem, StoredSystemMetadata, System, SystemId, SystemMetadata};
+
+use std::time::Duration;
+
+/// Registers a system under its label without an associated event, for tests needing metadata `SystemBuilder` doesn't cover
+pub struct TestSystem {
+    system_id: SystemId,
+    stored_system: StoredSystem,
+    system_metadata: SystemMetadata,
+}
+
+impl TestSystem {
+    pub fn new(name: &str, system: System) -> Self {
+        Self::from_stored(name, StoredSystem::new(system))
+    }
+
+    pub fn from_stored(name: &str, stored_system: StoredSystem) -> Self {
+        let system_id = SystemId::from(name);
+        let resource_id = ResourceId::from_labelled_heap(system_id.clone().into_id());
+
+        Self {
+            system_id,
+            stored_system,
+            system_metadata: SystemMetadata::new(
+                StoredSystemMetadata::new(resource_id, None, None),
+                Criteria::always(),
+                SchedulerOrdering::default()
+            ),
+        }
+    }
+
+    pub fn replace_criteria(mut self, criteria: Criteria) -> Self {
+        self.system_metadata.replace_criteria(criteria);
+        self
+    }
+
+    pub fn insert_ordering(mut self, ordering: SchedulerOrdering) -> Self {
+        self.system_metadata.insert_ordering(ordering);
+        self
+    }
+
+    pub fn replace_timeout(mut self, timeout: Duration) -> Self {
+        self.system_metadata.replace_timeout(Some(timeout));
+        self
+    }
+
+    pub fn replace_panic_policy(mut self, panic_policy: PanicPolicy) -> Self {
+        self.system_metadata.replace_panic_policy(panic_policy);
+        self
+    }
+
+    pub fn replace_run_conditions(mut self, run_conditions: RunConditions) -> Self {
+        self.system_metadata.replace_run_conditions(run_conditions);
+        self
+    }
+
+    pub fn build_blocking(self, state_machine: &StateMachine) {
+        assert!(BlockingProcessor::insert_system(state_machine, self.system_id, self.system_metadata, self.stored_system).is_some());
+    }
+
+    pub fn build_non_blocking(self, state_machine: &StateMachine) {
+        assert!(FinishNonBlockingProcessor::insert_system(state_machine, self.system_id, self.system_metadata, self.stored_system).is_some());
+    }
+
+    pub fn build_read_only(self, state_machine: &StateMachine) {
+        assert!(ReadOnlyProcessor::insert_system(state_machine, self.system_id, self.system_metadata, self.stored_system).is_some());
+    }
+}